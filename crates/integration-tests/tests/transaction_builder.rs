@@ -268,6 +268,10 @@ async fn test_upgrade() -> Result<()> {
 
     let package_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/", "packages/test_publish_v2");
     let (bytecode, package_digest) = myso.build_package(package_dir.as_ref()).unwrap();
+    assert_eq!(
+        myso_transaction_builder::package_digest(&bytecode.modules, &bytecode.dependencies),
+        package_digest
+    );
     let upgrade_cap = builder.object(ObjectInput::new(*upgrade_cap.object_id()));
     let policy = builder.pure(&0u8);
     let package_digest = builder.pure(&package_digest);
@@ -84,6 +84,23 @@ pub mod multisig;
 #[doc(inline)]
 pub use multisig::UserSignatureVerifier;
 
+#[cfg(any(
+    feature = "ed25519",
+    feature = "secp256r1",
+    feature = "secp256k1",
+    feature = "zklogin"
+))]
+#[cfg_attr(
+    doc_cfg,
+    doc(cfg(any(
+        feature = "ed25519",
+        feature = "secp256r1",
+        feature = "secp256k1",
+        feature = "zklogin"
+    )))
+)]
+pub mod personal_message;
+
 /// Interface for signing user transactions and messages in MySo
 ///
 /// # Note
@@ -2,6 +2,7 @@ use crate::SignatureError;
 use crate::Verifier;
 use myso_sdk_types::MultisigAggregatedSignature;
 use myso_sdk_types::MultisigCommittee;
+use myso_sdk_types::MultisigMember;
 use myso_sdk_types::MultisigMemberPublicKey;
 use myso_sdk_types::MultisigMemberSignature;
 use myso_sdk_types::UserSignature;
@@ -271,51 +272,183 @@ impl Verifier<UserSignature> for UserSignatureVerifier {
     }
 }
 
+/// The in-progress state of aggregating a multisig signature.
+///
+/// Unlike [`MultisigAggregator`], a `PartialMultisig` does not hold onto a verifier and can be
+/// serialized, making it possible to collect member signatures over time, e.g. from different
+/// devices or services, persisting the partial state between each contribution. Each signature
+/// is checked against the committee and the message when it is added.
+///
+/// Deserializing a `PartialMultisig` checks that its signatures belong to members of its
+/// committee, but not that they are valid. Use [`PartialMultisig::verify_signatures`] or
+/// [`MultisigAggregator::from_partial`] to verify them before trusting state loaded from storage.
 #[derive(Debug, Clone, PartialEq)]
-pub struct MultisigAggregator {
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize),
+    serde(try_from = "PartialMultisigData")
+)]
+pub struct PartialMultisig {
     committee: MultisigCommittee,
+    /// The message, i.e. the signing digest, that members are signing.
+    message: Vec<u8>,
+    /// Signatures that have been collected, keyed by the index of the member in the committee.
     signatures: std::collections::BTreeMap<usize, MultisigMemberSignature>,
-    signed_weight: u16,
+    /// Whether the aggregated signature should be produced using the legacy roaring bitmap
+    /// encoding.
+    #[cfg_attr(feature = "serde", serde(default))]
+    legacy_bitmap: bool,
+}
+
+/// The unvalidated serialized form of a [`PartialMultisig`].
+#[cfg(feature = "serde")]
+#[derive(serde_derive::Deserialize)]
+struct PartialMultisigData {
+    committee: MultisigCommittee,
     message: Vec<u8>,
-    verifier: MultisigVerifier,
+    signatures: std::collections::BTreeMap<usize, MultisigMemberSignature>,
+    #[serde(default)]
+    legacy_bitmap: bool,
 }
 
-impl MultisigAggregator {
+#[cfg(feature = "serde")]
+impl TryFrom<PartialMultisigData> for PartialMultisig {
+    type Error = SignatureError;
+
+    fn try_from(data: PartialMultisigData) -> Result<Self, Self::Error> {
+        let PartialMultisigData {
+            committee,
+            message,
+            signatures,
+            legacy_bitmap,
+        } = data;
+
+        if !committee.is_valid() {
+            return Err(SignatureError::from_source("invalid multisig committee"));
+        }
+
+        if signatures
+            .keys()
+            .any(|member_idx| *member_idx >= committee.members().len())
+        {
+            return Err(SignatureError::from_source(
+                "signature does not belong to committee member",
+            ));
+        }
+
+        Ok(Self {
+            committee,
+            message,
+            signatures,
+            legacy_bitmap,
+        })
+    }
+}
+
+impl PartialMultisig {
     pub fn new_with_transaction(
         committee: MultisigCommittee,
         transaction: &myso_sdk_types::Transaction,
     ) -> Self {
-        Self {
-            committee,
-            signatures: Default::default(),
-            signed_weight: 0,
-            message: transaction.signing_digest().to_vec(),
-            verifier: Default::default(),
-        }
+        Self::new(committee, transaction.signing_digest().to_vec())
     }
 
     pub fn new_with_message(
         committee: MultisigCommittee,
         message: &myso_sdk_types::PersonalMessage<'_>,
     ) -> Self {
+        Self::new(committee, message.signing_digest().to_vec())
+    }
+
+    fn new(committee: MultisigCommittee, message: Vec<u8>) -> Self {
         Self {
             committee,
+            message,
             signatures: Default::default(),
-            signed_weight: 0,
-            message: message.signing_digest().to_vec(),
-            verifier: Default::default(),
+            legacy_bitmap: false,
         }
     }
 
-    pub fn verifier(&self) -> &MultisigVerifier {
-        &self.verifier
+    /// Configure whether the aggregated signature should be produced using the legacy roaring
+    /// bitmap encoding, for compatibility with consumers which only understand that format.
+    ///
+    /// The legacy encoding does not support committees with zklogin or passkey members.
+    pub fn with_legacy_bitmap(mut self, legacy_bitmap: bool) -> Self {
+        self.legacy_bitmap = legacy_bitmap;
+        self
     }
 
-    pub fn verifier_mut(&mut self) -> &mut MultisigVerifier {
-        &mut self.verifier
+    pub fn committee(&self) -> &MultisigCommittee {
+        &self.committee
     }
 
-    pub fn add_signature(&mut self, signature: UserSignature) -> Result<(), SignatureError> {
+    /// The message, i.e. the signing digest, that members are signing.
+    pub fn message(&self) -> &[u8] {
+        &self.message
+    }
+
+    /// The total weight of the members who have provided signatures so far.
+    pub fn signed_weight(&self) -> u16 {
+        self.signatures
+            .keys()
+            .map(|member_idx| self.committee.members()[*member_idx].weight() as u16)
+            .sum()
+    }
+
+    /// The weight required for the aggregated signature to be valid.
+    pub fn threshold(&self) -> u16 {
+        self.committee.threshold()
+    }
+
+    /// The additional weight needed before the threshold is reached.
+    pub fn remaining_weight(&self) -> u16 {
+        self.threshold().saturating_sub(self.signed_weight())
+    }
+
+    /// Returns `true` if enough signatures have been collected to reach the threshold.
+    pub fn is_complete(&self) -> bool {
+        self.signed_weight() >= self.threshold()
+    }
+
+    /// Iterate over the committee members who have provided a signature.
+    pub fn signers(&self) -> impl Iterator<Item = &MultisigMember> {
+        self.signatures
+            .keys()
+            .map(|member_idx| &self.committee.members()[*member_idx])
+    }
+
+    /// Iterate over the committee members who have not yet provided a signature.
+    pub fn pending_members(&self) -> impl Iterator<Item = &MultisigMember> {
+        self.committee
+            .members()
+            .iter()
+            .enumerate()
+            .filter(|(member_idx, _)| !self.signatures.contains_key(member_idx))
+            .map(|(_, member)| member)
+    }
+
+    /// Verify the collected signatures with the provided verifier.
+    ///
+    /// Signatures are verified as they are added, but this should be used to check signatures
+    /// which were loaded from an untrusted source.
+    pub fn verify_signatures(&self, verifier: &MultisigVerifier) -> Result<(), SignatureError> {
+        for (member_idx, signature) in &self.signatures {
+            let member = self.committee.members().get(*member_idx).ok_or_else(|| {
+                SignatureError::from_source("signature does not belong to committee member")
+            })?;
+            verifier.verify_member_signature(&self.message, member.public_key(), signature)?;
+        }
+        Ok(())
+    }
+
+    /// Add a signature from a committee member, verifying it with the provided verifier.
+    ///
+    /// Returns the total signed weight after the signature has been added.
+    pub fn add_signature(
+        &mut self,
+        verifier: &MultisigVerifier,
+        signature: UserSignature,
+    ) -> Result<u16, SignatureError> {
         use std::collections::btree_map::Entry;
 
         let (public_key, signature) = multisig_pubkey_and_signature_from_user_signature(signature)?;
@@ -330,8 +463,7 @@ impl MultisigAggregator {
                 )
             })?;
 
-        self.verifier()
-            .verify_member_signature(&self.message, &public_key, &signature)?;
+        verifier.verify_member_signature(&self.message, &public_key, &signature)?;
 
         match self.signatures.entry(member_idx) {
             Entry::Vacant(v) => {
@@ -344,13 +476,26 @@ impl MultisigAggregator {
             }
         }
 
-        self.signed_weight += self.committee.members()[member_idx].weight() as u16;
+        Ok(self.signed_weight())
+    }
 
-        Ok(())
+    /// Add a base64 encoded `UserSignature` from a committee member, verifying it with the
+    /// provided verifier.
+    ///
+    /// Returns the total signed weight after the signature has been added.
+    pub fn add_base64_signature(
+        &mut self,
+        verifier: &MultisigVerifier,
+        signature: &str,
+    ) -> Result<u16, SignatureError> {
+        let signature =
+            UserSignature::from_base64(signature).map_err(SignatureError::from_source)?;
+        self.add_signature(verifier, signature)
     }
 
+    /// Produce the aggregated signature from the collected member signatures.
     pub fn finish(&self) -> Result<MultisigAggregatedSignature, SignatureError> {
-        if self.signed_weight < self.committee.threshold() {
+        if !self.is_complete() {
             return Err(SignatureError::from_source(
                 "insufficient signature weight to reach threshold",
             ));
@@ -365,11 +510,96 @@ impl MultisigAggregator {
             },
         );
 
-        Ok(MultisigAggregatedSignature::new(
-            self.committee.clone(),
-            signatures,
-            bitmap,
-        ))
+        let mut aggregated =
+            MultisigAggregatedSignature::new(self.committee.clone(), signatures, bitmap);
+
+        if self.legacy_bitmap {
+            if self.committee.members().iter().any(|member| {
+                matches!(
+                    member.public_key(),
+                    MultisigMemberPublicKey::ZkLogin(_) | MultisigMemberPublicKey::Passkey(_)
+                )
+            }) {
+                return Err(SignatureError::from_source(
+                    "legacy multisig does not support zklogin or passkey members",
+                ));
+            }
+
+            aggregated.with_legacy_bitmap(
+                self.signatures
+                    .keys()
+                    .map(|member_idx| *member_idx as u32)
+                    .collect(),
+            );
+        }
+
+        Ok(aggregated)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultisigAggregator {
+    partial: PartialMultisig,
+    verifier: MultisigVerifier,
+}
+
+impl MultisigAggregator {
+    pub fn new_with_transaction(
+        committee: MultisigCommittee,
+        transaction: &myso_sdk_types::Transaction,
+    ) -> Self {
+        Self {
+            partial: PartialMultisig::new_with_transaction(committee, transaction),
+            verifier: Default::default(),
+        }
+    }
+
+    pub fn new_with_message(
+        committee: MultisigCommittee,
+        message: &myso_sdk_types::PersonalMessage<'_>,
+    ) -> Self {
+        Self {
+            partial: PartialMultisig::new_with_message(committee, message),
+            verifier: Default::default(),
+        }
+    }
+
+    /// Resume aggregation from a previously saved `PartialMultisig`.
+    ///
+    /// The signatures already collected are verified with the provided verifier.
+    pub fn from_partial(
+        partial: PartialMultisig,
+        verifier: MultisigVerifier,
+    ) -> Result<Self, SignatureError> {
+        partial.verify_signatures(&verifier)?;
+        Ok(Self { partial, verifier })
+    }
+
+    /// The in-progress aggregation state, which can be serialized and resumed later.
+    pub fn partial(&self) -> &PartialMultisig {
+        &self.partial
+    }
+
+    pub fn into_partial(self) -> PartialMultisig {
+        self.partial
+    }
+
+    pub fn verifier(&self) -> &MultisigVerifier {
+        &self.verifier
+    }
+
+    pub fn verifier_mut(&mut self) -> &mut MultisigVerifier {
+        &mut self.verifier
+    }
+
+    pub fn add_signature(&mut self, signature: UserSignature) -> Result<(), SignatureError> {
+        self.partial
+            .add_signature(&self.verifier, signature)
+            .map(|_| ())
+    }
+
+    pub fn finish(&self) -> Result<MultisigAggregatedSignature, SignatureError> {
+        self.partial.finish()
     }
 }
 
@@ -428,3 +658,141 @@ fn multisig_pubkey_and_signature_from_user_signature(
         _ => Err(SignatureError::from_source("unknown siganture scheme")),
     }
 }
+
+#[cfg(all(test, feature = "ed25519", feature = "secp256r1"))]
+mod test {
+    use super::*;
+    use crate::MySoSigner;
+    use crate::MySoVerifier;
+    use crate::ed25519::Ed25519PrivateKey;
+    use crate::secp256r1::Secp256r1PrivateKey;
+    use myso_sdk_types::PersonalMessage;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    #[cfg(all(feature = "passkey", feature = "serde"))]
+    #[test]
+    fn partial_multisig_with_passkey_member() {
+        use crate::passkey::PasskeySigner;
+
+        let ed25519 = Ed25519PrivateKey::new([1; 32]);
+        let passkey = PasskeySigner::new(
+            Secp256r1PrivateKey::new([2; 32]),
+            "example.com",
+            "https://example.com",
+        );
+        let outsider = Ed25519PrivateKey::new([3; 32]);
+
+        let committee = MultisigCommittee::new(
+            vec![
+                MultisigMember::new(MultisigMemberPublicKey::Ed25519(ed25519.public_key()), 1),
+                MultisigMember::new(MultisigMemberPublicKey::Passkey(passkey.public_key()), 2),
+            ],
+            3,
+        );
+        let message = PersonalMessage(b"hello world".into());
+        let verifier = MultisigVerifier::new();
+
+        let mut partial = PartialMultisig::new_with_message(committee, &message);
+        assert_eq!(partial.remaining_weight(), 3);
+
+        let ed25519_signature = ed25519.sign_personal_message(&message).unwrap();
+        assert_eq!(
+            partial
+                .add_base64_signature(&verifier, &ed25519_signature.to_base64())
+                .unwrap(),
+            1
+        );
+        partial.finish().unwrap_err();
+
+        // Signatures from non-members, duplicates and signatures over a different message are
+        // all rejected
+        partial
+            .add_signature(&verifier, outsider.sign_personal_message(&message).unwrap())
+            .unwrap_err();
+        partial
+            .add_signature(&verifier, ed25519_signature)
+            .unwrap_err();
+        let other_message = PersonalMessage(b"goodbye world".into());
+        partial
+            .add_signature(
+                &verifier,
+                passkey.sign_personal_message(&other_message).unwrap(),
+            )
+            .unwrap_err();
+
+        // The partial state survives a round trip through its serialized form
+        let json = serde_json::to_string(&partial).unwrap();
+        let mut partial: PartialMultisig = serde_json::from_str(&json).unwrap();
+        assert_eq!(partial.signers().count(), 1);
+        assert_eq!(partial.pending_members().count(), 1);
+        partial.verify_signatures(&verifier).unwrap();
+
+        // Tampered state is rejected, either when it is loaded or when it is resumed
+        let mut tampered: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let signature = tampered["signatures"]["0"].take();
+        tampered["signatures"] = serde_json::json!({ "7": signature });
+        serde_json::from_value::<PartialMultisig>(tampered.clone()).unwrap_err();
+        tampered["signatures"] = serde_json::json!({ "1": signature });
+        let tampered: PartialMultisig = serde_json::from_value(tampered).unwrap();
+        tampered.verify_signatures(&verifier).unwrap_err();
+        MultisigAggregator::from_partial(tampered, verifier.clone()).unwrap_err();
+
+        assert_eq!(
+            partial
+                .add_signature(&verifier, passkey.sign_personal_message(&message).unwrap())
+                .unwrap(),
+            3
+        );
+        assert!(partial.is_complete());
+
+        let signature = UserSignature::Multisig(partial.finish().unwrap());
+        UserSignatureVerifier::new()
+            .verify_personal_message(&message, &signature)
+            .unwrap();
+
+        // Passkey members can't be encoded using the legacy format
+        partial.with_legacy_bitmap(true).finish().unwrap_err();
+    }
+
+    #[test]
+    fn partial_multisig_legacy_bitmap() {
+        let ed25519 = Ed25519PrivateKey::new([1; 32]);
+        let secp256r1 = Secp256r1PrivateKey::new([2; 32]);
+
+        let committee = MultisigCommittee::new(
+            vec![
+                MultisigMember::new(MultisigMemberPublicKey::Ed25519(ed25519.public_key()), 1),
+                MultisigMember::new(
+                    MultisigMemberPublicKey::Secp256r1(secp256r1.public_key()),
+                    1,
+                ),
+            ],
+            1,
+        );
+        let message = PersonalMessage(b"hello world".into());
+
+        let mut aggregator = MultisigAggregator::from_partial(
+            PartialMultisig::new_with_message(committee, &message).with_legacy_bitmap(true),
+            MultisigVerifier::new(),
+        )
+        .unwrap();
+        aggregator
+            .add_signature(secp256r1.sign_personal_message(&message).unwrap())
+            .unwrap();
+        let aggregated = aggregator.finish().unwrap();
+        assert_eq!(aggregated.bitmap(), 0b10);
+        assert!(aggregated.legacy_bitmap().unwrap().contains(1));
+
+        let signature = UserSignature::Multisig(aggregated);
+        let signature = UserSignature::from_base64(&signature.to_base64()).unwrap();
+        let UserSignature::Multisig(multisig) = &signature else {
+            panic!("expected multisig");
+        };
+        assert!(multisig.legacy_bitmap().is_some());
+        UserSignatureVerifier::new()
+            .verify_personal_message(&message, &signature)
+            .unwrap();
+    }
+}
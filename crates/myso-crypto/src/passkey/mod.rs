@@ -5,6 +5,13 @@ use myso_sdk_types::PasskeyAuthenticator;
 use myso_sdk_types::SimpleSignature;
 use myso_sdk_types::UserSignature;
 
+mod registration;
+mod signer;
+
+pub use registration::PasskeyCredential;
+pub use registration::parse_cose_public_key;
+pub use signer::PasskeySigner;
+
 #[derive(Default, Clone, Debug)]
 pub struct PasskeyVerifier {}
 
@@ -87,4 +94,43 @@ mod test {
             .verify_transaction(&transaction, &signature)
             .unwrap();
     }
+
+    #[test]
+    fn passkey_signer_round_trip() {
+        use crate::MySoSigner;
+        use crate::secp256r1::Secp256r1PrivateKey;
+        use myso_sdk_types::PersonalMessage;
+
+        let signer = PasskeySigner::new(
+            Secp256r1PrivateKey::new([3; 32]),
+            "example.com",
+            "https://example.com",
+        );
+        let verifier = PasskeyVerifier::new();
+
+        let message = PersonalMessage(b"hello world".into());
+        let signature = signer.sign_personal_message(&message).unwrap();
+        verifier
+            .verify_personal_message(&message, &signature)
+            .unwrap();
+
+        let UserSignature::Passkey(authenticator) = &signature else {
+            panic!("expected passkey signature");
+        };
+        assert_eq!(authenticator.challenge(), message.signing_digest());
+        assert_eq!(authenticator.public_key(), signer.public_key());
+        assert_eq!(authenticator.authenticator_data().len(), 37);
+
+        // The signature survives a round trip through its serialized form
+        let signature = UserSignature::from_base64(&signature.to_base64()).unwrap();
+        verifier
+            .verify_personal_message(&message, &signature)
+            .unwrap();
+
+        // A signature over a different message is rejected
+        let other = PersonalMessage(b"goodbye world".into());
+        verifier
+            .verify_personal_message(&other, &signature)
+            .unwrap_err();
+    }
 }
@@ -0,0 +1,396 @@
+use crate::SignatureError;
+use myso_sdk_types::Address;
+use myso_sdk_types::PasskeyPublicKey;
+use myso_sdk_types::Secp256r1PublicKey;
+use p256::elliptic_curve::group::GroupEncoding;
+
+/// COSE key type for elliptic curve keys with x and y coordinates.
+const COSE_KTY_EC2: i128 = 2;
+
+/// COSE algorithm identifier for ECDSA w/ SHA-256.
+const COSE_ALG_ES256: i128 = -7;
+
+/// COSE curve identifier for P-256.
+const COSE_CRV_P256: i128 = 1;
+
+/// Authenticator data flag indicating that attested credential data is included.
+const FLAG_ATTESTED_CREDENTIAL_DATA: u8 = 0x40;
+
+/// A passkey credential extracted from a WebAuthn registration response.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PasskeyCredential {
+    /// The credential id assigned by the authenticator.
+    pub credential_id: Vec<u8>,
+
+    /// SHA-256 hash of the relying party id the credential is scoped to.
+    pub rp_id_hash: [u8; 32],
+
+    /// The public key of the credential.
+    pub public_key: PasskeyPublicKey,
+}
+
+impl PasskeyCredential {
+    /// Parse the `attestationObject` returned in the `AuthenticatorAttestationResponse` of a
+    /// WebAuthn registration (`navigator.credentials.create`).
+    ///
+    /// The attestation statement itself is not verified, only the attested credential data is
+    /// extracted from the authenticator data.
+    pub fn from_attestation_object(attestation_object: &[u8]) -> Result<Self, SignatureError> {
+        let value = cbor::decode(attestation_object)?;
+        let auth_data = value
+            .map_get(&cbor::Value::Text("authData".to_owned()))
+            .and_then(cbor::Value::as_bytes)
+            .ok_or_else(|| SignatureError::from_source("attestation object missing authData"))?;
+
+        Self::from_authenticator_data(auth_data)
+    }
+
+    /// Parse authenticator data which includes attested credential data.
+    ///
+    /// See [Authenticator Data](https://www.w3.org/TR/webauthn-2/#sctn-authenticator-data).
+    pub fn from_authenticator_data(authenticator_data: &[u8]) -> Result<Self, SignatureError> {
+        // rp_id_hash (32) || flags (1) || sign_count (4) || aaguid (16) || credential_id_len (2)
+        const HEADER_LENGTH: usize = 32 + 1 + 4 + 16 + 2;
+
+        if authenticator_data.len() < HEADER_LENGTH {
+            return Err(SignatureError::from_source("authenticator data too short"));
+        }
+
+        let rp_id_hash: [u8; 32] = authenticator_data[..32].try_into().unwrap();
+        let flags = authenticator_data[32];
+        if flags & FLAG_ATTESTED_CREDENTIAL_DATA == 0 {
+            return Err(SignatureError::from_source(
+                "authenticator data does not include attested credential data",
+            ));
+        }
+
+        let credential_id_length = u16::from_be_bytes([
+            authenticator_data[HEADER_LENGTH - 2],
+            authenticator_data[HEADER_LENGTH - 1],
+        ]) as usize;
+        let credential_id = authenticator_data
+            .get(HEADER_LENGTH..HEADER_LENGTH + credential_id_length)
+            .ok_or_else(|| SignatureError::from_source("authenticator data too short"))?
+            .to_vec();
+
+        // The credential public key is followed by optional extensions so only the first cbor
+        // item is parsed.
+        let (cose_key, _) =
+            cbor::decode_prefix(&authenticator_data[HEADER_LENGTH + credential_id_length..])?;
+
+        Ok(Self {
+            credential_id,
+            rp_id_hash,
+            public_key: public_key_from_cose_value(&cose_key)?,
+        })
+    }
+
+    /// Derive the MySo `Address` for this passkey.
+    pub fn address(&self) -> Address {
+        self.public_key.derive_address()
+    }
+}
+
+/// Parse a COSE_Key encoded P-256 public key, as returned by the
+/// `AuthenticatorAttestationResponse.getPublicKey` family of APIs or embedded in attested
+/// credential data, into a `PasskeyPublicKey`.
+pub fn parse_cose_public_key(cose_key: &[u8]) -> Result<PasskeyPublicKey, SignatureError> {
+    public_key_from_cose_value(&cbor::decode(cose_key)?)
+}
+
+fn public_key_from_cose_value(cose_key: &cbor::Value) -> Result<PasskeyPublicKey, SignatureError> {
+    let get_int = |label: i128| cose_key.map_get(&cbor::Value::Integer(label));
+    let get_coordinate = |label: i128| -> Result<&[u8], SignatureError> {
+        get_int(label)
+            .and_then(cbor::Value::as_bytes)
+            .filter(|coordinate| coordinate.len() == 32)
+            .ok_or_else(|| SignatureError::from_source("invalid cose key coordinate"))
+    };
+
+    if get_int(1).and_then(cbor::Value::as_integer) != Some(COSE_KTY_EC2) {
+        return Err(SignatureError::from_source("cose key is not an EC2 key"));
+    }
+    // The alg parameter is optional but if present it is required to be ES256
+    if get_int(3).is_some_and(|alg| alg.as_integer() != Some(COSE_ALG_ES256)) {
+        return Err(SignatureError::from_source("cose key alg is not ES256"));
+    }
+    if get_int(-1).and_then(cbor::Value::as_integer) != Some(COSE_CRV_P256) {
+        return Err(SignatureError::from_source("cose key curve is not P-256"));
+    }
+
+    let mut sec1 = Vec::with_capacity(65);
+    sec1.push(0x04);
+    sec1.extend_from_slice(get_coordinate(-2)?);
+    sec1.extend_from_slice(get_coordinate(-3)?);
+
+    let verifying_key = p256::ecdsa::VerifyingKey::from_sec1_bytes(&sec1)?;
+    let public_key = Secp256r1PublicKey::new(verifying_key.as_ref().to_bytes().into());
+    Ok(PasskeyPublicKey::new(public_key))
+}
+
+/// A minimal CBOR (RFC 8949) decoder supporting the subset of data items used by WebAuthn.
+mod cbor {
+    use crate::SignatureError;
+
+    /// Maximum nesting depth permitted when decoding.
+    const MAX_DEPTH: usize = 16;
+
+    #[derive(Clone, Debug, PartialEq)]
+    pub(super) enum Value {
+        Integer(i128),
+        Bytes(Vec<u8>),
+        Text(String),
+        Array(Vec<Value>),
+        Map(Vec<(Value, Value)>),
+        Bool(bool),
+        Null,
+        Undefined,
+        Float(f64),
+    }
+
+    impl Value {
+        pub(super) fn as_integer(&self) -> Option<i128> {
+            match self {
+                Value::Integer(i) => Some(*i),
+                _ => None,
+            }
+        }
+
+        pub(super) fn as_bytes(&self) -> Option<&[u8]> {
+            match self {
+                Value::Bytes(b) => Some(b),
+                _ => None,
+            }
+        }
+
+        pub(super) fn map_get(&self, key: &Value) -> Option<&Value> {
+            match self {
+                Value::Map(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+                _ => None,
+            }
+        }
+    }
+
+    /// Decode a single data item, requiring that it spans the entire input.
+    pub(super) fn decode(bytes: &[u8]) -> Result<Value, SignatureError> {
+        let (value, rest) = decode_prefix(bytes)?;
+        if !rest.is_empty() {
+            return Err(SignatureError::from_source(
+                "trailing bytes after cbor item",
+            ));
+        }
+        Ok(value)
+    }
+
+    /// Decode a single data item from the front of the input, returning the remaining bytes.
+    pub(super) fn decode_prefix(bytes: &[u8]) -> Result<(Value, &[u8]), SignatureError> {
+        let mut decoder = Decoder { bytes };
+        let value = decoder.value(0)?;
+        Ok((value, decoder.bytes))
+    }
+
+    struct Decoder<'a> {
+        bytes: &'a [u8],
+    }
+
+    impl<'a> Decoder<'a> {
+        fn take(&mut self, len: usize) -> Result<&'a [u8], SignatureError> {
+            if self.bytes.len() < len {
+                return Err(SignatureError::from_source("unexpected end of cbor input"));
+            }
+            let (head, tail) = self.bytes.split_at(len);
+            self.bytes = tail;
+            Ok(head)
+        }
+
+        fn argument(&mut self, additional: u8) -> Result<u64, SignatureError> {
+            Ok(match additional {
+                0..=23 => additional as u64,
+                24 => self.take(1)?[0] as u64,
+                25 => u16::from_be_bytes(self.take(2)?.try_into().unwrap()) as u64,
+                26 => u32::from_be_bytes(self.take(4)?.try_into().unwrap()) as u64,
+                27 => u64::from_be_bytes(self.take(8)?.try_into().unwrap()),
+                _ => {
+                    return Err(SignatureError::from_source(
+                        "indefinite length cbor items are not supported",
+                    ));
+                }
+            })
+        }
+
+        fn length(&mut self, additional: u8) -> Result<usize, SignatureError> {
+            let len = self.argument(additional)?;
+            // Every item is at least one byte so a length longer than the remaining input is
+            // always invalid.
+            if len > self.bytes.len() as u64 {
+                return Err(SignatureError::from_source("cbor length exceeds input"));
+            }
+            Ok(len as usize)
+        }
+
+        fn value(&mut self, depth: usize) -> Result<Value, SignatureError> {
+            if depth > MAX_DEPTH {
+                return Err(SignatureError::from_source("cbor nesting too deep"));
+            }
+
+            let initial = self.take(1)?[0];
+            let major = initial >> 5;
+            let additional = initial & 0x1f;
+
+            Ok(match major {
+                0 => Value::Integer(self.argument(additional)? as i128),
+                1 => Value::Integer(-1 - self.argument(additional)? as i128),
+                2 => {
+                    let len = self.length(additional)?;
+                    Value::Bytes(self.take(len)?.to_vec())
+                }
+                3 => {
+                    let len = self.length(additional)?;
+                    let text = std::str::from_utf8(self.take(len)?)
+                        .map_err(SignatureError::from_source)?;
+                    Value::Text(text.to_owned())
+                }
+                4 => {
+                    let len = self.length(additional)?;
+                    let mut items = Vec::with_capacity(len);
+                    for _ in 0..len {
+                        items.push(self.value(depth + 1)?);
+                    }
+                    Value::Array(items)
+                }
+                5 => {
+                    let len = self.length(additional)?;
+                    let mut entries = Vec::with_capacity(len);
+                    for _ in 0..len {
+                        let key = self.value(depth + 1)?;
+                        let value = self.value(depth + 1)?;
+                        entries.push((key, value));
+                    }
+                    Value::Map(entries)
+                }
+                // Tags are ignored and the tagged item is returned directly
+                6 => {
+                    self.argument(additional)?;
+                    self.value(depth + 1)?
+                }
+                7 => match additional {
+                    20 => Value::Bool(false),
+                    21 => Value::Bool(true),
+                    22 => Value::Null,
+                    23 => Value::Undefined,
+                    26 => {
+                        Value::Float(f32::from_be_bytes(self.take(4)?.try_into().unwrap()) as f64)
+                    }
+                    27 => Value::Float(f64::from_be_bytes(self.take(8)?.try_into().unwrap())),
+                    _ => {
+                        return Err(SignatureError::from_source("unsupported cbor simple value"));
+                    }
+                },
+                _ => unreachable!("major type is only 3 bits"),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::secp256r1::Secp256r1PrivateKey;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    fn cbor_header(major: u8, len: usize) -> Vec<u8> {
+        match len {
+            0..=23 => vec![(major << 5) | len as u8],
+            24..=0xff => vec![(major << 5) | 24, len as u8],
+            _ => {
+                let mut header = vec![(major << 5) | 25];
+                header.extend_from_slice(&(len as u16).to_be_bytes());
+                header
+            }
+        }
+    }
+
+    fn cbor_bytes(bytes: &[u8]) -> Vec<u8> {
+        let mut buf = cbor_header(2, bytes.len());
+        buf.extend_from_slice(bytes);
+        buf
+    }
+
+    fn cbor_text(text: &str) -> Vec<u8> {
+        let mut buf = cbor_header(3, text.len());
+        buf.extend_from_slice(text.as_bytes());
+        buf
+    }
+
+    fn cose_key(private_key: &Secp256r1PrivateKey) -> Vec<u8> {
+        let verifying_key =
+            p256::ecdsa::VerifyingKey::from_sec1_bytes(private_key.public_key().inner()).unwrap();
+        let point = verifying_key.to_encoded_point(false);
+
+        let mut buf = cbor_header(5, 5);
+        buf.extend_from_slice(&[0x01, 0x02]); // kty: EC2
+        buf.extend_from_slice(&[0x03, 0x26]); // alg: ES256
+        buf.extend_from_slice(&[0x20, 0x01]); // crv: P-256
+        buf.push(0x21); // x
+        buf.extend(cbor_bytes(point.x().unwrap()));
+        buf.push(0x22); // y
+        buf.extend(cbor_bytes(point.y().unwrap()));
+        buf
+    }
+
+    #[test]
+    fn parse_attestation_object() {
+        let private_key = Secp256r1PrivateKey::new([7; 32]);
+        let credential_id = [42u8; 16];
+
+        let mut auth_data = vec![1u8; 32];
+        auth_data.push(0x45); // UP | UV | AT
+        auth_data.extend_from_slice(&0u32.to_be_bytes());
+        auth_data.extend_from_slice(&[0u8; 16]);
+        auth_data.extend_from_slice(&(credential_id.len() as u16).to_be_bytes());
+        auth_data.extend_from_slice(&credential_id);
+        auth_data.extend(cose_key(&private_key));
+
+        let mut attestation_object = cbor_header(5, 3);
+        attestation_object.extend(cbor_text("fmt"));
+        attestation_object.extend(cbor_text("none"));
+        attestation_object.extend(cbor_text("attStmt"));
+        attestation_object.extend(cbor_header(5, 0));
+        attestation_object.extend(cbor_text("authData"));
+        attestation_object.extend(cbor_bytes(&auth_data));
+
+        let credential = PasskeyCredential::from_attestation_object(&attestation_object).unwrap();
+        let expected = PasskeyPublicKey::new(private_key.public_key());
+        assert_eq!(credential.credential_id, credential_id);
+        assert_eq!(credential.rp_id_hash, [1; 32]);
+        assert_eq!(credential.public_key, expected);
+        assert_eq!(credential.address(), expected.derive_address());
+
+        assert_eq!(
+            parse_cose_public_key(&cose_key(&private_key)).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn reject_invalid_cose_keys() {
+        let private_key = Secp256r1PrivateKey::new([7; 32]);
+        let mut key = cose_key(&private_key);
+
+        // Not an EC2 key
+        key[2] = 0x01;
+        parse_cose_public_key(&key).unwrap_err();
+
+        // Truncated input
+        let key = cose_key(&private_key);
+        parse_cose_public_key(&key[..key.len() - 1]).unwrap_err();
+
+        // Point not on the curve
+        let mut key = cose_key(&private_key);
+        let last = key.len() - 1;
+        key[last] ^= 1;
+        parse_cose_public_key(&key).unwrap_err();
+    }
+}
@@ -0,0 +1,146 @@
+use crate::SignatureError;
+use crate::secp256r1::Secp256r1PrivateKey;
+use myso_sdk_types::PasskeyAuthenticator;
+use myso_sdk_types::PasskeyPublicKey;
+use myso_sdk_types::Secp256r1Signature;
+use myso_sdk_types::SimpleSignature;
+use myso_sdk_types::UserSignature;
+use signature::Signer;
+
+/// Authenticator data flag indicating that the user was present.
+const FLAG_USER_PRESENT: u8 = 0x01;
+
+/// Authenticator data flag indicating that the user was verified.
+const FLAG_USER_VERIFIED: u8 = 0x04;
+
+/// Authenticator data flag indicating that the credential is eligible to be backed up.
+const FLAG_BACKUP_ELIGIBLE: u8 = 0x08;
+
+/// Authenticator data flag indicating that the credential is currently backed up.
+const FLAG_BACKUP_STATE: u8 = 0x10;
+
+/// A software passkey which produces WebAuthn assertions in the same form as a platform
+/// authenticator.
+///
+/// This is intended for server-side testing and for custodial emulation of a passkey. Each
+/// signature is produced by constructing:
+///
+/// * `authenticator_data` = `sha256(rp_id) || flags || sign_count`, where the flags indicate that
+///   the user was both present and verified and that the credential is backed up, as reported by
+///   synced passkeys.
+/// * `client_data_json` = `{"type":"webauthn.get","challenge":..,"origin":..,"crossOrigin":false}`
+///   where the challenge is the base64url encoding of the message being signed, which for MySo is
+///   the intent message digest.
+///
+/// and then signing `authenticator_data || sha256(client_data_json)` with the underlying
+/// secp256r1 key.
+#[derive(Clone, Debug)]
+pub struct PasskeySigner {
+    private_key: Secp256r1PrivateKey,
+    rp_id: String,
+    origin: String,
+    sign_count: u32,
+}
+
+impl PasskeySigner {
+    /// Create a new passkey for the relying party identified by `rp_id` (e.g. `example.com`)
+    /// which will produce assertions as if requested from `origin` (e.g. `https://example.com`).
+    pub fn new<R: Into<String>, O: Into<String>>(
+        private_key: Secp256r1PrivateKey,
+        rp_id: R,
+        origin: O,
+    ) -> Self {
+        Self {
+            private_key,
+            rp_id: rp_id.into(),
+            origin: origin.into(),
+            sign_count: 0,
+        }
+    }
+
+    /// Set the signature counter reported in the authenticator data.
+    ///
+    /// Synced passkeys always report a counter of `0`, which is the default.
+    pub fn with_sign_count(mut self, sign_count: u32) -> Self {
+        self.sign_count = sign_count;
+        self
+    }
+
+    pub fn private_key(&self) -> &Secp256r1PrivateKey {
+        &self.private_key
+    }
+
+    pub fn rp_id(&self) -> &str {
+        &self.rp_id
+    }
+
+    pub fn origin(&self) -> &str {
+        &self.origin
+    }
+
+    pub fn public_key(&self) -> PasskeyPublicKey {
+        PasskeyPublicKey::new(self.private_key.public_key())
+    }
+
+    fn authenticator_data(&self) -> Vec<u8> {
+        use sha2::Digest;
+
+        let rp_id_hash = sha2::Sha256::digest(self.rp_id.as_bytes());
+
+        let mut authenticator_data = Vec::with_capacity(37);
+        authenticator_data.extend_from_slice(&rp_id_hash);
+        authenticator_data.push(
+            FLAG_USER_PRESENT | FLAG_USER_VERIFIED | FLAG_BACKUP_ELIGIBLE | FLAG_BACKUP_STATE,
+        );
+        authenticator_data.extend_from_slice(&self.sign_count.to_be_bytes());
+        authenticator_data
+    }
+
+    fn client_data_json(&self, challenge: &[u8]) -> Result<String, SignatureError> {
+        let challenge =
+            <base64ct::Base64UrlUnpadded as base64ct::Encoding>::encode_string(challenge);
+        let origin = serde_json::to_string(&self.origin).map_err(SignatureError::from_source)?;
+
+        // The members are required to be serialized in this specific order, see
+        // https://w3c.github.io/webauthn/#clientdatajson-serialization
+        Ok(format!(
+            r#"{{"type":"webauthn.get","challenge":"{challenge}","origin":{origin},"crossOrigin":false}}"#
+        ))
+    }
+}
+
+impl Signer<PasskeyAuthenticator> for PasskeySigner {
+    fn try_sign(&self, message: &[u8]) -> Result<PasskeyAuthenticator, SignatureError> {
+        let authenticator_data = self.authenticator_data();
+        let client_data_json = self.client_data_json(message)?;
+
+        // Construct passkey signing message = authenticator_data || sha256(client_data_json).
+        let mut signing_message = authenticator_data.clone();
+        let client_data_hash = {
+            use sha2::Digest;
+
+            let mut hasher = sha2::Sha256::new();
+            hasher.update(client_data_json.as_bytes());
+            hasher.finalize()
+        };
+        signing_message.extend_from_slice(&client_data_hash);
+
+        let signature: Secp256r1Signature = self.private_key.try_sign(&signing_message)?;
+
+        PasskeyAuthenticator::new(
+            authenticator_data,
+            client_data_json,
+            SimpleSignature::Secp256r1 {
+                signature,
+                public_key: self.private_key.public_key(),
+            },
+        )
+        .ok_or_else(|| SignatureError::from_source("unable to construct passkey authenticator"))
+    }
+}
+
+impl Signer<UserSignature> for PasskeySigner {
+    fn try_sign(&self, message: &[u8]) -> Result<UserSignature, SignatureError> {
+        <Self as Signer<PasskeyAuthenticator>>::try_sign(self, message).map(UserSignature::Passkey)
+    }
+}
@@ -0,0 +1,374 @@
+//! Helpers for "sign in with MySo" style flows built on personal message signatures.
+//!
+//! A client signs a [`PersonalMessage`] with [`MySoSigner::sign_personal_message`] and sends the
+//! base64 encoded [`UserSignature`] (see [`UserSignature::to_base64`]) to a server, which then
+//! checks that the signature is valid and was produced by the account it claims to be from with
+//! [`UserSignatureVerifier::verify_personal_message_for_address`].
+//!
+//! [`LoginChallenge`] provides a structured, human readable message format carrying a nonce and an
+//! expiration time which servers can issue to clients in order to prevent replay of a signed
+//! login message.
+//!
+//! [`MySoSigner::sign_personal_message`]: crate::MySoSigner::sign_personal_message
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::MySoVerifier;
+use crate::SignatureError;
+use crate::UserSignatureVerifier;
+use myso_sdk_types::Address;
+use myso_sdk_types::PersonalMessage;
+use myso_sdk_types::UserSignature;
+
+impl UserSignatureVerifier {
+    /// Verify that `signature_b64`, a base64 encoded `UserSignature`, is a valid signature over
+    /// `message` produced by the account with the provided `address`.
+    ///
+    /// The address of the signer is derived from the signature itself and compared against the
+    /// claimed `address` before the signature is verified. This works for every signature scheme,
+    /// including multisig and zklogin (which requires a zklogin verifier to be configured via
+    /// [`UserSignatureVerifier::with_zklogin_verifier`]).
+    ///
+    /// Returns the decoded signature on success.
+    pub fn verify_personal_message_for_address(
+        &self,
+        message: &PersonalMessage<'_>,
+        signature_b64: &str,
+        address: &Address,
+    ) -> Result<UserSignature, SignatureError> {
+        let signature =
+            UserSignature::from_base64(signature_b64).map_err(SignatureError::from_source)?;
+
+        // zklogin signatures may correspond to more than a single address so we need to check
+        // all of them
+        if !signature
+            .derive_addresses()
+            .any(|derived| &derived == address)
+        {
+            return Err(SignatureError::from_source(format!(
+                "signature does not correspond to address {address}"
+            )));
+        }
+
+        self.verify_personal_message(message, &signature)?;
+        Ok(signature)
+    }
+}
+
+/// A login challenge issued by a server for a client to sign as a personal message.
+///
+/// The challenge binds a login to a specific domain and account, and carries a server chosen
+/// nonce and an expiration time so that a signed challenge cannot be replayed. It is rendered to
+/// and parsed from the following text format, where the statement line is optional and times are
+/// expressed as milliseconds since the unix epoch:
+///
+/// ```text
+/// example.com wants you to sign in with your MySo account:
+/// 0x0000000000000000000000000000000000000000000000000000000000000001
+///
+/// Sign in to Example
+///
+/// Nonce: 32891756
+/// Issued At: 1700000000000
+/// Expiration Time: 1700000300000
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LoginChallenge {
+    domain: String,
+    address: Address,
+    statement: Option<String>,
+    nonce: String,
+    issued_at_ms: u64,
+    expiration_time_ms: u64,
+}
+
+impl LoginChallenge {
+    const HEADER_SUFFIX: &str = " wants you to sign in with your MySo account:";
+    const NONCE_PREFIX: &str = "Nonce: ";
+    const ISSUED_AT_PREFIX: &str = "Issued At: ";
+    const EXPIRATION_TIME_PREFIX: &str = "Expiration Time: ";
+
+    /// The minimum length of a nonce.
+    pub const MIN_NONCE_LENGTH: usize = 8;
+
+    /// Create a new login challenge.
+    ///
+    /// The `nonce` is required to be at least [`LoginChallenge::MIN_NONCE_LENGTH`] ascii
+    /// alphanumeric characters and should be randomly generated and tracked by the server so
+    /// that it is only accepted once. Neither the `domain` nor the `statement` may contain line
+    /// breaks.
+    pub fn new<D: Into<String>, N: Into<String>>(
+        domain: D,
+        address: Address,
+        nonce: N,
+        issued_at_ms: u64,
+        expiration_time_ms: u64,
+    ) -> Result<Self, SignatureError> {
+        let domain = domain.into();
+        let nonce = nonce.into();
+
+        if domain.is_empty() || domain.contains(['\n', '\r', ' ']) {
+            return Err(SignatureError::from_source(
+                "invalid login challenge domain",
+            ));
+        }
+
+        if nonce.len() < Self::MIN_NONCE_LENGTH || !nonce.chars().all(|c| c.is_ascii_alphanumeric())
+        {
+            return Err(SignatureError::from_source("invalid login challenge nonce"));
+        }
+
+        if expiration_time_ms <= issued_at_ms {
+            return Err(SignatureError::from_source(
+                "login challenge expires before it was issued",
+            ));
+        }
+
+        Ok(Self {
+            domain,
+            address,
+            statement: None,
+            nonce,
+            issued_at_ms,
+            expiration_time_ms,
+        })
+    }
+
+    /// Attach a human readable statement to the challenge, e.g. `Sign in to Example`.
+    pub fn with_statement<S: Into<String>>(mut self, statement: S) -> Result<Self, SignatureError> {
+        let statement = statement.into();
+        if statement.is_empty() || statement.contains(['\n', '\r']) {
+            return Err(SignatureError::from_source(
+                "invalid login challenge statement",
+            ));
+        }
+        self.statement = Some(statement);
+        Ok(self)
+    }
+
+    pub fn domain(&self) -> &str {
+        &self.domain
+    }
+
+    pub fn address(&self) -> &Address {
+        &self.address
+    }
+
+    pub fn statement(&self) -> Option<&str> {
+        self.statement.as_deref()
+    }
+
+    pub fn nonce(&self) -> &str {
+        &self.nonce
+    }
+
+    pub fn issued_at_ms(&self) -> u64 {
+        self.issued_at_ms
+    }
+
+    pub fn expiration_time_ms(&self) -> u64 {
+        self.expiration_time_ms
+    }
+
+    /// The `PersonalMessage` that is signed for this challenge.
+    pub fn personal_message(&self) -> PersonalMessage<'static> {
+        PersonalMessage(self.to_string().into_bytes().into())
+    }
+
+    /// Verify that `signature_b64` is a valid signature of this challenge, produced by the
+    /// challenge's address, and that the challenge is valid at `now_ms`.
+    pub fn verify(
+        &self,
+        verifier: &UserSignatureVerifier,
+        signature_b64: &str,
+        now_ms: u64,
+    ) -> Result<UserSignature, SignatureError> {
+        if now_ms < self.issued_at_ms {
+            return Err(SignatureError::from_source(
+                "login challenge is not yet valid",
+            ));
+        }
+
+        if now_ms >= self.expiration_time_ms {
+            return Err(SignatureError::from_source("login challenge has expired"));
+        }
+
+        verifier.verify_personal_message_for_address(
+            &self.personal_message(),
+            signature_b64,
+            &self.address,
+        )
+    }
+}
+
+impl fmt::Display for LoginChallenge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}{}", self.domain, Self::HEADER_SUFFIX)?;
+        writeln!(f, "{}", self.address)?;
+        writeln!(f)?;
+        if let Some(statement) = &self.statement {
+            writeln!(f, "{statement}")?;
+            writeln!(f)?;
+        }
+        writeln!(f, "{}{}", Self::NONCE_PREFIX, self.nonce)?;
+        writeln!(f, "{}{}", Self::ISSUED_AT_PREFIX, self.issued_at_ms)?;
+        write!(
+            f,
+            "{}{}",
+            Self::EXPIRATION_TIME_PREFIX,
+            self.expiration_time_ms
+        )
+    }
+}
+
+impl FromStr for LoginChallenge {
+    type Err = SignatureError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        fn invalid() -> SignatureError {
+            SignatureError::from_source("malformed login challenge")
+        }
+
+        fn field<'a>(line: Option<&'a str>, prefix: &str) -> Result<&'a str, SignatureError> {
+            line.and_then(|line| line.strip_prefix(prefix))
+                .ok_or_else(invalid)
+        }
+
+        let mut lines = s.split('\n');
+
+        let domain = field(lines.next(), "")?
+            .strip_suffix(Self::HEADER_SUFFIX)
+            .ok_or_else(invalid)?;
+        let address = field(lines.next(), "")?
+            .parse::<Address>()
+            .map_err(SignatureError::from_source)?;
+        if lines.next() != Some("") {
+            return Err(invalid());
+        }
+
+        let mut line = lines.next();
+        let mut statement = None;
+        if line.is_some_and(|line| !line.starts_with(Self::NONCE_PREFIX)) {
+            statement = line;
+            if lines.next() != Some("") {
+                return Err(invalid());
+            }
+            line = lines.next();
+        }
+
+        let nonce = field(line, Self::NONCE_PREFIX)?;
+        let issued_at_ms = field(lines.next(), Self::ISSUED_AT_PREFIX)?
+            .parse()
+            .map_err(SignatureError::from_source)?;
+        let expiration_time_ms = field(lines.next(), Self::EXPIRATION_TIME_PREFIX)?
+            .parse()
+            .map_err(SignatureError::from_source)?;
+
+        if lines.next().is_some() {
+            return Err(invalid());
+        }
+
+        let challenge = Self::new(domain, address, nonce, issued_at_ms, expiration_time_ms)?;
+        match statement {
+            Some(statement) => challenge.with_statement(statement),
+            None => Ok(challenge),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "ed25519"))]
+mod test {
+    use super::*;
+    use crate::MySoSigner;
+    use crate::ed25519::Ed25519PrivateKey;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    #[test]
+    fn verify_for_address() {
+        let key = Ed25519PrivateKey::new([1; 32]);
+        let address = key.public_key().derive_address();
+        let message = PersonalMessage(b"hello world".into());
+        let signature = key.sign_personal_message(&message).unwrap().to_base64();
+
+        let verifier = UserSignatureVerifier::new();
+        verifier
+            .verify_personal_message_for_address(&message, &signature, &address)
+            .unwrap();
+
+        // wrong address
+        verifier
+            .verify_personal_message_for_address(&message, &signature, &Address::TWO)
+            .unwrap_err();
+
+        // wrong message
+        verifier
+            .verify_personal_message_for_address(
+                &PersonalMessage(b"goodbye world".into()),
+                &signature,
+                &address,
+            )
+            .unwrap_err();
+
+        // invalid base64
+        verifier
+            .verify_personal_message_for_address(&message, "not base64", &address)
+            .unwrap_err();
+    }
+
+    #[test]
+    fn login_challenge() {
+        let key = Ed25519PrivateKey::new([1; 32]);
+        let address = key.public_key().derive_address();
+
+        let challenge = LoginChallenge::new("example.com", address, "abcdef123456", 1_000, 2_000)
+            .unwrap()
+            .with_statement("Sign in to Example")
+            .unwrap();
+
+        let text = challenge.to_string();
+        assert_eq!(
+            text,
+            format!(
+                "example.com wants you to sign in with your MySo account:\n{address}\n\nSign in to Example\n\nNonce: abcdef123456\nIssued At: 1000\nExpiration Time: 2000"
+            )
+        );
+        assert_eq!(text.parse::<LoginChallenge>().unwrap(), challenge);
+
+        let without_statement =
+            LoginChallenge::new("example.com", address, "abcdef123456", 1_000, 2_000).unwrap();
+        assert_eq!(
+            without_statement
+                .to_string()
+                .parse::<LoginChallenge>()
+                .unwrap(),
+            without_statement
+        );
+
+        let signature = key
+            .sign_personal_message(&challenge.personal_message())
+            .unwrap()
+            .to_base64();
+        let verifier = UserSignatureVerifier::new();
+        challenge.verify(&verifier, &signature, 1_500).unwrap();
+        challenge.verify(&verifier, &signature, 500).unwrap_err();
+        challenge.verify(&verifier, &signature, 2_000).unwrap_err();
+
+        // A signature over a different challenge is rejected
+        without_statement
+            .verify(&verifier, &signature, 1_500)
+            .unwrap_err();
+
+        // Invalid challenges
+        LoginChallenge::new("example.com", address, "short", 1_000, 2_000).unwrap_err();
+        LoginChallenge::new("example.com", address, "abcdef123456", 2_000, 1_000).unwrap_err();
+        "example.com wants you to sign in"
+            .parse::<LoginChallenge>()
+            .unwrap_err();
+        format!("{text}\nextra")
+            .parse::<LoginChallenge>()
+            .unwrap_err();
+    }
+}
@@ -10,6 +10,18 @@ use myso_sdk_types::SignatureScheme;
 use myso_sdk_types::SimpleSignature;
 use myso_sdk_types::UserSignature;
 
+#[cfg(feature = "secp256k1-recovery")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "secp256k1-recovery")))]
+mod recovery;
+#[cfg(feature = "secp256k1-recovery")]
+pub use recovery::EthereumAddress;
+#[cfg(feature = "secp256k1-recovery")]
+pub use recovery::Secp256k1RecoverableSignature;
+#[cfg(feature = "secp256k1-recovery")]
+pub use recovery::ethereum_message_hash;
+#[cfg(feature = "secp256k1-recovery")]
+pub use recovery::keccak256;
+
 #[derive(Clone)]
 pub struct Secp256k1PrivateKey(SigningKey);
 
@@ -0,0 +1,410 @@
+use super::Secp256k1PrivateKey;
+use super::Secp256k1VerifyingKey;
+use crate::SignatureError;
+use k256::ecdsa::RecoveryId;
+use k256::ecdsa::VerifyingKey;
+use myso_sdk_types::Secp256k1PublicKey;
+use myso_sdk_types::Secp256k1Signature;
+use sha3::Digest;
+use sha3::Keccak256;
+
+/// The prefix prepended to messages signed with Ethereum's `personal_sign`, as defined by
+/// [EIP-191](https://eips.ethereum.org/EIPS/eip-191).
+const ETHEREUM_MESSAGE_PREFIX: &str = "\x19Ethereum Signed Message:\n";
+
+/// Offset added to the recovery id in the `v` byte of legacy Ethereum signatures.
+const ETHEREUM_RECOVERY_ID_OFFSET: u8 = 27;
+
+/// Compute the Keccak-256 hash of `bytes`.
+pub fn keccak256(bytes: &[u8]) -> [u8; 32] {
+    Keccak256::digest(bytes).into()
+}
+
+/// Compute the hash of `message` as signed by Ethereum's `personal_sign`, i.e.
+/// `keccak256("\x19Ethereum Signed Message:\n" || len(message) || message)`.
+pub fn ethereum_message_hash(message: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(ETHEREUM_MESSAGE_PREFIX.as_bytes());
+    hasher.update(message.len().to_string().as_bytes());
+    hasher.update(message);
+    hasher.finalize().into()
+}
+
+/// A secp256k1 ECDSA signature along with the recovery id needed to recover the public key which
+/// produced it.
+///
+/// # BCS
+///
+/// This type is not used onchain and has no BCS representation. Its byte representation is the
+/// 64 byte compact `r || s` signature followed by a single byte recovery id `v`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Secp256k1RecoverableSignature {
+    signature: Secp256k1Signature,
+    recovery_id: u8,
+}
+
+impl Secp256k1RecoverableSignature {
+    /// The length of a recoverable signature in bytes.
+    pub const LENGTH: usize = Secp256k1Signature::LENGTH + 1;
+
+    pub fn new(signature: Secp256k1Signature, recovery_id: u8) -> Result<Self, SignatureError> {
+        if recovery_id > RecoveryId::MAX {
+            return Err(SignatureError::from_source(format!(
+                "invalid recovery id {recovery_id}"
+            )));
+        }
+
+        Ok(Self {
+            signature,
+            recovery_id,
+        })
+    }
+
+    /// Parse a recoverable signature from its `r || s || v` byte representation.
+    ///
+    /// Both raw recovery ids (`0..=3`) and the legacy Ethereum form of `v` (`27..=30`) are
+    /// accepted.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SignatureError> {
+        let bytes: &[u8; Self::LENGTH] = bytes.try_into().map_err(|_| {
+            SignatureError::from_source(format!(
+                "recoverable signature must be {} bytes",
+                Self::LENGTH
+            ))
+        })?;
+
+        let (signature, v) = bytes.split_at(Secp256k1Signature::LENGTH);
+        // Safe to unwrap as the split is at exactly the signature length
+        let signature = Secp256k1Signature::new(signature.try_into().unwrap());
+        let recovery_id = match v[0] {
+            v if v >= ETHEREUM_RECOVERY_ID_OFFSET => v - ETHEREUM_RECOVERY_ID_OFFSET,
+            v => v,
+        };
+
+        Self::new(signature, recovery_id)
+    }
+
+    /// Return the `r || s || v` byte representation of this signature where `v` is the raw
+    /// recovery id.
+    pub fn to_bytes(&self) -> [u8; Self::LENGTH] {
+        let mut bytes = [0; Self::LENGTH];
+        bytes[..Secp256k1Signature::LENGTH].copy_from_slice(self.signature.as_ref());
+        bytes[Secp256k1Signature::LENGTH] = self.recovery_id;
+        bytes
+    }
+
+    /// Return the `r || s || v` byte representation of this signature where `v` is the recovery
+    /// id offset by 27, as expected by Ethereum's `ecrecover` and `personal_sign` tooling.
+    pub fn to_ethereum_bytes(&self) -> [u8; Self::LENGTH] {
+        let mut bytes = self.to_bytes();
+        bytes[Secp256k1Signature::LENGTH] += ETHEREUM_RECOVERY_ID_OFFSET;
+        bytes
+    }
+
+    /// The signature without its recovery id, as used for MySo secp256k1 signatures.
+    pub fn signature(&self) -> Secp256k1Signature {
+        self.signature
+    }
+
+    pub fn recovery_id(&self) -> u8 {
+        self.recovery_id
+    }
+
+    /// Recover the public key which signed `message` using MySo's hashing of secp256k1 messages
+    /// (sha256).
+    pub fn recover(&self, message: &[u8]) -> Result<Secp256k1VerifyingKey, SignatureError> {
+        let (signature, recovery_id) = self.to_k256()?;
+        VerifyingKey::recover_from_msg(message, &signature, recovery_id).map(Secp256k1VerifyingKey)
+    }
+
+    /// Recover the public key which signed the 32 byte message digest `prehash`.
+    pub fn recover_prehash(
+        &self,
+        prehash: &[u8; 32],
+    ) -> Result<Secp256k1VerifyingKey, SignatureError> {
+        let (signature, recovery_id) = self.to_k256()?;
+        VerifyingKey::recover_from_prehash(prehash, &signature, recovery_id)
+            .map(Secp256k1VerifyingKey)
+    }
+
+    /// Recover the public key which signed `message` with Ethereum's `personal_sign`.
+    pub fn recover_ethereum_message(
+        &self,
+        message: &[u8],
+    ) -> Result<Secp256k1VerifyingKey, SignatureError> {
+        self.recover_prehash(&ethereum_message_hash(message))
+    }
+
+    fn to_k256(self) -> Result<(k256::ecdsa::Signature, RecoveryId), SignatureError> {
+        let signature = k256::ecdsa::Signature::from_slice(self.signature.as_ref())?;
+        let recovery_id = RecoveryId::from_byte(self.recovery_id)
+            .ok_or_else(|| SignatureError::from_source("invalid recovery id"))?;
+        Ok((signature, recovery_id))
+    }
+
+    fn from_k256(
+        (signature, recovery_id): (k256::ecdsa::Signature, RecoveryId),
+    ) -> Result<Self, SignatureError> {
+        Self::new(
+            Secp256k1Signature::new(signature.to_bytes().into()),
+            recovery_id.to_byte(),
+        )
+    }
+}
+
+/// A 20 byte Ethereum address, derived from the last 20 bytes of the Keccak-256 hash of an
+/// uncompressed secp256k1 public key.
+///
+/// Addresses are displayed with the mixed-case checksum defined by
+/// [EIP-55](https://eips.ethereum.org/EIPS/eip-55).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EthereumAddress([u8; Self::LENGTH]);
+
+impl EthereumAddress {
+    /// The length of an Ethereum address in bytes.
+    pub const LENGTH: usize = 20;
+
+    pub const fn new(bytes: [u8; Self::LENGTH]) -> Self {
+        Self(bytes)
+    }
+
+    /// Derive the Ethereum address of a secp256k1 public key.
+    pub fn from_public_key(public_key: &Secp256k1PublicKey) -> Result<Self, SignatureError> {
+        Secp256k1VerifyingKey::new(public_key).map(|key| key.ethereum_address())
+    }
+
+    pub const fn inner(&self) -> &[u8; Self::LENGTH] {
+        &self.0
+    }
+
+    pub const fn into_inner(self) -> [u8; Self::LENGTH] {
+        self.0
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for EthereumAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const HEX: &[u8; 16] = b"0123456789abcdef";
+
+        let mut lower = [0u8; Self::LENGTH * 2];
+        for (i, byte) in self.0.iter().enumerate() {
+            lower[2 * i] = HEX[(byte >> 4) as usize];
+            lower[2 * i + 1] = HEX[(byte & 0xf) as usize];
+        }
+
+        // EIP-55: uppercase each letter whose corresponding nibble in the hash of the lowercase
+        // hex address is >= 8
+        let hash = keccak256(&lower);
+        let checksummed = lower
+            .iter()
+            .enumerate()
+            .map(|(i, c)| {
+                let nibble = (hash[i / 2] >> (4 * (1 - i % 2))) & 0xf;
+                if nibble >= 8 {
+                    c.to_ascii_uppercase() as char
+                } else {
+                    *c as char
+                }
+            })
+            .collect::<String>();
+
+        write!(f, "0x{checksummed}")
+    }
+}
+
+impl std::str::FromStr for EthereumAddress {
+    type Err = SignatureError;
+
+    /// Parse a `0x` prefixed hex Ethereum address.
+    ///
+    /// All lowercase and all uppercase addresses are accepted as-is while mixed-case addresses
+    /// are required to have a valid EIP-55 checksum.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = s
+            .strip_prefix("0x")
+            .ok_or_else(|| SignatureError::from_source("ethereum address must start with 0x"))?;
+        if hex.len() != Self::LENGTH * 2 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(SignatureError::from_source("invalid ethereum address"));
+        }
+
+        let mut bytes = [0u8; Self::LENGTH];
+        for (byte, chunk) in bytes.iter_mut().zip(hex.as_bytes().chunks_exact(2)) {
+            // Safe to unwrap as all characters have been checked to be ascii hex digits
+            *byte = u8::from_str_radix(std::str::from_utf8(chunk).unwrap(), 16).unwrap();
+        }
+        let address = Self(bytes);
+
+        let is_mixed_case = hex.bytes().any(|b| b.is_ascii_lowercase())
+            && hex.bytes().any(|b| b.is_ascii_uppercase());
+        if is_mixed_case && address.to_string() != s {
+            return Err(SignatureError::from_source(
+                "invalid ethereum address checksum",
+            ));
+        }
+
+        Ok(address)
+    }
+}
+
+impl Secp256k1PrivateKey {
+    /// Sign `message` using MySo's hashing of secp256k1 messages (sha256), producing a signature
+    /// from which the signer's public key can be recovered.
+    pub fn sign_recoverable(
+        &self,
+        message: &[u8],
+    ) -> Result<Secp256k1RecoverableSignature, SignatureError> {
+        Secp256k1RecoverableSignature::from_k256(self.0.sign_recoverable(message)?)
+    }
+
+    /// Sign the 32 byte message digest `prehash`, producing a signature from which the signer's
+    /// public key can be recovered.
+    pub fn sign_recoverable_prehash(
+        &self,
+        prehash: &[u8; 32],
+    ) -> Result<Secp256k1RecoverableSignature, SignatureError> {
+        Secp256k1RecoverableSignature::from_k256(self.0.sign_prehash_recoverable(prehash)?)
+    }
+
+    /// Sign `message` as done by Ethereum's `personal_sign`.
+    ///
+    /// Use [`Secp256k1RecoverableSignature::to_ethereum_bytes`] to obtain the signature in the
+    /// form expected by Ethereum tooling.
+    pub fn sign_ethereum_message(
+        &self,
+        message: &[u8],
+    ) -> Result<Secp256k1RecoverableSignature, SignatureError> {
+        self.sign_recoverable_prehash(&ethereum_message_hash(message))
+    }
+
+    /// The Ethereum address controlled by this key.
+    pub fn ethereum_address(&self) -> EthereumAddress {
+        self.verifying_key().ethereum_address()
+    }
+}
+
+impl Secp256k1VerifyingKey {
+    /// The Ethereum address corresponding to this key.
+    pub fn ethereum_address(&self) -> EthereumAddress {
+        let uncompressed = self.0.to_encoded_point(false);
+        // Skip the leading 0x04 tag byte of the uncompressed SEC1 encoding
+        let hash = keccak256(&uncompressed.as_bytes()[1..]);
+
+        let mut address = [0; EthereumAddress::LENGTH];
+        address.copy_from_slice(&hash[32 - EthereumAddress::LENGTH..]);
+        EthereumAddress(address)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use signature::Verifier;
+    use test_strategy::proptest;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    fn hex_decode(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[proptest]
+    fn recoverable_signing(signer: Secp256k1PrivateKey, message: Vec<u8>) {
+        let signature = signer.sign_recoverable(&message).unwrap();
+        assert_eq!(signature.recover(&message).unwrap(), signer.verifying_key());
+
+        // The signature is a valid MySo secp256k1 signature
+        signer
+            .verifying_key()
+            .verify(&message, &signature.signature())
+            .unwrap();
+
+        assert_eq!(
+            Secp256k1RecoverableSignature::from_bytes(&signature.to_bytes()).unwrap(),
+            signature
+        );
+        assert_eq!(
+            Secp256k1RecoverableSignature::from_bytes(&signature.to_ethereum_bytes()).unwrap(),
+            signature
+        );
+    }
+
+    #[test]
+    fn keccak256_vector() {
+        assert_eq!(
+            keccak256(b"").to_vec(),
+            hex_decode("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470")
+        );
+    }
+
+    #[test]
+    fn ethereum_vectors() {
+        let key = Secp256k1PrivateKey::new(
+            hex_decode("4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318")
+                .try_into()
+                .unwrap(),
+        )
+        .unwrap();
+
+        let address = key.ethereum_address();
+        assert_eq!(
+            address.to_string(),
+            "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23"
+        );
+        assert_eq!(
+            EthereumAddress::from_public_key(&key.public_key()).unwrap(),
+            address
+        );
+
+        let message = b"Some data";
+        assert_eq!(
+            ethereum_message_hash(message).to_vec(),
+            hex_decode("1da44b586eb0729ff70a73c326926f6ed5a25f5b056e7f47fbc6e58d86871655")
+        );
+
+        let signature = key.sign_ethereum_message(message).unwrap();
+        assert_eq!(
+            signature.to_ethereum_bytes().to_vec(),
+            hex_decode(
+                "b91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a0291c"
+            )
+        );
+        assert_eq!(
+            signature
+                .recover_ethereum_message(message)
+                .unwrap()
+                .ethereum_address(),
+            address
+        );
+    }
+
+    #[test]
+    fn ethereum_address_parsing() {
+        let address = "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23"
+            .parse::<EthereumAddress>()
+            .unwrap();
+        assert_eq!(
+            "0x2c7536e3605d9c16a7a3d7b1898e529396a65c23"
+                .parse::<EthereumAddress>()
+                .unwrap(),
+            address
+        );
+
+        // bad checksum
+        "0x2C7536E3605D9C16a7a3D7b1898e529396a65c23"
+            .parse::<EthereumAddress>()
+            .unwrap_err();
+        // missing prefix
+        "2c7536e3605d9c16a7a3d7b1898e529396a65c23"
+            .parse::<EthereumAddress>()
+            .unwrap_err();
+        // wrong length
+        "0x2c7536e3".parse::<EthereumAddress>().unwrap_err();
+    }
+}
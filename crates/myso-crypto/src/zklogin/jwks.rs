@@ -0,0 +1,68 @@
+use crate::SignatureError;
+use myso_sdk_types::Jwk;
+use myso_sdk_types::JwkId;
+
+/// The only key type supported for zklogin.
+const SUPPORTED_KTY: &str = "RSA";
+
+/// The only signing algorithm supported for zklogin.
+const SUPPORTED_ALG: &str = "RS256";
+
+#[derive(serde_derive::Deserialize)]
+struct JwkSet {
+    keys: Vec<JwkMember>,
+}
+
+#[derive(serde_derive::Deserialize)]
+struct JwkMember {
+    kty: String,
+    kid: String,
+    #[serde(default)]
+    e: Option<String>,
+    #[serde(default)]
+    n: Option<String>,
+    #[serde(default)]
+    alg: Option<String>,
+}
+
+/// Parse a JSON Web Key Set document, as served by an OIDC provider's `jwks_uri`, into the set of
+/// jwks usable for zklogin verification.
+///
+/// Keys which are not `RSA` keys used with the `RS256` algorithm are skipped. A key with no `alg`
+/// is assumed to be `RS256`. Trailing base64 padding is stripped from the modulus to match the
+/// form in which jwks are stored onchain.
+///
+/// Returns an error if the document is malformed or if it contains no supported keys.
+pub fn parse_jwks(iss: &str, json: &[u8]) -> Result<Vec<(JwkId, Jwk)>, SignatureError> {
+    let JwkSet { keys } = serde_json::from_slice(json).map_err(SignatureError::from_source)?;
+
+    let jwks = keys
+        .into_iter()
+        .filter_map(|member| {
+            let alg = member.alg.unwrap_or_else(|| SUPPORTED_ALG.to_owned());
+            if member.kty != SUPPORTED_KTY || alg != SUPPORTED_ALG {
+                return None;
+            }
+
+            let jwk_id = JwkId {
+                iss: iss.to_owned(),
+                kid: member.kid,
+            };
+            let jwk = Jwk {
+                kty: member.kty,
+                e: member.e?,
+                n: member.n?.trim_end_matches('=').to_owned(),
+                alg,
+            };
+            Some((jwk_id, jwk))
+        })
+        .collect::<Vec<_>>();
+
+    if jwks.is_empty() {
+        return Err(SignatureError::from_source(format!(
+            "no supported jwks found for issuer '{iss}'"
+        )));
+    }
+
+    Ok(jwks)
+}
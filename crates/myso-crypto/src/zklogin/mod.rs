@@ -3,14 +3,18 @@ use std::collections::HashMap;
 use crate::SignatureError;
 use poseidon::POSEIDON;
 use signature::Verifier;
+use myso_sdk_types::ActiveJwk;
 use myso_sdk_types::Jwk;
 use myso_sdk_types::JwkId;
 use myso_sdk_types::UserSignature;
 use myso_sdk_types::ZkLoginAuthenticator;
 
+mod jwks;
 mod poseidon;
 mod verify;
 
+pub use jwks::parse_jwks;
+
 #[cfg(test)]
 mod tests;
 
@@ -18,6 +22,9 @@ mod tests;
 pub struct ZkloginVerifier {
     proof_verifying_key: verify::VerifyingKey,
     jwks: HashMap<JwkId, Jwk>,
+    /// Most recent epoch in which each jwk was validated onchain, for jwks which were loaded
+    /// from an `ActiveJwk`.
+    jwk_epochs: HashMap<JwkId, u64>,
 }
 
 impl ZkloginVerifier {
@@ -25,6 +32,7 @@ impl ZkloginVerifier {
         Self {
             proof_verifying_key,
             jwks: Default::default(),
+            jwk_epochs: Default::default(),
         }
     }
 
@@ -43,6 +51,60 @@ impl ZkloginVerifier {
     pub fn jwks_mut(&mut self) -> &mut HashMap<JwkId, Jwk> {
         &mut self.jwks
     }
+
+    /// Insert a jwk which has been accepted onchain, recording the epoch it was last validated in.
+    ///
+    /// If the jwk is already present its epoch is only ever moved forward.
+    pub fn insert_active_jwk(&mut self, active_jwk: ActiveJwk) {
+        let ActiveJwk { jwk_id, jwk, epoch } = active_jwk;
+
+        let entry = self.jwk_epochs.entry(jwk_id.clone()).or_insert(epoch);
+        *entry = (*entry).max(epoch);
+        self.jwks.insert(jwk_id, jwk);
+    }
+
+    /// Insert a set of jwks which have been accepted onchain.
+    ///
+    /// See [`ZkloginVerifier::insert_active_jwk`].
+    pub fn extend_active_jwks<I: IntoIterator<Item = ActiveJwk>>(&mut self, active_jwks: I) {
+        for active_jwk in active_jwks {
+            self.insert_active_jwk(active_jwk);
+        }
+    }
+
+    /// Returns the epoch in which the jwk with the provided id was last validated onchain, if it
+    /// was loaded from an `ActiveJwk`.
+    pub fn jwk_epoch(&self, jwk_id: &JwkId) -> Option<u64> {
+        self.jwk_epochs.get(jwk_id).copied()
+    }
+
+    /// Expire all jwks that were last validated in an epoch earlier than `min_epoch`.
+    ///
+    /// This mirrors the behavior of the onchain authenticator state: the most recently validated
+    /// jwks for each issuer are always retained so that an issuer is never left without any keys.
+    /// Jwks which were inserted manually via [`ZkloginVerifier::jwks_mut`] have no associated
+    /// epoch and are never expired.
+    pub fn expire_jwks(&mut self, min_epoch: u64) {
+        let mut latest_epoch_per_issuer: HashMap<&str, u64> = HashMap::new();
+        for (jwk_id, epoch) in &self.jwk_epochs {
+            let latest = latest_epoch_per_issuer.entry(&jwk_id.iss).or_default();
+            *latest = (*latest).max(*epoch);
+        }
+
+        let expired = self
+            .jwk_epochs
+            .iter()
+            .filter(|(jwk_id, epoch)| {
+                **epoch < min_epoch && latest_epoch_per_issuer[jwk_id.iss.as_str()] != **epoch
+            })
+            .map(|(jwk_id, _)| jwk_id.clone())
+            .collect::<Vec<_>>();
+
+        for jwk_id in expired {
+            self.jwk_epochs.remove(&jwk_id);
+            self.jwks.remove(&jwk_id);
+        }
+    }
 }
 
 impl Verifier<ZkLoginAuthenticator> for ZkloginVerifier {
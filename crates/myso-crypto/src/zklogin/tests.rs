@@ -80,3 +80,102 @@ fn zklogin_sign_personal_message() {
         .verify_personal_message(&message, &user_signature)
         .unwrap();
 }
+
+#[test]
+fn zklogin_verify_personal_message_for_address() {
+    let message = PersonalMessage(b"hello world".into());
+
+    let (jwk, jwk_id, inputs, key, max_epoch) = test_zklogin_material();
+    let signature = key.sign(&message.signing_digest());
+    let user_signature = UserSignature::ZkLogin(
+        ZkLoginAuthenticator {
+            inputs,
+            max_epoch,
+            signature,
+        }
+        .into(),
+    );
+    let signature_b64 = user_signature.to_base64();
+    let address = user_signature.derive_address();
+
+    let mut zklogin_verifier = ZkloginVerifier::new_dev();
+    zklogin_verifier.jwks_mut().insert(jwk_id, jwk);
+
+    // zklogin signatures can't be verified without a configured zklogin verifier
+    let mut verifier = crate::UserSignatureVerifier::new();
+    verifier
+        .verify_personal_message_for_address(&message, &signature_b64, &address)
+        .unwrap_err();
+
+    verifier.with_zklogin_verifier(zklogin_verifier);
+    verifier
+        .verify_personal_message_for_address(&message, &signature_b64, &address)
+        .unwrap();
+    verifier
+        .verify_personal_message_for_address(
+            &message,
+            &signature_b64,
+            &myso_sdk_types::Address::ZERO,
+        )
+        .unwrap_err();
+}
+
+#[test]
+fn parse_provider_jwks() {
+    let json = br#"{
+        "keys": [
+            {"alg":"RS256","e":"AQAB","kid":"1","kty":"RSA","n":"6lq9MQ-q6hcxr7kOUp-tHlHtdcDsVLwVIw13iXUCvuDOeCi0VSuxCCUY6UmMjy53dX00ih2E4Y4UvlrmmurK0eG26b-HMNNAvCGsVXHU3RcRhVoHDaOwHwU72j7bpHn9XbP3Q3jebX6KIfNbei2MiR0Wyb8RZHE-aZhRYO8_-k9G2GycTpvc-2GBsP8VHLUKKfAs2B6sW3q3ymU6M0L-cFXkZ9fHkn9ejs-sqZPhMJxtBPBxoUIUQFTgv4VXTSv914f_YkNw-EjuwbgwXMvpyr06EyfImxHoxsZkFYB-qBYHtaMxTnFsZBr6fn8Ha2JqT1hoP7Z5r5wxDu3GQhKkHw","use":"sig"},
+            {"kty":"EC","kid":"2","crv":"P-256","x":"f83OJ3D2xF1Bg8vub9tLe1gHMzV76e8Tus9uPHvRVEU","y":"x_FEzRu9m36HLN_tue659LNpXW6pCyStikYjKIWI5a0"}
+        ]
+    }"#;
+
+    let (jwk, jwk_id, ..) = test_zklogin_material();
+    let jwks = parse_jwks("https://id.twitch.tv/oauth2", json).unwrap();
+    assert_eq!(jwks, vec![(jwk_id, jwk)]);
+
+    parse_jwks("https://id.twitch.tv/oauth2", br#"{"keys":[]}"#).unwrap_err();
+    parse_jwks("https://id.twitch.tv/oauth2", b"not json").unwrap_err();
+}
+
+#[test]
+fn expire_active_jwks() {
+    let (jwk, jwk_id, ..) = test_zklogin_material();
+    let jwk_id_for = |kid: &str| JwkId {
+        kid: kid.to_owned(),
+        ..jwk_id.clone()
+    };
+
+    let mut verifier = ZkloginVerifier::new_dev();
+    verifier.extend_active_jwks([
+        ActiveJwk {
+            jwk_id: jwk_id_for("old"),
+            jwk: jwk.clone(),
+            epoch: 1,
+        },
+        ActiveJwk {
+            jwk_id: jwk_id_for("new"),
+            jwk: jwk.clone(),
+            epoch: 5,
+        },
+    ]);
+    verifier
+        .jwks_mut()
+        .insert(jwk_id_for("manual"), jwk.clone());
+
+    // Epochs only ever move forward
+    verifier.insert_active_jwk(ActiveJwk {
+        jwk_id: jwk_id_for("new"),
+        jwk: jwk.clone(),
+        epoch: 3,
+    });
+    assert_eq!(verifier.jwk_epoch(&jwk_id_for("new")), Some(5));
+
+    verifier.expire_jwks(4);
+    assert!(!verifier.jwks().contains_key(&jwk_id_for("old")));
+    assert!(verifier.jwks().contains_key(&jwk_id_for("new")));
+    assert!(verifier.jwks().contains_key(&jwk_id_for("manual")));
+
+    // The latest keys for an issuer are never expired
+    verifier.expire_jwks(10);
+    assert!(verifier.jwks().contains_key(&jwk_id_for("new")));
+}
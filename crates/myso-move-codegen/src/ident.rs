@@ -0,0 +1,32 @@
+//! Utility functions for working with identifiers.
+
+use proc_macro2::Ident;
+use proc_macro2::Span;
+
+/// Create a Rust identifier from a Move identifier, escaping it if it is a Rust keyword.
+pub fn ident(s: &str) -> Ident {
+    // Use a raw identifier if the identifier matches a Rust keyword:
+    // https://doc.rust-lang.org/reference/keywords.html.
+    match s {
+        // 2015 strict keywords.
+        | "as" | "break" | "const" | "continue" | "else" | "enum" | "false"
+        | "fn" | "for" | "if" | "impl" | "in" | "let" | "loop" | "match" | "mod" | "move" | "mut"
+        | "pub" | "ref" | "return" | "static" | "struct" | "trait" | "true"
+        | "type" | "unsafe" | "use" | "where" | "while"
+        // 2018 strict keywords.
+        | "dyn"
+        // 2015 reserved keywords.
+        | "abstract" | "become" | "box" | "do" | "final" | "macro" | "override" | "priv" | "typeof"
+        | "unsized" | "virtual" | "yield"
+        // 2018 reserved keywords.
+        | "async" | "await" | "try"
+        // 2024 reserved keywords.
+        | "gen" => Ident::new_raw(s, Span::call_site()),
+        // the following keywords are not supported as raw identifiers and are therefore suffixed
+        // with an underscore.
+        "_" | "super" | "self" | "Self" | "extern" | "crate" => {
+            Ident::new(&format!("{s}_"), Span::call_site())
+        }
+        _ => Ident::new(s, Span::call_site()),
+    }
+}
@@ -0,0 +1,822 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Generate type-safe Rust bindings for a Move package.
+//!
+//! Given the modules of a package along with their `FunctionDescriptor`s and
+//! `DatatypeDescriptor`s, as returned by `MovePackageService::GetPackage`, a [`Generator`] emits
+//! Rust source containing:
+//!
+//! - `PACKAGE_ID` and `ORIGINAL_ID` constants for the package.
+//! - A Rust module per Move module.
+//! - An extension trait per module, implemented for `TransactionBuilder`, with a method for every
+//!   public or entry function which adds a call to it. Parameters which can only be passed as pure
+//!   values, e.g. `u64`, `address` or `vector<u8>`, take the Rust value directly while any other
+//!   parameter takes an `Argument`, and type arguments are passed as a fixed size array. A call
+//!   returns an `Argument` for a single return value and one `Argument` per value for multiple.
+//! - A serde type for every struct and enum which can be used to BCS decode values of that type.
+//!
+//! Since the bindings are generated from the package itself they can simply be regenerated when
+//! the package is upgraded, instead of hand maintaining wrappers which drift out of sync with it.
+//!
+//! The generated code depends on the `myso-sdk-types`, `myso-transaction-builder` and `serde`
+//! (with the `derive` feature) crates.
+//!
+//! # Build script usage
+//!
+//! ```no_run
+//! # fn main() -> Result<(), myso_move_codegen::Error> {
+//! // A `GetPackageResponse` or `Package` saved as JSON
+//! let json = std::fs::read_to_string("packages/counter.json")?;
+//! let out_dir = std::path::PathBuf::from(std::env::var("OUT_DIR").unwrap());
+//!
+//! myso_move_codegen::Generator::from_json(&json)?
+//!     .extern_type("0x2::coin::Coin", "my_crate::Coin")?
+//!     .write_to_file(out_dir.join("counter.rs"))?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The bindings can then be included in a module with
+//! `include!(concat!(env!("OUT_DIR"), "/counter.rs"));`.
+//!
+//! The `myso-move-codegen` binary can be used to generate bindings ahead of time instead.
+//!
+//! # Type mapping
+//!
+//! Move primitives map to their Rust equivalents, with `u256` mapping to
+//! `myso_sdk_types::U256`, and a number of standard library and framework types are
+//! mapped to Rust types with the same BCS encoding: `String` for `0x1::string::String`,
+//! `0x1::ascii::String`, `0x1::type_name::TypeName` and `0x2::url::Url`, `Option<T>` for
+//! `0x1::option::Option<T>`, `Address` for `0x2::object::UID` and `0x2::object::ID` and `u64`
+//! for `0x2::balance::Balance<T>` and `0x2::balance::Supply<T>`. Phantom type parameters are
+//! omitted from generated types, and any other type parameter which isn't used by a field is
+//! marked with a `PhantomData` which isn't serialized.
+//!
+//! Any other datatype defined outside of the package needs to be mapped to a Rust type with a
+//! compatible BCS encoding using [`Generator::extern_type`].
+
+mod ident;
+
+use heck::ToSnakeCase;
+use heck::ToUpperCamelCase;
+use ident::ident;
+use myso_rpc::proto::myso::rpc::v2::DatatypeDescriptor;
+use myso_rpc::proto::myso::rpc::v2::FunctionDescriptor;
+use myso_rpc::proto::myso::rpc::v2::GetPackageResponse;
+use myso_rpc::proto::myso::rpc::v2::Module;
+use myso_rpc::proto::myso::rpc::v2::OpenSignature;
+use myso_rpc::proto::myso::rpc::v2::OpenSignatureBody;
+use myso_rpc::proto::myso::rpc::v2::Package;
+use myso_rpc::proto::myso::rpc::v2::datatype_descriptor::DatatypeKind;
+use myso_rpc::proto::myso::rpc::v2::function_descriptor::Visibility;
+use myso_rpc::proto::myso::rpc::v2::open_signature::Reference;
+use myso_rpc::proto::myso::rpc::v2::open_signature_body::Type;
+use myso_sdk_types::Address;
+use proc_macro2::Ident;
+use proc_macro2::TokenStream;
+use quote::format_ident;
+use quote::quote;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashSet;
+use std::path::Path;
+
+#[derive(thiserror::Error, Debug)]
+#[non_exhaustive]
+pub enum Error {
+    #[error("invalid package json: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("invalid package: {0}")]
+    InvalidPackage(String),
+    #[error("invalid type name {0}")]
+    InvalidTypeName(String),
+    #[error("invalid rust type {0}")]
+    InvalidRustType(String),
+    #[error("no rust type for {type_name} used by {used_by}, map it with `Generator::extern_type`")]
+    UnmappedType { type_name: String, used_by: String },
+    #[error("generated invalid rust code: {0}")]
+    InvalidCode(#[from] syn::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+/// A fully qualified Move datatype name: its address, module and name.
+type TypeKey = (Address, String, String);
+
+/// Generates Rust bindings for a Move package.
+pub struct Generator {
+    package: Package,
+    extern_types: BTreeMap<TypeKey, syn::Type>,
+}
+
+impl Generator {
+    /// Create a generator for a package.
+    pub fn from_package(package: Package) -> Self {
+        Self {
+            package,
+            extern_types: BTreeMap::new(),
+        }
+    }
+
+    /// Create a generator from the JSON encoding of either a `GetPackageResponse` or a
+    /// `Package`.
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        let package = match serde_json::from_str::<GetPackageResponse>(json) {
+            Ok(GetPackageResponse {
+                package: Some(package),
+                ..
+            }) => package,
+            _ => serde_json::from_str(json)?,
+        };
+        Ok(Self::from_package(package))
+    }
+
+    /// Map a Move datatype, e.g. `0x2::coin::Coin`, to a Rust type, e.g. `my_crate::Coin`, which
+    /// has the same BCS encoding.
+    ///
+    /// Any type arguments of the Move datatype are ignored. This takes precedence over the
+    /// built-in mappings and over types defined by the package itself.
+    pub fn extern_type(mut self, type_name: &str, rust_type: &str) -> Result<Self, Error> {
+        let key = parse_type_name(type_name)?;
+        let rust_type =
+            syn::parse_str(rust_type).map_err(|_| Error::InvalidRustType(rust_type.to_owned()))?;
+        self.extern_types.insert(key, rust_type);
+        Ok(self)
+    }
+
+    /// Generate the Rust source for the package's bindings.
+    pub fn generate(&self) -> Result<String, Error> {
+        let context = Context::new(self)?;
+
+        let storage_id = context.storage_id.to_string();
+        let original_id = context.original_id.to_string();
+
+        let mut modules = self.package.modules().iter().collect::<Vec<_>>();
+        modules.sort_by_key(|module| module.name());
+        let modules = modules
+            .into_iter()
+            .map(|module| context.module(module))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let code = quote! {
+            /// The storage id of the package these bindings were generated from.
+            pub const PACKAGE_ID: ::myso_sdk_types::Address =
+                ::myso_sdk_types::Address::from_static(#storage_id);
+
+            /// The id of the first version of the package, which defines the types introduced by it.
+            pub const ORIGINAL_ID: ::myso_sdk_types::Address =
+                ::myso_sdk_types::Address::from_static(#original_id);
+
+            #(#modules)*
+        };
+
+        let ast: syn::File = syn::parse2(code)?;
+        Ok(format!(
+            "// @generated by myso-move-codegen from package {storage_id}. Do not edit.\n\n{}",
+            prettyplease::unparse(&ast)
+        ))
+    }
+
+    /// Generate the package's bindings and write them to `path`.
+    pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        std::fs::write(path, self.generate()?)?;
+        Ok(())
+    }
+}
+
+struct Context<'a> {
+    storage_id: Address,
+    original_id: Address,
+    /// Every id that types defined by this package may be defined at.
+    package_ids: HashSet<Address>,
+    /// Datatypes defined by this package, indexed by their module and name.
+    datatypes: BTreeMap<(&'a str, &'a str), &'a DatatypeDescriptor>,
+    extern_types: &'a BTreeMap<TypeKey, syn::Type>,
+}
+
+impl<'a> Context<'a> {
+    fn new(generator: &'a Generator) -> Result<Self, Error> {
+        let package = &generator.package;
+        let parse_id = |id: &str, field: &str| {
+            id.parse::<Address>()
+                .map_err(|_| Error::InvalidPackage(format!("invalid {field} {id:?}")))
+        };
+
+        let storage_id = parse_id(package.storage_id(), "storage_id")?;
+        let original_id = match package.original_id_opt() {
+            Some(id) => parse_id(id, "original_id")?,
+            None => storage_id,
+        };
+
+        let mut package_ids = HashSet::from([storage_id, original_id]);
+        for origin in package.type_origins() {
+            package_ids.insert(parse_id(origin.package_id(), "type origin")?);
+        }
+
+        let mut datatypes = BTreeMap::new();
+        for module in package.modules() {
+            for datatype in &module.datatypes {
+                if let Some(id) = datatype.defining_id_opt() {
+                    package_ids.insert(parse_id(id, "defining_id")?);
+                }
+                datatypes.insert((module.name(), datatype.name()), datatype);
+            }
+        }
+
+        Ok(Self {
+            storage_id,
+            original_id,
+            package_ids,
+            datatypes,
+            extern_types: &generator.extern_types,
+        })
+    }
+
+    fn module(&self, module: &Module) -> Result<TokenStream, Error> {
+        let module_ident = ident(module.name());
+        let module_name = module.name();
+
+        let mut datatypes = module.datatypes.iter().collect::<Vec<_>>();
+        datatypes.sort_by_key(|datatype| datatype.name());
+        let datatypes = datatypes
+            .into_iter()
+            .map(|datatype| self.datatype(module_name, datatype))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut functions = module
+            .functions
+            .iter()
+            .filter(|function| function.visibility() == Visibility::Public || function.is_entry())
+            .collect::<Vec<_>>();
+        functions.sort_by_key(|function| function.name());
+        let (declarations, definitions): (Vec<_>, Vec<_>) = functions
+            .into_iter()
+            .map(|function| self.function(module_name, function))
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .unzip();
+
+        let calls = if declarations.is_empty() {
+            quote! {}
+        } else {
+            let trait_name = format_ident!("{}Calls", module_name.to_upper_camel_case());
+            let doc = format!(
+                " Calls to the functions of the `{module_name}` module, added to a \
+                 `TransactionBuilder`."
+            );
+            quote! {
+                #[doc = #doc]
+                ///
+                /// A method which has the same name as a method of `TransactionBuilder` itself
+                /// needs to be called through the trait, e.g. `Trait::method(&mut builder, ..)`.
+                pub trait #trait_name {
+                    #(#declarations)*
+                }
+
+                impl #trait_name for ::myso_transaction_builder::TransactionBuilder {
+                    #(#definitions)*
+                }
+            }
+        };
+
+        let doc = format!(" Bindings for the `{module_name}` module.");
+        Ok(quote! {
+            #[doc = #doc]
+            pub mod #module_ident {
+                /// The name of this module.
+                pub const MODULE_NAME: &str = #module_name;
+
+                #(#datatypes)*
+
+                #calls
+            }
+        })
+    }
+
+    fn datatype(&self, module: &str, datatype: &DatatypeDescriptor) -> Result<TokenStream, Error> {
+        let name = ident(datatype.name());
+        let datatype_name = datatype.name();
+        let used_by = format!("{module}::{datatype_name}");
+
+        // Phantom type parameters don't affect the layout of a type so they are omitted
+        let generics = Generics::new(
+            datatype
+                .type_parameters
+                .iter()
+                .enumerate()
+                .map(|(index, parameter)| {
+                    (!parameter.is_phantom()).then(|| format_ident!("T{index}"))
+                })
+                .collect(),
+        );
+        let declared_generics = generics.idents.iter().flatten().collect::<Vec<_>>();
+        let generics_decl = if declared_generics.is_empty() {
+            quote! {}
+        } else {
+            quote! { <#(#declared_generics),*> }
+        };
+
+        let fields = |fields: &[myso_rpc::proto::myso::rpc::v2::FieldDescriptor]| {
+            let mut fields = fields.iter().collect::<Vec<_>>();
+            fields.sort_by_key(|field| field.position());
+            fields
+                .into_iter()
+                .map(|field| {
+                    let name = ident(field.name());
+                    let type_ = self.rust_type(field.r#type(), &generics, &used_by)?;
+                    Ok(quote! { #name: #type_ })
+                })
+                .collect::<Result<Vec<_>, Error>>()
+        };
+
+        let derives = quote! {
+            #[derive(
+                Clone,
+                Debug,
+                PartialEq,
+                Eq,
+                ::serde::Serialize,
+                ::serde::Deserialize,
+            )]
+        };
+
+        let definition = match datatype.kind() {
+            DatatypeKind::Enum => {
+                let mut variants = datatype.variants.iter().collect::<Vec<_>>();
+                variants.sort_by_key(|variant| variant.position());
+                let variants = variants
+                    .into_iter()
+                    .map(|variant| {
+                        let name = ident(variant.name());
+                        let fields = fields(&variant.fields)?;
+                        Ok(if fields.is_empty() {
+                            quote! { #name }
+                        } else {
+                            quote! { #name { #(#fields),* } }
+                        })
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?;
+                let phantom = generics.unused().map(|unused| {
+                    quote! {
+                        #[doc(hidden)]
+                        #[serde(skip)]
+                        __Phantom(#unused)
+                    }
+                });
+                quote! {
+                    #derives
+                    pub enum #name #generics_decl {
+                        #(#variants,)*
+                        #phantom
+                    }
+                }
+            }
+            _ => {
+                let fields = fields(&datatype.fields)?;
+                let phantom = generics.unused().map(|unused| {
+                    quote! {
+                        #[serde(skip)]
+                        pub __phantom: #unused
+                    }
+                });
+                quote! {
+                    #derives
+                    pub struct #name #generics_decl {
+                        #(pub #fields,)*
+                        #phantom
+                    }
+                }
+            }
+        };
+
+        let defining_id = match datatype.defining_id_opt() {
+            Some(id) => id
+                .parse::<Address>()
+                .map_err(|_| Error::InvalidPackage(format!("invalid defining_id of {used_by}")))?,
+            None => self.original_id,
+        }
+        .to_string();
+        let doc = format!(" The Move type `{defining_id}::{module}::{datatype_name}`.");
+
+        let (tag_doc, type_params_arg, type_params) = if datatype.type_parameters.is_empty() {
+            (
+                " The struct tag of this type.",
+                quote! {},
+                quote! { ::std::vec::Vec::new() },
+            )
+        } else {
+            (
+                " The struct tag of this type, instantiated with the provided type parameters.",
+                quote! { type_params: ::std::vec::Vec<::myso_sdk_types::TypeTag> },
+                quote! { type_params },
+            )
+        };
+
+        Ok(quote! {
+            #[doc = #doc]
+            #definition
+
+            impl #generics_decl #name #generics_decl {
+                #[doc = #tag_doc]
+                pub fn struct_tag(#type_params_arg) -> ::myso_sdk_types::StructTag {
+                    ::myso_sdk_types::StructTag::new(
+                        ::myso_sdk_types::Address::from_static(#defining_id),
+                        ::myso_sdk_types::Identifier::from_static(#module),
+                        ::myso_sdk_types::Identifier::from_static(#datatype_name),
+                        #type_params,
+                    )
+                }
+            }
+        })
+    }
+
+    /// The declaration of the extension trait method which calls a function, and its definition
+    /// for `TransactionBuilder`.
+    fn function(
+        &self,
+        module: &str,
+        function: &FunctionDescriptor,
+    ) -> Result<(TokenStream, TokenStream), Error> {
+        let name = ident(function.name());
+        let function_name = function.name();
+
+        // A trailing `TxContext` parameter is supplied by the runtime
+        let mut parameters = function.parameters.as_slice();
+        if let Some((last, rest)) = parameters.split_last()
+            && is_tx_context(last.body())
+        {
+            parameters = rest;
+        }
+
+        // Parameters taken by value which can only be pure values are passed as Rust values
+        let pure_types = parameters
+            .iter()
+            .map(|parameter| match parameter.reference() {
+                Reference::Immutable | Reference::Mutable => None,
+                _ => pure_type(parameter.body()),
+            })
+            .collect::<Vec<_>>();
+
+        let mut taken = BTreeSet::from(["type_args".to_owned()]);
+        let parameter_names = parameters
+            .iter()
+            .zip(&pure_types)
+            .enumerate()
+            .map(|(index, (parameter, pure_type))| {
+                let body = parameter.body();
+                let name = match parse_type_name(body.type_name()) {
+                    Ok((_, _, name)) if body.r#type() == Type::Datatype && pure_type.is_none() => {
+                        name.to_snake_case()
+                    }
+                    _ => format!("arg{index}"),
+                };
+                let name = if taken.contains(&name) {
+                    format!("{name}_{index}")
+                } else {
+                    name
+                };
+                taken.insert(name.clone());
+                ident(&name)
+            })
+            .collect::<Vec<_>>();
+
+        let parameter_decls = parameter_names
+            .iter()
+            .zip(&pure_types)
+            .map(|(name, pure_type)| match pure_type {
+                Some(type_) => quote! { #name: #type_ },
+                None => quote! { #name: ::myso_transaction_builder::Argument },
+            })
+            .collect::<Vec<_>>();
+        let pure_inputs = parameter_names
+            .iter()
+            .zip(&pure_types)
+            .filter(|(_, pure_type)| pure_type.is_some())
+            .map(|(name, _)| quote! { let #name = self.pure(&#name); });
+
+        let type_arg_count = function.type_parameters.len();
+        let (type_args_arg, with_type_args) = if type_arg_count == 0 {
+            (quote! {}, quote! {})
+        } else {
+            let count = proc_macro2::Literal::usize_unsuffixed(type_arg_count);
+            (
+                quote! { type_args: [::myso_sdk_types::TypeTag; #count], },
+                quote! { .with_type_args(::std::vec::Vec::from(type_args)) },
+            )
+        };
+
+        let allow = if parameters.len() + usize::from(type_arg_count > 0) >= 7 {
+            quote! { #[allow(clippy::too_many_arguments)] }
+        } else {
+            quote! {}
+        };
+
+        let (return_type, into_return) = match function.returns.len() {
+            0 => (quote! {}, quote! { ; }),
+            1 => (
+                quote! { -> ::myso_transaction_builder::Argument },
+                quote! {},
+            ),
+            count => {
+                let count = proc_macro2::Literal::usize_unsuffixed(count);
+                (
+                    quote! { -> ::std::vec::Vec<::myso_transaction_builder::Argument> },
+                    quote! { .to_nested(#count) },
+                )
+            }
+        };
+
+        let storage_id = self.storage_id;
+        let signature = parameters
+            .iter()
+            .map(display_signature)
+            .collect::<Vec<_>>()
+            .join(", ");
+        let returns = match function.returns.as_slice() {
+            [] => String::new(),
+            [single] => format!(": {}", display_signature(single)),
+            many => format!(
+                ": ({})",
+                many.iter()
+                    .map(display_signature)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        };
+        let doc = format!(" Call `{storage_id}::{module}::{function_name}`.");
+        let signature_doc = format!(" Move signature: `({signature}){returns}`");
+
+        let declaration = quote! {
+            #[doc = #doc]
+            ///
+            #[doc = #signature_doc]
+            #allow
+            fn #name(
+                &mut self,
+                #type_args_arg
+                #(#parameter_decls),*
+            ) #return_type;
+        };
+
+        let definition = quote! {
+            #allow
+            fn #name(
+                &mut self,
+                #type_args_arg
+                #(#parameter_decls),*
+            ) #return_type {
+                #(#pure_inputs)*
+                self.move_call(
+                    ::myso_transaction_builder::Function::new(
+                        super::PACKAGE_ID,
+                        ::myso_sdk_types::Identifier::from_static(#module),
+                        ::myso_sdk_types::Identifier::from_static(#function_name),
+                    )
+                    #with_type_args,
+                    ::std::vec![#(#parameter_names),*],
+                )
+                #into_return
+            }
+        };
+
+        Ok((declaration, definition))
+    }
+
+    /// The Rust type of a Move type, where `generics` are the Rust type parameters corresponding
+    /// to the Move type's type parameters.
+    fn rust_type(
+        &self,
+        body: &OpenSignatureBody,
+        generics: &Generics,
+        used_by: &str,
+    ) -> Result<TokenStream, Error> {
+        let type_ = match body.r#type() {
+            Type::Address => quote! { ::myso_sdk_types::Address },
+            Type::Bool => quote! { bool },
+            Type::U8 => quote! { u8 },
+            Type::U16 => quote! { u16 },
+            Type::U32 => quote! { u32 },
+            Type::U64 => quote! { u64 },
+            Type::U128 => quote! { u128 },
+            Type::U256 => quote! { ::myso_sdk_types::U256 },
+            Type::Vector => {
+                let element = body.type_parameter_instantiation.first().ok_or_else(|| {
+                    Error::InvalidPackage(format!("vector without an element type in {used_by}"))
+                })?;
+                let element = self.rust_type(element, generics, used_by)?;
+                quote! { ::std::vec::Vec<#element> }
+            }
+            Type::Parameter => {
+                let generic = generics
+                    .use_parameter(body.type_parameter())
+                    .ok_or_else(|| {
+                        Error::InvalidPackage(format!(
+                            "invalid use of type parameter {} in {used_by}",
+                            body.type_parameter()
+                        ))
+                    })?;
+                quote! { #generic }
+            }
+            Type::Datatype => return self.datatype_type(body, generics, used_by),
+            _ => {
+                return Err(Error::InvalidPackage(format!("unknown type in {used_by}")));
+            }
+        };
+        Ok(type_)
+    }
+
+    fn datatype_type(
+        &self,
+        body: &OpenSignatureBody,
+        generics: &Generics,
+        used_by: &str,
+    ) -> Result<TokenStream, Error> {
+        let key = parse_type_name(body.type_name())?;
+        if let Some(rust_type) = self.extern_types.get(&key) {
+            return Ok(quote! { #rust_type });
+        }
+
+        let (address, module, name) = &key;
+        let type_ = match (address, module.as_str(), name.as_str()) {
+            (a, "string", "String") | (a, "ascii", "String") | (a, "type_name", "TypeName")
+                if a == &STD =>
+            {
+                quote! { ::std::string::String }
+            }
+            (a, "option", "Option") if a == &STD => {
+                let inner = body.type_parameter_instantiation.first().ok_or_else(|| {
+                    Error::InvalidPackage(format!("option without a type argument in {used_by}"))
+                })?;
+                let inner = self.rust_type(inner, generics, used_by)?;
+                quote! { ::std::option::Option<#inner> }
+            }
+            (&Address::TWO, "url", "Url") => quote! { ::std::string::String },
+            (&Address::TWO, "object", "UID" | "ID") => quote! { ::myso_sdk_types::Address },
+            (&Address::TWO, "balance", "Balance" | "Supply") => quote! { u64 },
+            _ => {
+                let datatype = self
+                    .package_ids
+                    .contains(address)
+                    .then(|| self.datatypes.get(&(module.as_str(), name.as_str())))
+                    .flatten()
+                    .ok_or_else(|| Error::UnmappedType {
+                        type_name: body.type_name().to_owned(),
+                        used_by: used_by.to_owned(),
+                    })?;
+
+                let module = ident(module);
+                let name = ident(name);
+                let type_arguments = datatype
+                    .type_parameters
+                    .iter()
+                    .zip(&body.type_parameter_instantiation)
+                    .filter(|(parameter, _)| !parameter.is_phantom())
+                    .map(|(_, argument)| self.rust_type(argument, generics, used_by))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                if type_arguments.is_empty() {
+                    quote! { super::#module::#name }
+                } else {
+                    quote! { super::#module::#name<#(#type_arguments),*> }
+                }
+            }
+        };
+        Ok(type_)
+    }
+}
+
+/// The Rust type parameters of a generated type, one for each non-phantom type parameter of the
+/// Move type, along with which of them are used by its fields.
+struct Generics {
+    idents: Vec<Option<Ident>>,
+    used: RefCell<BTreeSet<usize>>,
+}
+
+impl Generics {
+    fn new(idents: Vec<Option<Ident>>) -> Self {
+        Self {
+            idents,
+            used: RefCell::default(),
+        }
+    }
+
+    /// The Rust type parameter for a Move type parameter, recording that it is used.
+    fn use_parameter(&self, index: u32) -> Option<&Ident> {
+        let index = index as usize;
+        let ident = self.idents.get(index)?.as_ref()?;
+        self.used.borrow_mut().insert(index);
+        Some(ident)
+    }
+
+    /// A `PhantomData` of the type parameters which aren't used by any field, since Rust requires
+    /// every type parameter to be used.
+    fn unused(&self) -> Option<TokenStream> {
+        let used = self.used.borrow();
+        let unused = self
+            .idents
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !used.contains(index))
+            .filter_map(|(_, ident)| ident.as_ref())
+            .collect::<Vec<_>>();
+        (!unused.is_empty()).then(|| quote! { ::std::marker::PhantomData<(#(#unused,)*)> })
+    }
+}
+
+const STD: Address = Address::from_static("0x1");
+
+fn parse_type_name(type_name: &str) -> Result<TypeKey, Error> {
+    let invalid = || Error::InvalidTypeName(type_name.to_owned());
+    let mut parts = type_name.split("::");
+    let (Some(address), Some(module), Some(name), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(invalid());
+    };
+    let address = address.parse::<Address>().map_err(|_| invalid())?;
+    Ok((address, module.to_owned(), name.to_owned()))
+}
+
+/// The Rust type of a Move type which can only be passed to a function as a pure value, or `None`
+/// if values of the type are objects or may be the result of another command.
+fn pure_type(body: &OpenSignatureBody) -> Option<TokenStream> {
+    let type_ = match body.r#type() {
+        Type::Address => quote! { ::myso_sdk_types::Address },
+        Type::Bool => quote! { bool },
+        Type::U8 => quote! { u8 },
+        Type::U16 => quote! { u16 },
+        Type::U32 => quote! { u32 },
+        Type::U64 => quote! { u64 },
+        Type::U128 => quote! { u128 },
+        Type::U256 => quote! { ::myso_sdk_types::U256 },
+        Type::Vector => {
+            let element = pure_type(body.type_parameter_instantiation.first()?)?;
+            quote! { ::std::vec::Vec<#element> }
+        }
+        Type::Datatype => {
+            let (address, module, name) = parse_type_name(body.type_name()).ok()?;
+            match (address, module.as_str(), name.as_str()) {
+                (STD, "string" | "ascii", "String") => quote! { ::std::string::String },
+                (STD, "option", "Option") => {
+                    let inner = pure_type(body.type_parameter_instantiation.first()?)?;
+                    quote! { ::std::option::Option<#inner> }
+                }
+                (Address::TWO, "object", "ID") => quote! { ::myso_sdk_types::Address },
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+    Some(type_)
+}
+
+fn is_tx_context(body: &OpenSignatureBody) -> bool {
+    body.r#type() == Type::Datatype
+        && parse_type_name(body.type_name()).is_ok_and(|(address, module, name)| {
+            address == Address::TWO && module == "tx_context" && name == "TxContext"
+        })
+}
+
+fn display_signature(signature: &OpenSignature) -> String {
+    let reference = match signature.reference() {
+        Reference::Immutable => "&",
+        Reference::Mutable => "&mut ",
+        _ => "",
+    };
+    format!("{reference}{}", display_body(signature.body()))
+}
+
+fn display_body(body: &OpenSignatureBody) -> String {
+    let type_arguments = || {
+        if body.type_parameter_instantiation.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "<{}>",
+                body.type_parameter_instantiation
+                    .iter()
+                    .map(display_body)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        }
+    };
+
+    match body.r#type() {
+        Type::Address => "address".to_owned(),
+        Type::Bool => "bool".to_owned(),
+        Type::U8 => "u8".to_owned(),
+        Type::U16 => "u16".to_owned(),
+        Type::U32 => "u32".to_owned(),
+        Type::U64 => "u64".to_owned(),
+        Type::U128 => "u128".to_owned(),
+        Type::U256 => "u256".to_owned(),
+        Type::Vector => format!("vector{}", type_arguments()),
+        Type::Datatype => format!("{}{}", body.type_name(), type_arguments()),
+        Type::Parameter => format!("T{}", body.type_parameter()),
+        _ => "_".to_owned(),
+    }
+}
@@ -0,0 +1,64 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+use myso_move_codegen::Generator;
+
+const USAGE: &str = "\
+Generate Rust bindings for a Move package
+
+Usage: myso-move-codegen <PACKAGE_JSON> [--out <FILE>] [--extern <MOVE_TYPE>=<RUST_TYPE>]...
+
+Arguments:
+  <PACKAGE_JSON>  A `GetPackageResponse` or `Package` encoded as JSON
+
+Options:
+  --out <FILE>                        Write the bindings to FILE instead of stdout
+  --extern <MOVE_TYPE>=<RUST_TYPE>    Map a Move datatype defined outside of the package, e.g.
+                                      `0x2::coin::Coin=my_crate::Coin`
+";
+
+fn main() {
+    if let Err(error) = run(std::env::args().skip(1)) {
+        eprintln!("error: {error}\n\n{USAGE}");
+        std::process::exit(1);
+    }
+}
+
+fn run(mut args: impl Iterator<Item = String>) -> Result<(), Box<dyn std::error::Error>> {
+    let mut package = None;
+    let mut out = None;
+    let mut externs = Vec::new();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--out" => out = Some(args.next().ok_or("missing value for --out")?),
+            "--extern" => {
+                let value = args.next().ok_or("missing value for --extern")?;
+                let (move_type, rust_type) = value
+                    .split_once('=')
+                    .ok_or("--extern must be of the form <MOVE_TYPE>=<RUST_TYPE>")?;
+                externs.push((move_type.to_owned(), rust_type.to_owned()));
+            }
+            "-h" | "--help" => {
+                print!("{USAGE}");
+                return Ok(());
+            }
+            _ if package.is_none() && !arg.starts_with("--") => package = Some(arg),
+            _ => return Err(format!("unexpected argument {arg}").into()),
+        }
+    }
+
+    let package = package.ok_or("missing package json")?;
+    let mut generator = Generator::from_json(&std::fs::read_to_string(package)?)?;
+    for (move_type, rust_type) in &externs {
+        generator = generator.extern_type(move_type, rust_type)?;
+    }
+
+    match out {
+        Some(out) => generator.write_to_file(out)?,
+        None => print!("{}", generator.generate()?),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,203 @@
+// @generated by myso-move-codegen from package 0x00000000000000000000000000000000000000000000000000000000000000c2. Do not edit.
+
+/// The storage id of the package these bindings were generated from.
+pub const PACKAGE_ID: ::myso_sdk_types::Address = ::myso_sdk_types::Address::from_static(
+    "0x00000000000000000000000000000000000000000000000000000000000000c2",
+);
+/// The id of the first version of the package, which defines the types introduced by it.
+pub const ORIGINAL_ID: ::myso_sdk_types::Address = ::myso_sdk_types::Address::from_static(
+    "0x00000000000000000000000000000000000000000000000000000000000000c1",
+);
+/// Bindings for the `counter` module.
+pub mod counter {
+    /// The name of this module.
+    pub const MODULE_NAME: &str = "counter";
+    /// The Move type `0x00000000000000000000000000000000000000000000000000000000000000c1::counter::Counter`.
+    #[derive(Clone, Debug, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    pub struct Counter {
+        pub id: ::myso_sdk_types::Address,
+        pub owner: ::myso_sdk_types::Address,
+        pub value: u64,
+        pub status: super::counter::Status,
+    }
+    impl Counter {
+        /// The struct tag of this type.
+        pub fn struct_tag() -> ::myso_sdk_types::StructTag {
+            ::myso_sdk_types::StructTag::new(
+                ::myso_sdk_types::Address::from_static(
+                    "0x00000000000000000000000000000000000000000000000000000000000000c1",
+                ),
+                ::myso_sdk_types::Identifier::from_static("counter"),
+                ::myso_sdk_types::Identifier::from_static("Counter"),
+                ::std::vec::Vec::new(),
+            )
+        }
+    }
+    /// The Move type `0x00000000000000000000000000000000000000000000000000000000000000c1::counter::Marker`.
+    #[derive(Clone, Debug, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    pub struct Marker<T0> {
+        pub amount: ::myso_sdk_types::U256,
+        #[serde(skip)]
+        pub __phantom: ::std::marker::PhantomData<(T0,)>,
+    }
+    impl<T0> Marker<T0> {
+        /// The struct tag of this type, instantiated with the provided type parameters.
+        pub fn struct_tag(
+            type_params: ::std::vec::Vec<::myso_sdk_types::TypeTag>,
+        ) -> ::myso_sdk_types::StructTag {
+            ::myso_sdk_types::StructTag::new(
+                ::myso_sdk_types::Address::from_static(
+                    "0x00000000000000000000000000000000000000000000000000000000000000c1",
+                ),
+                ::myso_sdk_types::Identifier::from_static("counter"),
+                ::myso_sdk_types::Identifier::from_static("Marker"),
+                type_params,
+            )
+        }
+    }
+    /// The Move type `0x00000000000000000000000000000000000000000000000000000000000000c2::counter::Status`.
+    #[derive(Clone, Debug, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    pub enum Status {
+        Active,
+        Paused { reason: ::std::string::String },
+    }
+    impl Status {
+        /// The struct tag of this type.
+        pub fn struct_tag() -> ::myso_sdk_types::StructTag {
+            ::myso_sdk_types::StructTag::new(
+                ::myso_sdk_types::Address::from_static(
+                    "0x00000000000000000000000000000000000000000000000000000000000000c2",
+                ),
+                ::myso_sdk_types::Identifier::from_static("counter"),
+                ::myso_sdk_types::Identifier::from_static("Status"),
+                ::std::vec::Vec::new(),
+            )
+        }
+    }
+    /// The Move type `0x00000000000000000000000000000000000000000000000000000000000000c1::counter::Tagged`.
+    #[derive(Clone, Debug, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    pub struct Tagged {
+        pub marker: super::counter::Marker<bool>,
+    }
+    impl Tagged {
+        /// The struct tag of this type.
+        pub fn struct_tag() -> ::myso_sdk_types::StructTag {
+            ::myso_sdk_types::StructTag::new(
+                ::myso_sdk_types::Address::from_static(
+                    "0x00000000000000000000000000000000000000000000000000000000000000c1",
+                ),
+                ::myso_sdk_types::Identifier::from_static("counter"),
+                ::myso_sdk_types::Identifier::from_static("Tagged"),
+                ::std::vec::Vec::new(),
+            )
+        }
+    }
+    /// The Move type `0x00000000000000000000000000000000000000000000000000000000000000c1::counter::Wrapper`.
+    #[derive(Clone, Debug, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    pub struct Wrapper<T1> {
+        pub balance: u64,
+        pub items: ::std::vec::Vec<T1>,
+        pub r#type: ::std::option::Option<u8>,
+    }
+    impl<T1> Wrapper<T1> {
+        /// The struct tag of this type, instantiated with the provided type parameters.
+        pub fn struct_tag(
+            type_params: ::std::vec::Vec<::myso_sdk_types::TypeTag>,
+        ) -> ::myso_sdk_types::StructTag {
+            ::myso_sdk_types::StructTag::new(
+                ::myso_sdk_types::Address::from_static(
+                    "0x00000000000000000000000000000000000000000000000000000000000000c1",
+                ),
+                ::myso_sdk_types::Identifier::from_static("counter"),
+                ::myso_sdk_types::Identifier::from_static("Wrapper"),
+                type_params,
+            )
+        }
+    }
+    /// Calls to the functions of the `counter` module, added to a `TransactionBuilder`.
+    ///
+    /// A method which has the same name as a method of `TransactionBuilder` itself
+    /// needs to be called through the trait, e.g. `Trait::method(&mut builder, ..)`.
+    pub trait CounterCalls {
+        /// Call `0x00000000000000000000000000000000000000000000000000000000000000c2::counter::increment`.
+        ///
+        /// Move signature: `(&mut 0x00000000000000000000000000000000000000000000000000000000000000c1::counter::Counter, u64): u64`
+        fn increment(
+            &mut self,
+            counter: ::myso_transaction_builder::Argument,
+            arg1: u64,
+        ) -> ::myso_transaction_builder::Argument;
+        /// Call `0x00000000000000000000000000000000000000000000000000000000000000c2::counter::stats`.
+        ///
+        /// Move signature: `(&0x00000000000000000000000000000000000000000000000000000000000000c1::counter::Counter, 0x1::string::String, vector<address>, 0x1::option::Option<u256>): (u64, bool)`
+        fn stats(
+            &mut self,
+            counter: ::myso_transaction_builder::Argument,
+            arg1: ::std::string::String,
+            arg2: ::std::vec::Vec<::myso_sdk_types::Address>,
+            arg3: ::std::option::Option<::myso_sdk_types::U256>,
+        ) -> ::std::vec::Vec<::myso_transaction_builder::Argument>;
+        /// Call `0x00000000000000000000000000000000000000000000000000000000000000c2::counter::wrap`.
+        ///
+        /// Move signature: `(0x2::coin::Coin<T0>, vector<T1>)`
+        fn wrap(
+            &mut self,
+            type_args: [::myso_sdk_types::TypeTag; 2],
+            coin: ::myso_transaction_builder::Argument,
+            arg1: ::myso_transaction_builder::Argument,
+        );
+    }
+    impl CounterCalls for ::myso_transaction_builder::TransactionBuilder {
+        fn increment(
+            &mut self,
+            counter: ::myso_transaction_builder::Argument,
+            arg1: u64,
+        ) -> ::myso_transaction_builder::Argument {
+            let arg1 = self.pure(&arg1);
+            self.move_call(
+                ::myso_transaction_builder::Function::new(
+                    super::PACKAGE_ID,
+                    ::myso_sdk_types::Identifier::from_static("counter"),
+                    ::myso_sdk_types::Identifier::from_static("increment"),
+                ),
+                ::std::vec![counter, arg1],
+            )
+        }
+        fn stats(
+            &mut self,
+            counter: ::myso_transaction_builder::Argument,
+            arg1: ::std::string::String,
+            arg2: ::std::vec::Vec<::myso_sdk_types::Address>,
+            arg3: ::std::option::Option<::myso_sdk_types::U256>,
+        ) -> ::std::vec::Vec<::myso_transaction_builder::Argument> {
+            let arg1 = self.pure(&arg1);
+            let arg2 = self.pure(&arg2);
+            let arg3 = self.pure(&arg3);
+            self.move_call(
+                    ::myso_transaction_builder::Function::new(
+                        super::PACKAGE_ID,
+                        ::myso_sdk_types::Identifier::from_static("counter"),
+                        ::myso_sdk_types::Identifier::from_static("stats"),
+                    ),
+                    ::std::vec![counter, arg1, arg2, arg3],
+                )
+                .to_nested(2)
+        }
+        fn wrap(
+            &mut self,
+            type_args: [::myso_sdk_types::TypeTag; 2],
+            coin: ::myso_transaction_builder::Argument,
+            arg1: ::myso_transaction_builder::Argument,
+        ) {
+            self.move_call(
+                ::myso_transaction_builder::Function::new(
+                        super::PACKAGE_ID,
+                        ::myso_sdk_types::Identifier::from_static("counter"),
+                        ::myso_sdk_types::Identifier::from_static("wrap"),
+                    )
+                    .with_type_args(::std::vec::Vec::from(type_args)),
+                ::std::vec![coin, arg1],
+            );
+        }
+    }
+}
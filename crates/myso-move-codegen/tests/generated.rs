@@ -0,0 +1,161 @@
+use myso_move_codegen::Generator;
+use myso_sdk_types::Address;
+use myso_sdk_types::Argument;
+use myso_sdk_types::Command;
+use myso_sdk_types::Input;
+use myso_sdk_types::TransactionKind;
+use myso_sdk_types::TypeTag;
+use myso_sdk_types::U256;
+use myso_transaction_builder::ObjectInput;
+use myso_transaction_builder::TransactionBuilder;
+
+// Not every generated item is exercised by these tests
+#[allow(dead_code)]
+mod bindings {
+    include!("fixtures/counter.rs");
+}
+
+use bindings::counter;
+
+const PACKAGE_JSON: &str = include_str!("fixtures/counter.json");
+
+#[test]
+fn bindings_are_up_to_date() {
+    let generated = Generator::from_json(PACKAGE_JSON)
+        .unwrap()
+        .generate()
+        .unwrap();
+    assert_eq!(
+        generated,
+        include_str!("fixtures/counter.rs"),
+        "regenerate with `cargo run -p myso-move-codegen -- tests/fixtures/counter.json --out tests/fixtures/counter.rs`"
+    );
+}
+
+#[test]
+fn unmapped_extern_type() {
+    let json = PACKAGE_JSON.replace("0x1::string::String", "0x5::name::Name");
+    let error = Generator::from_json(&json).unwrap().generate().unwrap_err();
+    assert!(matches!(
+        error,
+        myso_move_codegen::Error::UnmappedType { .. }
+    ));
+
+    Generator::from_json(&json)
+        .unwrap()
+        .extern_type("0x5::name::Name", "::std::string::String")
+        .unwrap()
+        .generate()
+        .unwrap();
+}
+
+#[test]
+fn decode_types() {
+    let id = Address::from_static("0x1234");
+    let owner = Address::from_static("0xabcd");
+
+    // `Counter` with a `Status::Paused` status
+    let bytes = bcs::to_bytes(&(id, owner, 42u64, 1u8, "maintenance")).unwrap();
+    let decoded: counter::Counter = bcs::from_bytes(&bytes).unwrap();
+    assert_eq!(
+        decoded,
+        counter::Counter {
+            id,
+            owner,
+            value: 42,
+            status: counter::Status::Paused {
+                reason: "maintenance".to_owned()
+            },
+        }
+    );
+    assert_eq!(bcs::to_bytes(&decoded).unwrap(), bytes);
+
+    // The phantom type parameter of `Wrapper` is omitted
+    let bytes = bcs::to_bytes(&(7u64, vec![1u16, 2], Some(3u8))).unwrap();
+    let decoded: counter::Wrapper<u16> = bcs::from_bytes(&bytes).unwrap();
+    assert_eq!(decoded.balance, 7);
+    assert_eq!(decoded.items, [1, 2]);
+    assert_eq!(decoded.r#type, Some(3));
+
+    assert_eq!(
+        counter::Wrapper::<u16>::struct_tag(vec![TypeTag::U8, TypeTag::U16]).to_string(),
+        format!("{}::counter::Wrapper<u8, u16>", bindings::ORIGINAL_ID)
+    );
+    // `u256` fields decode to `U256` and the unused type parameter of `Marker` is kept as a
+    // `PhantomData` which isn't serialized
+    let amount = U256::from(1_000_000u64);
+    let bytes = bcs::to_bytes(&amount).unwrap();
+    let decoded: counter::Tagged = bcs::from_bytes(&bytes).unwrap();
+    assert_eq!(decoded.marker.amount, amount);
+    assert_eq!(bcs::to_bytes(&decoded).unwrap(), bytes);
+
+    assert_eq!(
+        counter::Status::struct_tag().to_string(),
+        format!("{}::counter::Status", bindings::PACKAGE_ID)
+    );
+}
+
+#[test]
+fn build_calls() {
+    use counter::CounterCalls;
+
+    let mut tx = TransactionBuilder::new();
+    let object = tx.object(ObjectInput::shared(Address::from_static("0x55"), 1, true));
+    let value = tx.increment(object, 5);
+
+    let coin = tx.gas();
+    let items = tx.make_move_vec(Some(TypeTag::U8), vec![]);
+    tx.wrap([TypeTag::Bool, TypeTag::U8], coin, items);
+
+    let stats = tx.stats(
+        object,
+        "label".to_owned(),
+        vec![Address::TWO],
+        Some(U256::from(7u64)),
+    );
+    assert_eq!(stats.len(), 2);
+    let recipient = tx.pure(&Address::TWO);
+    tx.transfer_objects(vec![value, stats[1]], recipient);
+
+    let TransactionKind::ProgrammableTransaction(ptb) = tx.try_build_kind().unwrap() else {
+        panic!("expected a programmable transaction");
+    };
+
+    let Command::MoveCall(increment) = &ptb.commands[0] else {
+        panic!("expected a move call");
+    };
+    assert_eq!(increment.package, bindings::PACKAGE_ID);
+    assert_eq!(increment.module.as_str(), counter::MODULE_NAME);
+    assert_eq!(increment.function.as_str(), "increment");
+    assert_eq!(increment.arguments.len(), 2);
+
+    // Pure parameters are added as inputs
+    let Argument::Input(amount) = increment.arguments[1] else {
+        panic!("expected an input");
+    };
+    assert_eq!(
+        ptb.inputs[amount as usize],
+        Input::Pure(bcs::to_bytes(&5u64).unwrap())
+    );
+
+    let Command::MoveCall(wrap) = &ptb.commands[2] else {
+        panic!("expected a move call");
+    };
+    assert_eq!(wrap.function.as_str(), "wrap");
+    assert_eq!(wrap.type_arguments, [TypeTag::Bool, TypeTag::U8]);
+
+    let Command::MoveCall(stats) = &ptb.commands[3] else {
+        panic!("expected a move call");
+    };
+    assert_eq!(stats.function.as_str(), "stats");
+    assert_eq!(stats.arguments.len(), 4);
+
+    // Each return value of a call can be used on its own
+    let Command::TransferObjects(transfer) = &ptb.commands[4] else {
+        panic!("expected a transfer");
+    };
+    assert_eq!(
+        transfer.objects,
+        [Argument::Result(0), Argument::NestedResult(3, 1)]
+    );
+}
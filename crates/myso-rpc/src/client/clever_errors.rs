@@ -0,0 +1,328 @@
+use myso_sdk_types::MoveLocation;
+use myso_sdk_types::MoveTypeLayout;
+use myso_sdk_types::MoveValue;
+
+use crate::proto::myso::rpc::v2::CleverError as CleverErrorProto;
+use crate::proto::myso::rpc::v2::GetPackageRequest;
+
+use super::Client;
+use super::Result;
+
+/// Set in abort codes which are "clever errors".
+const CLEVER_ERROR_TAG: u64 = 1 << 63;
+
+/// The value of an index in an abort code which isn't present.
+const SENTINEL: u16 = u16::MAX;
+
+/// The magic bytes every serialized Move module starts with.
+const MOVE_MAGIC: [u8; 4] = [0xa1, 0x1c, 0xeb, 0x0b];
+
+/// The kind of the constant pool table in a serialized Move module.
+const CONSTANT_POOL: u8 = 0x6;
+
+/// The details of a Move abort whose abort code is a "clever error".
+///
+/// Clever error abort codes pack the source line of the abort along with the indices of two
+/// constants in the aborting module's constant pool: the name of the error constant and its
+/// value, which is commonly a message describing the error.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CleverError {
+    /// The line in the module's source which aborted.
+    pub line_number: u16,
+
+    /// The name of the error constant, if the abort used one.
+    pub constant_name: Option<String>,
+
+    /// The value of the error constant, with `vector<u8>` values rendered as strings.
+    pub value: Option<String>,
+}
+
+impl CleverError {
+    /// Decode a clever error from an abort code and the bytecode of the module which aborted.
+    ///
+    /// Returns `None` if the abort code isn't a clever error or if the module's constant pool
+    /// doesn't contain the constants it refers to.
+    pub fn from_module(abort_code: u64, module: &[u8]) -> Option<Self> {
+        if abort_code & CLEVER_ERROR_TAG == 0 {
+            return None;
+        }
+
+        let line_number = (abort_code >> 32) as u16;
+        let identifier_index = (abort_code >> 16) as u16;
+        let constant_index = abort_code as u16;
+
+        if identifier_index == SENTINEL && constant_index == SENTINEL {
+            return Some(Self {
+                line_number,
+                constant_name: None,
+                value: None,
+            });
+        }
+
+        let constants = constant_pool(module)?;
+        let constant = |index: u16| -> Option<&MoveValue> {
+            (index != SENTINEL)
+                .then(|| constants.get(index as usize))
+                .flatten()
+        };
+
+        let constant_name = match constant(identifier_index)? {
+            MoveValue::Vector(_, bytes) => String::from_utf8(bytes_of(bytes)?).ok()?,
+            _ => return None,
+        };
+        let value = constant(constant_index).map(render);
+
+        Some(Self {
+            line_number,
+            constant_name: Some(constant_name),
+            value,
+        })
+    }
+}
+
+impl From<&CleverErrorProto> for CleverError {
+    /// Convert a clever error already decoded by the server.
+    fn from(error: &CleverErrorProto) -> Self {
+        let value = error.rendered_opt().map(ToOwned::to_owned).or_else(|| {
+            error
+                .raw_opt()
+                .map(|raw| String::from_utf8_lossy(raw).into_owned())
+        });
+        Self {
+            line_number: error.line_number() as u16,
+            constant_name: error.constant_name_opt().map(ToOwned::to_owned),
+            value,
+        }
+    }
+}
+
+impl std::fmt::Display for CleverError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.constant_name, &self.value) {
+            (Some(name), Some(value)) => write!(f, "{name} at line {}: {value}", self.line_number),
+            (Some(name), None) => write!(f, "{name} at line {}", self.line_number),
+            (None, _) => write!(f, "abort at line {}", self.line_number),
+        }
+    }
+}
+
+impl Client {
+    /// Decode the clever error of a Move abort, fetching the bytecode of the module which
+    /// aborted with `GetPackage`.
+    ///
+    /// Returns `None` if the abort code isn't a clever error.
+    pub async fn decode_clever_error(
+        &mut self,
+        location: &MoveLocation,
+        abort_code: u64,
+    ) -> Result<Option<CleverError>> {
+        if abort_code & CLEVER_ERROR_TAG == 0 {
+            return Ok(None);
+        }
+
+        let package = self
+            .package_client()
+            .get_package(GetPackageRequest::new(&location.package))
+            .await?
+            .into_inner()
+            .package
+            .unwrap_or_default();
+        let module = package
+            .modules
+            .iter()
+            .find(|module| module.name() == location.module.as_str())
+            .ok_or_else(|| {
+                tonic::Status::not_found(format!(
+                    "module {}::{} not found",
+                    location.package, location.module
+                ))
+            })?;
+
+        Ok(CleverError::from_module(abort_code, module.contents()))
+    }
+}
+
+/// Read the constants of a serialized Move module.
+fn constant_pool(module: &[u8]) -> Option<Vec<MoveValue>> {
+    let mut reader = Reader(module);
+    if reader.take(MOVE_MAGIC.len())? != MOVE_MAGIC {
+        return None;
+    }
+    // The bytecode version
+    reader.take(4)?;
+
+    let table_count = reader.uleb128()?;
+    let mut pool = None;
+    for _ in 0..table_count {
+        let kind = reader.take(1)?[0];
+        let offset = reader.uleb128()? as usize;
+        let len = reader.uleb128()? as usize;
+        if kind == CONSTANT_POOL {
+            pool = Some((offset, len));
+        }
+    }
+
+    // Table offsets are relative to the end of the table headers
+    let Some((offset, len)) = pool else {
+        return Some(vec![]);
+    };
+    let mut reader = Reader(reader.0.get(offset..offset.checked_add(len)?)?);
+
+    let mut constants = Vec::new();
+    while !reader.0.is_empty() {
+        let layout = reader.layout(0)?;
+        let len = reader.uleb128()? as usize;
+        let value = MoveValue::from_bcs(reader.take(len)?, &layout).ok()?;
+        constants.push(value);
+    }
+    Some(constants)
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if len > self.0.len() {
+            return None;
+        }
+        let (bytes, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Some(bytes)
+    }
+
+    fn uleb128(&mut self) -> Option<u64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.take(1)?[0];
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Some(value);
+            }
+        }
+        None
+    }
+
+    /// Read the signature token of a constant, which can only be a primitive or a vector.
+    fn layout(&mut self, depth: usize) -> Option<MoveTypeLayout> {
+        // Constants are limited to a small nesting depth by the bytecode verifier
+        if depth > 16 {
+            return None;
+        }
+
+        let layout = match self.take(1)?[0] {
+            0x1 => MoveTypeLayout::Bool,
+            0x2 => MoveTypeLayout::U8,
+            0x3 => MoveTypeLayout::U64,
+            0x4 => MoveTypeLayout::U128,
+            0x5 => MoveTypeLayout::Address,
+            0xa => MoveTypeLayout::Vector(Box::new(self.layout(depth + 1)?)),
+            0xd => MoveTypeLayout::U16,
+            0xe => MoveTypeLayout::U32,
+            0xf => MoveTypeLayout::U256,
+            _ => return None,
+        };
+        Some(layout)
+    }
+}
+
+fn bytes_of(values: &[MoveValue]) -> Option<Vec<u8>> {
+    values
+        .iter()
+        .map(|value| match value {
+            MoveValue::U8(byte) => Some(*byte),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Render a constant, displaying byte vectors as strings when they are valid UTF-8.
+fn render(value: &MoveValue) -> String {
+    match value {
+        MoveValue::Bool(value) => value.to_string(),
+        MoveValue::U8(value) => value.to_string(),
+        MoveValue::U16(value) => value.to_string(),
+        MoveValue::U32(value) => value.to_string(),
+        MoveValue::U64(value) => value.to_string(),
+        MoveValue::U128(value) => value.to_string(),
+        MoveValue::U256(value) => value.to_string(),
+        MoveValue::Address(address) => address.to_string(),
+        MoveValue::Vector(_, values) => {
+            if let Some(string) = bytes_of(values).and_then(|bytes| String::from_utf8(bytes).ok()) {
+                return string;
+            }
+            let values = values.iter().map(render).collect::<Vec<_>>();
+            format!("[{}]", values.join(", "))
+        }
+        // Constants can't contain structs or enums
+        MoveValue::Struct(_) | MoveValue::Variant(_) => format!("{value:?}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A serialized module containing only a constant pool.
+    fn module(constants: &[(&[u8], Vec<u8>)]) -> Vec<u8> {
+        let mut pool = Vec::new();
+        for (token, value) in constants {
+            pool.extend_from_slice(token);
+            pool.push(value.len() as u8);
+            pool.extend(value);
+        }
+
+        let mut module = MOVE_MAGIC.to_vec();
+        module.extend(6u32.to_le_bytes());
+        module.extend([1, CONSTANT_POOL, 0, pool.len() as u8]);
+        module.extend(pool);
+        module
+    }
+
+    fn abort_code(line: u16, identifier: u16, constant: u16) -> u64 {
+        CLEVER_ERROR_TAG | (line as u64) << 32 | (identifier as u64) << 16 | constant as u64
+    }
+
+    #[test]
+    fn clever_errors() {
+        let module = module(&[
+            (&[0x3], bcs::to_bytes(&7u64).unwrap()),
+            (&[0xa, 0x2], bcs::to_bytes("EInsufficientFunds").unwrap()),
+            (&[0xa, 0x2], bcs::to_bytes("not enough funds").unwrap()),
+            (&[0xa, 0x3], bcs::to_bytes(&vec![1u64, 2]).unwrap()),
+        ]);
+
+        let error = CleverError::from_module(abort_code(42, 1, 2), &module).unwrap();
+        assert_eq!(
+            error,
+            CleverError {
+                line_number: 42,
+                constant_name: Some("EInsufficientFunds".to_owned()),
+                value: Some("not enough funds".to_owned()),
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "EInsufficientFunds at line 42: not enough funds"
+        );
+
+        let error = CleverError::from_module(abort_code(7, 1, 3), &module).unwrap();
+        assert_eq!(error.value.as_deref(), Some("[1, 2]"));
+
+        let error = CleverError::from_module(abort_code(9, SENTINEL, SENTINEL), &[]).unwrap();
+        assert_eq!(error.to_string(), "abort at line 9");
+
+        // Not a clever error
+        assert_eq!(CleverError::from_module(7, &module), None);
+        // The identifier must be a string
+        assert_eq!(CleverError::from_module(abort_code(1, 0, 2), &module), None);
+        // Out of bounds
+        assert_eq!(
+            CleverError::from_module(abort_code(1, 1, 9), &module).and_then(|error| error.value),
+            None
+        );
+        assert_eq!(
+            CleverError::from_module(abort_code(1, 1, 2), &module[..20]),
+            None
+        );
+    }
+}
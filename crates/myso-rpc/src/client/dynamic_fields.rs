@@ -0,0 +1,213 @@
+use futures::Stream;
+use futures::StreamExt;
+use myso_sdk_types::Address;
+use myso_sdk_types::Identifier;
+use myso_sdk_types::StructTag;
+use myso_sdk_types::TypeTag;
+use myso_sdk_types::framework::Field;
+use prost_types::FieldMask;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::field::FieldMaskUtil;
+use crate::proto::myso::rpc::v2::Bcs;
+use crate::proto::myso::rpc::v2::DynamicField;
+use crate::proto::myso::rpc::v2::GetObjectRequest;
+use crate::proto::myso::rpc::v2::ListDynamicFieldsRequest;
+use crate::proto::myso::rpc::v2::Object;
+
+use super::Client;
+use super::Result;
+
+/// A value stored in a `0x2::linked_table::LinkedTable<K, V>`.
+///
+/// Entries of a `LinkedTable` are stored as dynamic fields whose values are nodes linking each
+/// entry to its neighbours, so iterating over a `LinkedTable` yields nodes rather than values.
+#[derive(serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct LinkedTableNode<K, V> {
+    /// The key of the previous entry, if this isn't the head of the table.
+    pub prev: Option<K>,
+    /// The key of the next entry, if this isn't the tail of the table.
+    pub next: Option<K>,
+    /// The value of this entry.
+    pub value: V,
+}
+
+/// The type of the key of the `Field` holding a dynamic object field with a key of `key_type`.
+fn object_field_key_type(key_type: &TypeTag) -> TypeTag {
+    StructTag::new(
+        Address::TWO,
+        Identifier::from_static("dynamic_object_field"),
+        Identifier::from_static("Wrapper"),
+        vec![key_type.clone()],
+    )
+    .into()
+}
+
+fn deserialize<T: DeserializeOwned>(bcs: &Bcs) -> Result<T> {
+    bcs.deserialize()
+        .map_err(Into::into)
+        .map_err(tonic::Status::from_error)
+}
+
+impl Client {
+    /// Read the value of the dynamic field of `parent` with the given key.
+    ///
+    /// The ID of the field is derived locally from `key_type` and the BCS serialization of `key`,
+    /// which must be the Rust representation of a Move value of type `key_type`. Returns `None` if
+    /// `parent` has no such field.
+    pub async fn get_dynamic_field<K, V>(
+        &mut self,
+        parent: &Address,
+        key_type: &TypeTag,
+        key: &K,
+    ) -> Result<Option<V>>
+    where
+        K: Serialize + DeserializeOwned,
+        V: DeserializeOwned,
+    {
+        let key = bcs::to_bytes(key)
+            .map_err(Into::into)
+            .map_err(tonic::Status::from_error)?;
+        let field_id = parent.derive_dynamic_child_id(key_type, &key);
+
+        let Some(field) = self.get_object_contents(&field_id).await? else {
+            return Ok(None);
+        };
+        let field = deserialize::<Field<K, V>>(field.contents())?;
+        Ok(Some(field.value))
+    }
+
+    /// Read the object stored in the dynamic object field of `parent` with the given key.
+    ///
+    /// Like [`get_dynamic_field`](Self::get_dynamic_field), `key` must be the Rust
+    /// representation of a Move value of type `key_type`. Returns `None` if `parent` has no such
+    /// field.
+    pub async fn get_dynamic_object_field<K, V>(
+        &mut self,
+        parent: &Address,
+        key_type: &TypeTag,
+        key: &K,
+    ) -> Result<Option<V>>
+    where
+        K: Serialize + DeserializeOwned,
+        V: DeserializeOwned,
+    {
+        // The field holds the ID of the child object, keyed by the key wrapped in a `Wrapper`
+        let Some(child_id) = self
+            .get_dynamic_field::<K, Address>(parent, &object_field_key_type(key_type), key)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let Some(child) = self.get_object_contents(&child_id).await? else {
+            return Ok(None);
+        };
+        deserialize(child.contents()).map(Some)
+    }
+
+    /// Creates a stream of the keys and values of the dynamic fields of `parent`.
+    ///
+    /// This can be used to iterate over the entries of a `Table`, `Bag`, `ObjectTable` or
+    /// `ObjectBag` by passing the ID of its `UID` as `parent`. Values of dynamic object fields are
+    /// decoded from the contents of the child object. The entries of a `LinkedTable` can be read
+    /// by using [`LinkedTableNode`] as the value type.
+    ///
+    /// All fields of `parent` must have keys of type `K` and values of type `V`, otherwise
+    /// decoding will fail. Fields are yielded in an unspecified order.
+    pub fn list_dynamic_field_entries<K, V>(
+        &self,
+        parent: &Address,
+    ) -> impl Stream<Item = Result<(K, V)>> + 'static
+    where
+        K: DeserializeOwned + 'static,
+        V: DeserializeOwned + 'static,
+    {
+        let request = ListDynamicFieldsRequest::default()
+            .with_parent(parent)
+            .with_read_mask(FieldMask::from_paths([
+                "name",
+                "value",
+                "child_object.contents",
+            ]));
+
+        self.list_dynamic_fields(request)
+            .map(|field| field.and_then(|field| decode_entry(&field)))
+    }
+
+    async fn get_object_contents(&mut self, object_id: &Address) -> Result<Option<Object>> {
+        match self
+            .ledger_client()
+            .get_object(
+                GetObjectRequest::new(object_id).with_read_mask(FieldMask::from_str("contents")),
+            )
+            .await
+        {
+            Ok(response) => Ok(Some(response.into_inner().object.unwrap_or_default())),
+            Err(status) if status.code() == tonic::Code::NotFound => Ok(None),
+            Err(status) => Err(status),
+        }
+    }
+}
+
+fn decode_entry<K: DeserializeOwned, V: DeserializeOwned>(field: &DynamicField) -> Result<(K, V)> {
+    // The name of a dynamic object field is a `Wrapper<K>`, which has the same BCS
+    // representation as `K`
+    let key = deserialize(field.name())?;
+    let value = match field.child_object_opt() {
+        Some(child) => deserialize(child.contents())?,
+        None => deserialize(field.value())?,
+    };
+    Ok((key, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entries() {
+        let mut field = DynamicField::default();
+        field.set_name(Bcs::serialize(&7u64).unwrap());
+        field.set_value(Bcs::serialize(&"seven").unwrap());
+        assert_eq!(
+            decode_entry::<u64, String>(&field).unwrap(),
+            (7, "seven".to_owned())
+        );
+
+        let node = LinkedTableNode {
+            prev: None,
+            next: Some(8u64),
+            value: true,
+        };
+        let mut bytes = bcs::to_bytes(&node.prev).unwrap();
+        bytes.extend(bcs::to_bytes(&node.next).unwrap());
+        bytes.extend(bcs::to_bytes(&node.value).unwrap());
+        field.set_value(Bcs::from(bytes));
+        assert_eq!(
+            decode_entry::<u64, LinkedTableNode<u64, bool>>(&field).unwrap(),
+            (7, node)
+        );
+
+        // Values of dynamic object fields are read from the child object
+        let mut child = Object::default();
+        child.set_contents(Bcs::serialize(&(Address::THREE, 5u64)).unwrap());
+        field.set_child_object(child);
+        assert_eq!(
+            decode_entry::<u64, (Address, u64)>(&field).unwrap(),
+            (7, (Address::THREE, 5))
+        );
+
+        field.set_name(Bcs::serialize(&true).unwrap());
+        decode_entry::<u64, (Address, u64)>(&field).unwrap_err();
+    }
+
+    #[test]
+    fn object_field_key() {
+        assert_eq!(
+            object_field_key_type(&TypeTag::U64),
+            "0x2::dynamic_object_field::Wrapper<u64>".parse().unwrap()
+        );
+    }
+}
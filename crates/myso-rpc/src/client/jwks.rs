@@ -0,0 +1,280 @@
+use std::collections::HashMap;
+
+use myso_sdk_types::ActiveJwk;
+use myso_sdk_types::Address;
+use myso_sdk_types::AuthenticatorStateExpire;
+use myso_sdk_types::AuthenticatorStateUpdate;
+use myso_sdk_types::EndOfEpochTransactionKind;
+use myso_sdk_types::JwkId;
+use myso_sdk_types::TransactionKind;
+use myso_sdk_types::TypeTag;
+use prost_types::FieldMask;
+
+use crate::field::FieldMaskUtil;
+use crate::proto::myso::rpc::v2::GetObjectRequest;
+use crate::proto::myso::rpc::v2::GetServiceInfoRequest;
+
+use super::Client;
+use super::ResponseExt;
+use super::Result;
+
+/// The address of the `0x2::authenticator_state::AuthenticatorState` shared object.
+const AUTHENTICATOR_STATE_ADDRESS: Address = Address::from_static("0x7");
+
+/// Onchain representation of `0x2::authenticator_state::AuthenticatorState`.
+#[derive(serde::Deserialize, Debug)]
+struct AuthenticatorState {
+    id: Address,
+    version: u64,
+}
+
+/// Onchain representation of `0x2::authenticator_state::AuthenticatorStateInner`.
+#[derive(serde::Deserialize, Debug)]
+struct AuthenticatorStateInner {
+    #[allow(unused)]
+    version: u64,
+    active_jwks: Vec<ActiveJwk>,
+}
+
+/// Onchain representation of `0x2::dynamic_field::Field<u64, AuthenticatorStateInner>`.
+#[derive(serde::Deserialize, Debug)]
+struct AuthenticatorStateInnerField {
+    #[allow(unused)]
+    id: Address,
+    #[allow(unused)]
+    name: u64,
+    value: AuthenticatorStateInner,
+}
+
+/// The set of jwks which are currently active onchain.
+#[derive(Clone, Debug, Default)]
+pub struct ActiveJwks {
+    /// The epoch at which this set was read.
+    pub epoch: u64,
+    /// The active jwks.
+    pub jwks: Vec<ActiveJwk>,
+}
+
+impl Client {
+    /// Fetch the set of jwks which are currently active onchain, as recorded in the
+    /// `0x2::authenticator_state::AuthenticatorState` object.
+    pub async fn get_active_jwks(&mut self) -> Result<ActiveJwks> {
+        let response = self
+            .ledger_client()
+            .get_object(
+                GetObjectRequest::new(&AUTHENTICATOR_STATE_ADDRESS)
+                    .with_read_mask(FieldMask::from_str("contents")),
+            )
+            .await?;
+        let epoch = response.epoch().unwrap_or_default();
+        let state = response
+            .into_inner()
+            .object()
+            .contents()
+            .deserialize::<AuthenticatorState>()
+            .map_err(Into::into)
+            .map_err(tonic::Status::from_error)?;
+
+        // The inner state is stored as a dynamic field keyed by the version of the state
+        let inner_id = state.id.derive_dynamic_child_id(
+            &TypeTag::U64,
+            &bcs::to_bytes(&state.version)
+                .map_err(Into::into)
+                .map_err(tonic::Status::from_error)?,
+        );
+
+        let inner = self
+            .ledger_client()
+            .get_object(
+                GetObjectRequest::new(&inner_id).with_read_mask(FieldMask::from_str("contents")),
+            )
+            .await?
+            .into_inner()
+            .object()
+            .contents()
+            .deserialize::<AuthenticatorStateInnerField>()
+            .map_err(Into::into)
+            .map_err(tonic::Status::from_error)?;
+
+        Ok(ActiveJwks {
+            epoch,
+            jwks: inner.value.active_jwks,
+        })
+    }
+}
+
+/// A locally maintained copy of the onchain set of active jwks.
+///
+/// The set can be kept up to date either by periodically calling [`JwkCache::refresh`], which
+/// reloads the full set from the chain whenever a new epoch begins, or by feeding it the
+/// transactions of every checkpoint via [`JwkCache::apply_transaction_kind`], which applies
+/// `AuthenticatorStateUpdate` and `AuthenticatorStateExpire` system transactions as they happen.
+#[derive(Clone, Debug, Default)]
+pub struct JwkCache {
+    epoch: Option<u64>,
+    jwks: HashMap<JwkId, ActiveJwk>,
+}
+
+impl JwkCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The epoch at which this cache was last updated, if it has ever been updated.
+    pub fn epoch(&self) -> Option<u64> {
+        self.epoch
+    }
+
+    /// Iterate over the currently active jwks.
+    pub fn jwks(&self) -> impl Iterator<Item = &ActiveJwk> {
+        self.jwks.values()
+    }
+
+    /// Lookup an active jwk by its id.
+    pub fn get(&self, jwk_id: &JwkId) -> Option<&ActiveJwk> {
+        self.jwks.get(jwk_id)
+    }
+
+    /// Replace the contents of this cache with the full set of active jwks read from the chain.
+    pub fn load(&mut self, active_jwks: ActiveJwks) {
+        self.jwks = active_jwks
+            .jwks
+            .into_iter()
+            .map(|active_jwk| (active_jwk.jwk_id.clone(), active_jwk))
+            .collect();
+        self.epoch = Some(active_jwks.epoch);
+    }
+
+    /// Reload the set of active jwks from the chain if the chain has moved to a new epoch since
+    /// this cache was last updated.
+    ///
+    /// Returns `true` if the cache was reloaded.
+    pub async fn refresh(&mut self, client: &mut Client) -> Result<bool> {
+        let current_epoch = client
+            .ledger_client()
+            .get_service_info(GetServiceInfoRequest::default())
+            .await?
+            .into_inner()
+            .epoch;
+
+        if current_epoch.is_some() && current_epoch == self.epoch {
+            return Ok(false);
+        }
+
+        self.load(client.get_active_jwks().await?);
+        Ok(true)
+    }
+
+    /// Apply the newly active jwks from an `AuthenticatorStateUpdate` system transaction.
+    pub fn apply_update(&mut self, update: &AuthenticatorStateUpdate) {
+        for active_jwk in &update.new_active_jwks {
+            self.jwks
+                .entry(active_jwk.jwk_id.clone())
+                .and_modify(|existing| {
+                    if active_jwk.epoch > existing.epoch {
+                        *existing = active_jwk.clone();
+                    }
+                })
+                .or_insert_with(|| active_jwk.clone());
+        }
+        self.epoch = Some(self.epoch.map_or(update.epoch, |e| e.max(update.epoch)));
+    }
+
+    /// Expire all jwks that were last validated in an epoch earlier than `min_epoch`.
+    ///
+    /// This mirrors the behavior of the onchain authenticator state: the most recently validated
+    /// jwks for each issuer are always retained.
+    pub fn expire(&mut self, min_epoch: u64) {
+        let mut latest_epoch_per_issuer: HashMap<String, u64> = HashMap::new();
+        for active_jwk in self.jwks.values() {
+            let latest = latest_epoch_per_issuer
+                .entry(active_jwk.jwk_id.iss.clone())
+                .or_default();
+            *latest = (*latest).max(active_jwk.epoch);
+        }
+
+        self.jwks.retain(|jwk_id, active_jwk| {
+            active_jwk.epoch >= min_epoch
+                || latest_epoch_per_issuer[&jwk_id.iss] == active_jwk.epoch
+        });
+    }
+
+    /// Apply any jwk updates or expirations contained in a transaction.
+    ///
+    /// Transactions which do not modify the authenticator state are ignored, so every transaction
+    /// of every checkpoint can be passed through this method to keep the cache up to date.
+    pub fn apply_transaction_kind(&mut self, kind: &TransactionKind) {
+        match kind {
+            TransactionKind::AuthenticatorStateUpdate(update) => self.apply_update(update),
+            TransactionKind::EndOfEpoch(transactions) => {
+                for transaction in transactions {
+                    if let EndOfEpochTransactionKind::AuthenticatorStateExpire(
+                        AuthenticatorStateExpire { min_epoch, .. },
+                    ) = transaction
+                    {
+                        self.expire(*min_epoch);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use myso_sdk_types::Jwk;
+
+    use super::*;
+
+    fn active_jwk(iss: &str, kid: &str, epoch: u64) -> ActiveJwk {
+        ActiveJwk {
+            jwk_id: JwkId {
+                iss: iss.to_owned(),
+                kid: kid.to_owned(),
+            },
+            jwk: Jwk {
+                kty: "RSA".to_owned(),
+                e: "AQAB".to_owned(),
+                n: kid.to_owned(),
+                alg: "RS256".to_owned(),
+            },
+            epoch,
+        }
+    }
+
+    #[test]
+    fn update_and_expire() {
+        let mut cache = JwkCache::new();
+        cache.load(ActiveJwks {
+            epoch: 1,
+            jwks: vec![active_jwk("google", "a", 1), active_jwk("twitch", "b", 1)],
+        });
+
+        cache.apply_transaction_kind(&TransactionKind::AuthenticatorStateUpdate(
+            AuthenticatorStateUpdate {
+                epoch: 3,
+                round: 0,
+                new_active_jwks: vec![active_jwk("google", "c", 3)],
+                authenticator_obj_initial_shared_version: 1,
+            },
+        ));
+        assert_eq!(cache.epoch(), Some(3));
+        assert_eq!(cache.jwks().count(), 3);
+
+        cache.apply_transaction_kind(&TransactionKind::EndOfEpoch(vec![
+            EndOfEpochTransactionKind::AuthenticatorStateExpire(AuthenticatorStateExpire {
+                min_epoch: 2,
+                authenticator_object_initial_shared_version: 1,
+            }),
+        ]));
+
+        // google's old key is expired while twitch's only key is retained
+        let mut kids = cache
+            .jwks()
+            .map(|jwk| jwk.jwk_id.kid.as_str())
+            .collect::<Vec<_>>();
+        kids.sort();
+        assert_eq!(kids, ["b", "c"]);
+    }
+}
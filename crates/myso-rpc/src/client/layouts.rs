@@ -0,0 +1,380 @@
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+
+use myso_sdk_types::MoveTypeLayout;
+use myso_sdk_types::MoveValue;
+use myso_sdk_types::StructTag;
+use myso_sdk_types::TypeTag;
+
+use crate::proto::TryFromProtoError;
+use crate::proto::myso::rpc::v2::DatatypeDescriptor;
+use crate::proto::myso::rpc::v2::GetDatatypeRequest;
+
+use super::Client;
+use super::Result;
+
+/// The maximum depth of a type, beyond which a layout isn't resolved.
+const MAX_TYPE_DEPTH: usize = 128;
+
+/// Resolves the [`MoveTypeLayout`] of Move types, fetching the definitions of the datatypes they
+/// are made up of with `GetDatatype`.
+///
+/// Fetched datatypes are cached, so a single resolver should be reused for decoding many values.
+/// Since datatypes are identified by the package which defined them and published packages are
+/// immutable, cached datatypes never become stale.
+#[derive(Clone, Debug, Default)]
+pub struct LayoutResolver {
+    /// Datatypes keyed by their type without any type parameters.
+    datatypes: HashMap<StructTag, DatatypeDescriptor>,
+}
+
+impl LayoutResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a datatype to the cache, for example one read from a `GetPackage` response.
+    #[allow(clippy::result_large_err)]
+    pub fn insert(&mut self, datatype: DatatypeDescriptor) -> Result<(), TryFromProtoError> {
+        let name = datatype
+            .type_name
+            .as_deref()
+            .ok_or_else(|| TryFromProtoError::missing("type_name"))?
+            .parse::<StructTag>()
+            .map_err(|e| TryFromProtoError::invalid("type_name", e))?;
+        self.datatypes.insert(uninstantiated(&name), datatype);
+        Ok(())
+    }
+
+    /// Lookup a cached datatype.
+    pub fn get(&self, type_: &StructTag) -> Option<&DatatypeDescriptor> {
+        self.datatypes.get(&uninstantiated(type_))
+    }
+
+    /// Build the layout of a type using only cached datatypes.
+    ///
+    /// Returns an error if any of the datatypes the type is made up of haven't been cached.
+    #[allow(clippy::result_large_err)]
+    pub fn layout(&self, type_: &TypeTag) -> Result<MoveTypeLayout, TryFromProtoError> {
+        self.build(type_, 0)
+    }
+
+    /// Resolve the layout of a type, fetching any datatypes which haven't been cached.
+    pub async fn resolve(
+        &mut self,
+        client: &mut Client,
+        type_: &TypeTag,
+    ) -> Result<MoveTypeLayout> {
+        loop {
+            let mut missing = BTreeSet::new();
+            self.missing(type_, 0, &mut missing);
+            if missing.is_empty() {
+                break;
+            }
+
+            for datatype in missing {
+                let response = client
+                    .package_client()
+                    .get_datatype(GetDatatypeRequest::new(
+                        datatype.address(),
+                        datatype.module().as_str(),
+                        datatype.name().as_str(),
+                    ))
+                    .await?
+                    .into_inner();
+                self.insert(response.datatype.unwrap_or_default())
+                    .map_err(|e| tonic::Status::from_error(e.into()))?;
+
+                // Guard against a node returning a different datatype than was requested, which
+                // would otherwise never be considered resolved
+                if self.get(&datatype).is_none() {
+                    return Err(tonic::Status::internal(format!(
+                        "requested datatype {datatype} was not returned"
+                    )));
+                }
+            }
+        }
+
+        self.layout(type_)
+            .map_err(|e| tonic::Status::from_error(e.into()))
+    }
+
+    /// Decode the BCS representation of a value of the provided type, resolving its layout.
+    pub async fn decode(
+        &mut self,
+        client: &mut Client,
+        type_: &TypeTag,
+        bytes: &[u8],
+    ) -> Result<MoveValue> {
+        let layout = self.resolve(client, type_).await?;
+        MoveValue::from_bcs(bytes, &layout).map_err(|e| tonic::Status::from_error(e.into()))
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn build(&self, type_: &TypeTag, depth: usize) -> Result<MoveTypeLayout, TryFromProtoError> {
+        if depth > MAX_TYPE_DEPTH {
+            return Err(TryFromProtoError::invalid(
+                "type",
+                format!("type exceeds the maximum depth of {MAX_TYPE_DEPTH}"),
+            ));
+        }
+
+        match type_ {
+            TypeTag::Vector(element) => Ok(MoveTypeLayout::Vector(Box::new(
+                self.build(element, depth + 1)?,
+            ))),
+            TypeTag::Struct(struct_tag) => {
+                let datatype = self.get(struct_tag).ok_or_else(|| {
+                    TryFromProtoError::invalid("type", format!("unknown datatype {struct_tag}"))
+                })?;
+                datatype.layout(struct_tag.type_params(), &mut |field| {
+                    self.build(field, depth + 1)
+                })
+            }
+            primitive => MoveTypeLayout::from_primitive(primitive).ok_or_else(|| {
+                TryFromProtoError::invalid("type", format!("{primitive} has no layout"))
+            }),
+        }
+    }
+
+    /// Collect the datatypes which need to be fetched in order to build the layout of a type.
+    ///
+    /// Datatypes are only discovered once the datatypes containing them have been fetched, so
+    /// this must be repeated until nothing is missing.
+    #[allow(clippy::result_large_err)]
+    fn missing(&self, type_: &TypeTag, depth: usize, missing: &mut BTreeSet<StructTag>) {
+        if depth > MAX_TYPE_DEPTH {
+            return;
+        }
+
+        match type_ {
+            TypeTag::Vector(element) => self.missing(element, depth + 1, missing),
+            TypeTag::Struct(struct_tag) => {
+                for type_param in struct_tag.type_params() {
+                    self.missing(type_param, depth + 1, missing);
+                }
+
+                let Some(datatype) = self.get(struct_tag) else {
+                    missing.insert(uninstantiated(struct_tag));
+                    return;
+                };
+
+                let fields = datatype
+                    .fields
+                    .iter()
+                    .chain(datatype.variants.iter().flat_map(|variant| &variant.fields));
+                for field in fields {
+                    if let Some(Ok(field_type)) = field
+                        .r#type
+                        .as_ref()
+                        .map(|body| body.type_tag(struct_tag.type_params()))
+                    {
+                        self.missing(&field_type, depth + 1, missing);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn uninstantiated(type_: &StructTag) -> StructTag {
+    StructTag::new(
+        *type_.address(),
+        type_.module().clone(),
+        type_.name().clone(),
+        vec![],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::myso::rpc::v2::FieldDescriptor;
+    use crate::proto::myso::rpc::v2::OpenSignatureBody;
+    use crate::proto::myso::rpc::v2::TypeParameter;
+    use crate::proto::myso::rpc::v2::VariantDescriptor;
+    use crate::proto::myso::rpc::v2::datatype_descriptor::DatatypeKind;
+    use crate::proto::myso::rpc::v2::open_signature_body::Type;
+    use myso_sdk_types::Address;
+
+    fn signature(type_: Type) -> OpenSignatureBody {
+        let mut body = OpenSignatureBody::default();
+        body.set_type(type_);
+        body
+    }
+
+    fn datatype_signature(
+        type_name: &str,
+        parameters: Vec<OpenSignatureBody>,
+    ) -> OpenSignatureBody {
+        let mut body = signature(Type::Datatype);
+        body.type_name = Some(type_name.to_owned());
+        body.type_parameter_instantiation = parameters;
+        body
+    }
+
+    fn parameter(index: u32) -> OpenSignatureBody {
+        let mut body = signature(Type::Parameter);
+        body.type_parameter = Some(index);
+        body
+    }
+
+    fn field(name: &str, position: u32, type_: OpenSignatureBody) -> FieldDescriptor {
+        FieldDescriptor {
+            name: Some(name.to_owned()),
+            position: Some(position),
+            r#type: Some(type_),
+        }
+    }
+
+    fn datatype(
+        type_name: &str,
+        type_parameters: usize,
+        kind: DatatypeKind,
+        fields: Vec<FieldDescriptor>,
+        variants: Vec<VariantDescriptor>,
+    ) -> DatatypeDescriptor {
+        DatatypeDescriptor {
+            type_name: Some(type_name.to_owned()),
+            type_parameters: vec![TypeParameter::default(); type_parameters],
+            kind: Some(kind.into()),
+            fields,
+            variants,
+            ..Default::default()
+        }
+    }
+
+    fn resolver() -> LayoutResolver {
+        let mut resolver = LayoutResolver::new();
+        let mut vector = signature(Type::Vector);
+        vector.type_parameter_instantiation = vec![parameter(0)];
+
+        // `Wrapper<T> { items: vector<T>, inner: Option<T>, status: Status }`
+        resolver
+            .insert(datatype(
+                "0x5::wrapper::Wrapper",
+                1,
+                DatatypeKind::Struct,
+                vec![
+                    field(
+                        "status",
+                        2,
+                        datatype_signature("0x5::wrapper::Status", vec![]),
+                    ),
+                    field("items", 0, vector.clone()),
+                    field(
+                        "inner",
+                        1,
+                        datatype_signature("0x1::option::Option", vec![parameter(0)]),
+                    ),
+                ],
+                vec![],
+            ))
+            .unwrap();
+        resolver
+            .insert(datatype(
+                "0x1::option::Option",
+                1,
+                DatatypeKind::Struct,
+                vec![field("vec", 0, vector)],
+                vec![],
+            ))
+            .unwrap();
+
+        let active = VariantDescriptor {
+            name: Some("Active".to_owned()),
+            position: Some(0),
+            fields: vec![],
+        };
+        let paused = VariantDescriptor {
+            name: Some("Paused".to_owned()),
+            position: Some(1),
+            fields: vec![field("code", 0, signature(Type::U64))],
+        };
+        resolver
+            .insert(datatype(
+                "0x5::wrapper::Status",
+                0,
+                DatatypeKind::Enum,
+                vec![],
+                vec![paused, active],
+            ))
+            .unwrap();
+
+        resolver
+    }
+
+    #[test]
+    fn layout() {
+        let resolver = resolver();
+        let type_: TypeTag = "0x5::wrapper::Wrapper<u8>".parse().unwrap();
+
+        let mut missing = BTreeSet::new();
+        resolver.missing(&type_, 0, &mut missing);
+        assert!(missing.is_empty());
+
+        let layout = resolver.layout(&type_).unwrap();
+        assert_eq!(layout.type_tag(), type_);
+
+        let MoveTypeLayout::Struct(wrapper) = &layout else {
+            panic!("expected a struct");
+        };
+        let fields = wrapper
+            .fields
+            .iter()
+            .map(|field| field.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(fields, ["items", "inner", "status"]);
+        assert_eq!(
+            wrapper.fields[0].layout,
+            MoveTypeLayout::Vector(Box::new(MoveTypeLayout::U8))
+        );
+
+        let MoveTypeLayout::Enum(status) = &wrapper.fields[2].layout else {
+            panic!("expected an enum");
+        };
+        assert_eq!(status.variants[0].name.as_str(), "Active");
+        assert_eq!(status.variants[1].name.as_str(), "Paused");
+
+        // `Wrapper<u8> { items: [1, 2], inner: Some(3), status: Paused { code: 9 } }`
+        let mut bytes = vec![2, 1, 2, 1, 3, 1];
+        bytes.extend(9u64.to_le_bytes());
+        let value = MoveValue::from_bcs(&bytes, &layout).unwrap();
+        assert_eq!(
+            value.to_json(),
+            serde_json::json!({
+                "items": "AQI=",
+                "inner": 3,
+                "status": { "@variant": "Paused", "code": "9" },
+            })
+        );
+    }
+
+    #[test]
+    fn missing_datatypes() {
+        let resolver = resolver();
+        let type_: TypeTag = "vector<0x5::wrapper::Wrapper<0x6::other::Other<u8>>>"
+            .parse()
+            .unwrap();
+
+        let mut missing = BTreeSet::new();
+        resolver.missing(&type_, 0, &mut missing);
+        assert_eq!(
+            missing.into_iter().collect::<Vec<_>>(),
+            [StructTag::new(
+                Address::from_static("0x6"),
+                "other".parse().unwrap(),
+                "Other".parse().unwrap(),
+                vec![],
+            )]
+        );
+        resolver.layout(&type_).unwrap_err();
+
+        // The number of type arguments must match the datatype's type parameters
+        resolver
+            .layout(&"0x5::wrapper::Wrapper".parse().unwrap())
+            .unwrap_err();
+        resolver.layout(&TypeTag::Signer).unwrap_err();
+    }
+}
@@ -12,7 +12,19 @@ pub use interceptors::HeadersInterceptor;
 mod staking_rewards;
 pub use staking_rewards::DelegatedStake;
 
+mod jwks;
+pub use jwks::ActiveJwks;
+pub use jwks::JwkCache;
+
+mod layouts;
+pub use layouts::LayoutResolver;
+
+mod clever_errors;
+pub use clever_errors::CleverError;
+
 mod coin_selection;
+mod dynamic_fields;
+pub use dynamic_fields::LinkedTableNode;
 mod lists;
 
 mod transaction_execution;
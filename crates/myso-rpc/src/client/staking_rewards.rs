@@ -1,5 +1,6 @@
 use prost_types::FieldMask;
 use myso_sdk_types::Address;
+use myso_sdk_types::framework::StakedMySo;
 
 use crate::field::FieldMaskUtil;
 use crate::proto::myso::rpc::v2::Argument;
@@ -32,17 +33,6 @@ pub struct DelegatedStake {
     pub rewards: u64,
 }
 
-#[derive(serde::Deserialize, Debug)]
-struct StakedMySo {
-    id: Address,
-    /// ID of the staking pool we are staking with.
-    pool_id: Address,
-    /// The epoch at which the stake becomes active.
-    stake_activation_epoch: u64,
-    /// The staked MYSO tokens.
-    principal: u64,
-}
-
 impl Client {
     pub async fn get_delegated_stake(&mut self, staked_myso_id: &Address) -> Result<DelegatedStake> {
         let maybe_staked_myso = self
@@ -113,8 +103,14 @@ impl Client {
             })
             .collect::<Result<Vec<StakedMySo>>>()?;
 
-        let ids = staked_mysos.iter().map(|s| s.id).collect::<Vec<_>>();
-        let pool_ids = staked_mysos.iter().map(|s| s.pool_id).collect::<Vec<_>>();
+        let ids = staked_mysos
+            .iter()
+            .map(|s| *s.id.address())
+            .collect::<Vec<_>>();
+        let pool_ids = staked_mysos
+            .iter()
+            .map(|s| *s.pool_id.address())
+            .collect::<Vec<_>>();
 
         let rewards = self.calculate_rewards(&ids).await?;
         let validator_addresses = self.get_validator_address_by_pool_id(&pool_ids).await?;
@@ -125,11 +121,11 @@ impl Client {
             .zip(validator_addresses)
             .map(
                 |((staked_myso, (_id, rewards)), (_pool_id, validator_address))| DelegatedStake {
-                    staked_myso_id: staked_myso.id,
+                    staked_myso_id: *staked_myso.id.address(),
                     validator_address,
-                    staking_pool: staked_myso.pool_id,
+                    staking_pool: *staked_myso.pool_id.address(),
                     activation_epoch: staked_myso.stake_activation_epoch,
-                    principal: staked_myso.principal,
+                    principal: staked_myso.principal.value,
                     rewards,
                 },
             )
@@ -1,7 +1,17 @@
 use bytes::Bytes;
 use myso_sdk_types::Address;
+use myso_sdk_types::Identifier;
+use myso_sdk_types::MoveEnumLayout;
+use myso_sdk_types::MoveFieldLayout;
+use myso_sdk_types::MoveStructLayout;
+use myso_sdk_types::MoveTypeLayout;
+use myso_sdk_types::MoveVariantLayout;
+use myso_sdk_types::StructTag;
+use myso_sdk_types::TypeTag;
+use tap::Pipe;
 
 use super::*;
+use crate::proto::TryFromProtoError;
 
 impl GetPackageRequest {
     pub fn new(package_id: &Address) -> Self {
@@ -82,3 +92,231 @@ impl PackageVersion {
         }
     }
 }
+
+//
+// Layouts
+//
+
+impl OpenSignatureBody {
+    /// The type this signature refers to, with type parameters substituted with the provided
+    /// type arguments.
+    #[allow(clippy::result_large_err)]
+    pub fn type_tag(&self, type_arguments: &[TypeTag]) -> Result<TypeTag, TryFromProtoError> {
+        use open_signature_body::Type;
+
+        let instantiation = |body: &Self| {
+            body.type_parameter_instantiation
+                .iter()
+                .enumerate()
+                .map(|(i, parameter)| {
+                    parameter
+                        .type_tag(type_arguments)
+                        .map_err(|e| e.nested_at("type_parameter_instantiation", i))
+                })
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        let type_tag = match self.r#type() {
+            Type::Address => TypeTag::Address,
+            Type::Bool => TypeTag::Bool,
+            Type::U8 => TypeTag::U8,
+            Type::U16 => TypeTag::U16,
+            Type::U32 => TypeTag::U32,
+            Type::U64 => TypeTag::U64,
+            Type::U128 => TypeTag::U128,
+            Type::U256 => TypeTag::U256,
+            Type::Vector => {
+                let [element] = instantiation(self)?.try_into().map_err(|_| {
+                    TryFromProtoError::invalid(
+                        "type_parameter_instantiation",
+                        "vector must have a single type parameter",
+                    )
+                })?;
+                TypeTag::Vector(Box::new(element))
+            }
+            Type::Datatype => {
+                let name = self
+                    .type_name
+                    .as_deref()
+                    .ok_or_else(|| TryFromProtoError::missing("type_name"))?
+                    .parse::<StructTag>()
+                    .map_err(|e| TryFromProtoError::invalid("type_name", e))?;
+                StructTag::new(
+                    *name.address(),
+                    name.module().clone(),
+                    name.name().clone(),
+                    instantiation(self)?,
+                )
+                .into()
+            }
+            Type::Parameter => {
+                let index = self
+                    .type_parameter
+                    .ok_or_else(|| TryFromProtoError::missing("type_parameter"))?;
+                type_arguments.get(index as usize).cloned().ok_or_else(|| {
+                    TryFromProtoError::invalid(
+                        "type_parameter",
+                        format!("no type argument for type parameter {index}"),
+                    )
+                })?
+            }
+            Type::Unknown => {
+                return Err(TryFromProtoError::invalid("type", "unknown signature type"));
+            }
+        };
+
+        Ok(type_tag)
+    }
+}
+
+impl DatatypeDescriptor {
+    /// Build the layout of an instantiation of this datatype.
+    ///
+    /// `resolve` is called to determine the layout of the type of each field, after substituting
+    /// the datatype's type parameters with `type_arguments`.
+    #[allow(clippy::result_large_err)]
+    pub fn layout<F>(
+        &self,
+        type_arguments: &[TypeTag],
+        resolve: &mut F,
+    ) -> Result<MoveTypeLayout, TryFromProtoError>
+    where
+        F: FnMut(&TypeTag) -> Result<MoveTypeLayout, TryFromProtoError>,
+    {
+        use datatype_descriptor::DatatypeKind;
+
+        if type_arguments.len() != self.type_parameters.len() {
+            return Err(TryFromProtoError::invalid(
+                "type_parameters",
+                format!(
+                    "expected {} type arguments, found {}",
+                    self.type_parameters.len(),
+                    type_arguments.len()
+                ),
+            ));
+        }
+
+        let name = self
+            .type_name
+            .as_deref()
+            .ok_or_else(|| TryFromProtoError::missing("type_name"))?
+            .parse::<StructTag>()
+            .map_err(|e| TryFromProtoError::invalid("type_name", e))?;
+        let type_ = StructTag::new(
+            *name.address(),
+            name.module().clone(),
+            name.name().clone(),
+            type_arguments.to_vec(),
+        );
+
+        let layout = match self.kind() {
+            DatatypeKind::Struct => MoveTypeLayout::Struct(Box::new(MoveStructLayout {
+                type_,
+                fields: fields_layout(&self.fields, type_arguments, resolve)?,
+            })),
+            DatatypeKind::Enum => {
+                let mut variants = self.variants.iter().collect::<Vec<_>>();
+                variants.sort_by_key(|variant| variant.position);
+                let variants = variants
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, variant)| {
+                        if variant.position != Some(i as u32) {
+                            return Err(TryFromProtoError::invalid(
+                                "position",
+                                "variant positions must be contiguous",
+                            ));
+                        }
+                        variant
+                            .layout(type_arguments, resolve)
+                            .map_err(|e| e.nested_at("variants", i))
+                    })
+                    .collect::<Result<_, _>>()?;
+                MoveTypeLayout::Enum(Box::new(MoveEnumLayout { type_, variants }))
+            }
+            DatatypeKind::Unknown => {
+                return Err(TryFromProtoError::invalid("kind", "unknown datatype kind"));
+            }
+        };
+
+        Ok(layout)
+    }
+}
+
+impl VariantDescriptor {
+    /// Build the layout of this variant, see [`DatatypeDescriptor::layout`].
+    #[allow(clippy::result_large_err)]
+    pub fn layout<F>(
+        &self,
+        type_arguments: &[TypeTag],
+        resolve: &mut F,
+    ) -> Result<MoveVariantLayout, TryFromProtoError>
+    where
+        F: FnMut(&TypeTag) -> Result<MoveTypeLayout, TryFromProtoError>,
+    {
+        let name = self
+            .name
+            .as_deref()
+            .ok_or_else(|| TryFromProtoError::missing("name"))?
+            .pipe(Identifier::new)
+            .map_err(|e| TryFromProtoError::invalid("name", e))?;
+
+        Ok(MoveVariantLayout {
+            name,
+            fields: fields_layout(&self.fields, type_arguments, resolve)?,
+        })
+    }
+}
+
+impl FieldDescriptor {
+    /// Build the layout of this field, see [`DatatypeDescriptor::layout`].
+    #[allow(clippy::result_large_err)]
+    pub fn layout<F>(
+        &self,
+        type_arguments: &[TypeTag],
+        resolve: &mut F,
+    ) -> Result<MoveFieldLayout, TryFromProtoError>
+    where
+        F: FnMut(&TypeTag) -> Result<MoveTypeLayout, TryFromProtoError>,
+    {
+        let name = self
+            .name
+            .as_deref()
+            .ok_or_else(|| TryFromProtoError::missing("name"))?
+            .pipe(Identifier::new)
+            .map_err(|e| TryFromProtoError::invalid("name", e))?;
+        let type_tag = self
+            .r#type
+            .as_ref()
+            .ok_or_else(|| TryFromProtoError::missing("type"))?
+            .type_tag(type_arguments)
+            .map_err(|e| e.nested("type"))?;
+
+        Ok(MoveFieldLayout {
+            name,
+            layout: resolve(&type_tag)?,
+        })
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn fields_layout<F>(
+    fields: &[FieldDescriptor],
+    type_arguments: &[TypeTag],
+    resolve: &mut F,
+) -> Result<Vec<MoveFieldLayout>, TryFromProtoError>
+where
+    F: FnMut(&TypeTag) -> Result<MoveTypeLayout, TryFromProtoError>,
+{
+    let mut fields = fields.iter().collect::<Vec<_>>();
+    fields.sort_by_key(|field| field.position);
+    fields
+        .into_iter()
+        .enumerate()
+        .map(|(i, field)| {
+            field
+                .layout(type_arguments, resolve)
+                .map_err(|e| e.nested_at("fields", i))
+        })
+        .collect()
+}
@@ -66,6 +66,7 @@ pub struct Address(
 impl Address {
     pub const LENGTH: usize = 32;
     pub const ZERO: Self = Self([0u8; Self::LENGTH]);
+    pub const ONE: Self = Self::from_u8(1);
     pub const TWO: Self = Self::from_u8(2);
     pub const THREE: Self = Self::from_u8(3);
 
@@ -126,6 +127,19 @@ impl Address {
         self.to_string()
     }
 
+    /// Encode this address as hex with leading zeros stripped, e.g. `0x2` for [`Address::TWO`].
+    ///
+    /// The [`Display`](std::fmt::Display) implementation produces the long, zero-padded form.
+    pub fn to_short_string(&self) -> String {
+        let hex = self.to_string();
+        let digits = hex[2..].trim_start_matches('0');
+        if digits.is_empty() {
+            "0x0".to_owned()
+        } else {
+            format!("0x{digits}")
+        }
+    }
+
     pub fn from_bytes<T: AsRef<[u8]>>(bytes: T) -> Result<Self, AddressParseError> {
         <[u8; Self::LENGTH]>::try_from(bytes.as_ref())
             .map_err(|_| AddressParseError { hex_error: None })
@@ -1,6 +1,6 @@
 use super::SimpleSignature;
 use crate::checkpoint::EpochId;
-use crate::u256::U256;
+use crate::u256::Uint;
 
 /// A zklogin authenticator
 ///
@@ -559,7 +559,7 @@ impl Bn254FieldElement {
     }
 
     pub const fn from_str_radix_10(s: &str) -> Result<Self, Bn254FieldElementParseError> {
-        let u256 = match U256::from_str_radix(s, 10) {
+        let u256 = match Uint::from_str_radix(s, 10) {
             Ok(u256) => u256,
             Err(e) => return Err(Bn254FieldElementParseError(e)),
         };
@@ -585,7 +585,7 @@ impl Bn254FieldElement {
 
 impl std::fmt::Display for Bn254FieldElement {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let u256 = U256::from_be(U256::from_digits(self.0));
+        let u256 = Uint::from_be(Uint::from_digits(self.0));
         let radix10 = u256.to_str_radix(10);
         f.write_str(&radix10)
     }
@@ -606,7 +606,7 @@ impl std::str::FromStr for Bn254FieldElement {
     type Err = Bn254FieldElementParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let u256 = U256::from_str_radix(s, 10).map_err(Bn254FieldElementParseError)?;
+        let u256 = Uint::from_str_radix(s, 10).map_err(Bn254FieldElementParseError)?;
         let be = u256.to_be();
         Ok(Self(*be.digits()))
     }
@@ -1,6 +1,12 @@
+mod summary;
 mod v1;
 mod v2;
 
+pub use summary::EffectsSummary;
+pub use summary::EffectsSummaryError;
+pub use summary::ObjectChange;
+pub use summary::ObjectChangeKind;
+
 pub use v1::ModifiedAtVersion;
 pub use v1::ObjectReferenceWithOwner;
 pub use v1::TransactionEffectsV1;
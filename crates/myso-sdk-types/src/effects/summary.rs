@@ -0,0 +1,561 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+use super::AccumulatorOperation;
+use super::IdOperation;
+use super::ObjectIn;
+use super::ObjectOut;
+use super::TransactionEffects;
+use super::TransactionEffectsV1;
+use super::TransactionEffectsV2;
+use crate::Address;
+use crate::BalanceChange;
+use crate::Digest;
+use crate::Object;
+use crate::ObjectType;
+use crate::Owner;
+use crate::TypeTag;
+use crate::Version;
+
+/// A summary of the objects changed by a transaction and the resulting changes to the balances of
+/// each address, derived from the transaction's effects and the objects it read and wrote.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EffectsSummary {
+    /// Every object created, mutated or removed by the transaction, in the order they appear in
+    /// the effects.
+    pub object_changes: Vec<ObjectChange>,
+
+    /// The net change of each address's balance of each coin type, ordered by address and coin
+    /// type.
+    ///
+    /// Both `Coin` objects owned by an address and the address's balance accumulators
+    /// contribute to the changes.
+    pub balance_changes: Vec<BalanceChange>,
+}
+
+/// A change made to an object by a transaction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ObjectChange {
+    pub object_id: Address,
+
+    pub kind: ObjectChangeKind,
+
+    /// The type of the object, unknown only for objects which were unwrapped and then deleted,
+    /// as neither the version read nor a version written by the transaction exists.
+    pub object_type: Option<ObjectType>,
+
+    /// The version of the object read by the transaction, if it existed beforehand.
+    pub input_version: Option<Version>,
+
+    /// The owner of the object before the transaction, if it existed beforehand.
+    pub input_owner: Option<Owner>,
+
+    /// The version of the object written by the transaction, if any.
+    pub output_version: Option<Version>,
+
+    /// The digest of the object written by the transaction, if any.
+    pub output_digest: Option<Digest>,
+
+    /// The owner of the object after the transaction, if it was written.
+    pub output_owner: Option<Owner>,
+}
+
+/// What happened to an object during a transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ObjectChangeKind {
+    /// A new object was created.
+    Created,
+    /// A new package was published.
+    Published,
+    /// An existing object was modified without changing its owner.
+    Mutated,
+    /// An existing object was modified and its owner changed.
+    Transferred,
+    /// An existing object was deleted.
+    Deleted,
+    /// An existing object was wrapped in another object and is no longer accessible by its ID.
+    Wrapped,
+    /// A previously wrapped object was taken out of its wrapper.
+    Unwrapped,
+    /// A previously wrapped object was taken out of its wrapper and deleted.
+    UnwrappedThenDeleted,
+}
+
+impl EffectsSummary {
+    /// Summarize the changes made by a transaction.
+    ///
+    /// `input_objects` must contain the version read by the transaction of every object which
+    /// existed beforehand and `output_objects` the version written of every object which exists
+    /// afterwards. Other objects are ignored.
+    pub fn new(
+        effects: &TransactionEffects,
+        input_objects: &[Object],
+        output_objects: &[Object],
+    ) -> Result<Self, EffectsSummaryError> {
+        let inputs = index(input_objects);
+        let outputs = index(output_objects);
+
+        let (changes, accumulator_writes) = match effects {
+            TransactionEffects::V1(effects) => (v1_changes(effects)?, vec![]),
+            TransactionEffects::V2(effects) => v2_changes(effects)?,
+        };
+
+        let mut balances = BTreeMap::<(Address, TypeTag), i128>::new();
+        for (address, coin_type, amount) in accumulator_writes {
+            *balances.entry((address, coin_type)).or_default() += amount;
+        }
+
+        let mut object_changes = Vec::with_capacity(changes.len());
+        for change in changes {
+            let input = change
+                .input_version
+                .map(|version| lookup(&inputs, change.object_id, version))
+                .transpose()?;
+            let output = change
+                .output
+                .as_ref()
+                .map(|(version, _, _)| lookup(&outputs, change.object_id, *version))
+                .transpose()?;
+
+            if let Some((address, coin_type, balance)) = input.and_then(coin_balance) {
+                *balances.entry((address, coin_type)).or_default() -= balance;
+            }
+            if let Some((address, coin_type, balance)) = output.and_then(coin_balance) {
+                *balances.entry((address, coin_type)).or_default() += balance;
+            }
+
+            let input_owner = input.map(|object| *object.owner());
+            let object_type = output.or(input).map(Object::object_type);
+            let kind = match change.kind {
+                ObjectChangeKind::Created if object_type == Some(ObjectType::Package) => {
+                    ObjectChangeKind::Published
+                }
+                ObjectChangeKind::Mutated
+                    if input_owner.as_ref()
+                        != change.output.as_ref().map(|(_, _, owner)| owner) =>
+                {
+                    ObjectChangeKind::Transferred
+                }
+                kind => kind,
+            };
+
+            let (output_version, output_digest, output_owner) = match change.output {
+                Some((version, digest, owner)) => (Some(version), Some(digest), Some(owner)),
+                None => (None, None, None),
+            };
+            object_changes.push(ObjectChange {
+                object_id: change.object_id,
+                kind,
+                object_type,
+                input_version: change.input_version,
+                input_owner,
+                output_version,
+                output_digest,
+                output_owner,
+            });
+        }
+
+        let balance_changes = balances
+            .into_iter()
+            .filter(|(_, amount)| *amount != 0)
+            .map(|((address, coin_type), amount)| BalanceChange {
+                address,
+                coin_type,
+                amount,
+            })
+            .collect();
+
+        Ok(Self {
+            object_changes,
+            balance_changes,
+        })
+    }
+
+    /// The changes of a particular kind.
+    pub fn changes(&self, kind: ObjectChangeKind) -> impl Iterator<Item = &ObjectChange> {
+        self.object_changes
+            .iter()
+            .filter(move |change| change.kind == kind)
+    }
+}
+
+/// A change as described by the effects, before being enriched with the objects themselves.
+struct RawChange {
+    object_id: Address,
+    kind: ObjectChangeKind,
+    input_version: Option<Version>,
+    output: Option<(Version, Digest, Owner)>,
+}
+
+fn v1_changes(effects: &TransactionEffectsV1) -> Result<Vec<RawChange>, EffectsSummaryError> {
+    let input_versions = effects
+        .modified_at_versions
+        .iter()
+        .map(|modified| (modified.object_id, modified.version))
+        .collect::<HashMap<_, _>>();
+    let input_version = |object_id: &Address| {
+        input_versions
+            .get(object_id)
+            .copied()
+            .ok_or(EffectsSummaryError::InvalidObjectChange {
+                object_id: *object_id,
+            })
+    };
+
+    let mut changes = Vec::new();
+    for (kind, objects) in [
+        (ObjectChangeKind::Created, &effects.created),
+        (ObjectChangeKind::Mutated, &effects.mutated),
+        (ObjectChangeKind::Unwrapped, &effects.unwrapped),
+    ] {
+        for object in objects {
+            let (object_id, version, digest) = object.reference.clone().into_parts();
+            changes.push(RawChange {
+                object_id,
+                kind,
+                input_version: match kind {
+                    ObjectChangeKind::Mutated => Some(input_version(&object_id)?),
+                    _ => None,
+                },
+                output: Some((version, digest, object.owner)),
+            });
+        }
+    }
+
+    for (kind, references) in [
+        (ObjectChangeKind::Deleted, &effects.deleted),
+        (ObjectChangeKind::Wrapped, &effects.wrapped),
+        (
+            ObjectChangeKind::UnwrappedThenDeleted,
+            &effects.unwrapped_then_deleted,
+        ),
+    ] {
+        for reference in references {
+            let object_id = *reference.object_id();
+            changes.push(RawChange {
+                object_id,
+                kind,
+                input_version: match kind {
+                    ObjectChangeKind::UnwrappedThenDeleted => None,
+                    _ => Some(input_version(&object_id)?),
+                },
+                output: None,
+            });
+        }
+    }
+
+    Ok(changes)
+}
+
+type AccumulatorChange = (Address, TypeTag, i128);
+
+fn v2_changes(
+    effects: &TransactionEffectsV2,
+) -> Result<(Vec<RawChange>, Vec<AccumulatorChange>), EffectsSummaryError> {
+    let mut changes = Vec::new();
+    let mut accumulator_writes = Vec::new();
+
+    for changed in &effects.changed_objects {
+        let object_id = changed.object_id;
+        let input_version = match &changed.input_state {
+            ObjectIn::NotExist => None,
+            ObjectIn::Exist { version, .. } => Some(*version),
+        };
+        let (output, written) = match &changed.output_state {
+            ObjectOut::NotExist => (None, false),
+            ObjectOut::ObjectWrite { digest, owner } => {
+                (Some((effects.lamport_version, *digest, *owner)), true)
+            }
+            ObjectOut::PackageWrite { version, digest } => {
+                (Some((*version, *digest, Owner::Immutable)), true)
+            }
+            ObjectOut::AccumulatorWrite(write) => {
+                if let Some(coin_type) = balance_type(write.accumulator_type()) {
+                    let amount = i128::from(write.value());
+                    let amount = match write.operation() {
+                        AccumulatorOperation::Merge => amount,
+                        AccumulatorOperation::Split => -amount,
+                    };
+                    accumulator_writes.push((*write.address(), coin_type.clone(), amount));
+                }
+                continue;
+            }
+        };
+
+        let kind = match (input_version.is_some(), written, changed.id_operation) {
+            (false, true, IdOperation::Created) => ObjectChangeKind::Created,
+            (false, true, IdOperation::None) => ObjectChangeKind::Unwrapped,
+            (true, true, IdOperation::None) => ObjectChangeKind::Mutated,
+            (true, false, IdOperation::Deleted) => ObjectChangeKind::Deleted,
+            (true, false, IdOperation::None) => ObjectChangeKind::Wrapped,
+            (false, false, IdOperation::Deleted) => ObjectChangeKind::UnwrappedThenDeleted,
+            _ => return Err(EffectsSummaryError::InvalidObjectChange { object_id }),
+        };
+
+        changes.push(RawChange {
+            object_id,
+            kind,
+            input_version,
+            output,
+        });
+    }
+
+    Ok((changes, accumulator_writes))
+}
+
+fn index(objects: &[Object]) -> HashMap<(Address, Version), &Object> {
+    objects
+        .iter()
+        .map(|object| ((object.object_id(), object.version()), object))
+        .collect()
+}
+
+fn lookup<'a>(
+    objects: &HashMap<(Address, Version), &'a Object>,
+    object_id: Address,
+    version: Version,
+) -> Result<&'a Object, EffectsSummaryError> {
+    objects
+        .get(&(object_id, version))
+        .copied()
+        .ok_or(EffectsSummaryError::MissingObject { object_id, version })
+}
+
+/// The owning address, coin type and balance of a `Coin` owned by an address or object.
+fn coin_balance(object: &Object) -> Option<(Address, TypeTag, i128)> {
+    let address = match object.owner() {
+        Owner::Address(address) | Owner::Object(address) => *address,
+        Owner::ConsensusAddress { owner, .. } => *owner,
+        Owner::Shared(_) | Owner::Immutable => return None,
+    };
+
+    let struct_ = object.as_struct()?;
+    let coin_type = struct_.object_type().is_coin()?;
+    // The contents of a `Coin` are its `UID` followed by its `u64` balance
+    let balance = struct_
+        .contents()
+        .get(Address::LENGTH..Address::LENGTH + 8)?
+        .try_into()
+        .ok()
+        .map(u64::from_le_bytes)?;
+
+    Some((address, coin_type.clone(), i128::from(balance)))
+}
+
+/// The coin type of a `0x2::balance::Balance<T>` accumulator.
+fn balance_type(type_: &TypeTag) -> Option<&TypeTag> {
+    let TypeTag::Struct(struct_tag) = type_ else {
+        return None;
+    };
+
+    if struct_tag.address() == &Address::TWO
+        && struct_tag.module() == "balance"
+        && struct_tag.name() == "Balance"
+        && let [coin_type] = struct_tag.type_params()
+    {
+        Some(coin_type)
+    } else {
+        None
+    }
+}
+
+/// An error summarizing the effects of a transaction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum EffectsSummaryError {
+    /// A version of an object read or written by the transaction wasn't provided.
+    MissingObject {
+        object_id: Address,
+        version: Version,
+    },
+    /// The effects describe a change to an object which isn't possible.
+    InvalidObjectChange { object_id: Address },
+}
+
+impl std::fmt::Display for EffectsSummaryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EffectsSummaryError::MissingObject { object_id, version } => {
+                write!(f, "missing version {version} of object {object_id}")
+            }
+            EffectsSummaryError::InvalidObjectChange { object_id } => {
+                write!(f, "invalid change to object {object_id}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for EffectsSummaryError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AccumulatorWrite;
+    use crate::ChangedObject;
+    use crate::ExecutionStatus;
+    use crate::GasCostSummary;
+    use crate::MoveStruct;
+    use crate::ObjectData;
+    use crate::StructTag;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    const LAMPORT_VERSION: Version = 10;
+
+    fn address(byte: u8) -> Address {
+        Address::new([byte; 32])
+    }
+
+    fn coin(id: Address, version: Version, owner: Address, balance: u64) -> Object {
+        let mut contents = id.into_inner().to_vec();
+        contents.extend(balance.to_le_bytes());
+        Object::new(
+            ObjectData::Struct(
+                MoveStruct::new(StructTag::gas_coin(), true, version, contents).unwrap(),
+            ),
+            Owner::Address(owner),
+            Digest::ZERO,
+            0,
+        )
+    }
+
+    fn changed(
+        input: Option<&Object>,
+        output: Option<&Object>,
+        id_operation: IdOperation,
+    ) -> ChangedObject {
+        let object = input.or(output).unwrap();
+        ChangedObject {
+            object_id: object.object_id(),
+            input_state: match input {
+                Some(input) => ObjectIn::Exist {
+                    version: input.version(),
+                    digest: input.digest(),
+                    owner: *input.owner(),
+                },
+                None => ObjectIn::NotExist,
+            },
+            output_state: match output {
+                Some(output) => ObjectOut::ObjectWrite {
+                    digest: output.digest(),
+                    owner: *output.owner(),
+                },
+                None => ObjectOut::NotExist,
+            },
+            id_operation,
+        }
+    }
+
+    #[test]
+    fn summary() {
+        let (sender, recipient) = (address(0xa), address(0xb));
+        let (gas, sent, split, deleted, unwrapped) =
+            (address(1), address(2), address(3), address(4), address(5));
+
+        let inputs = vec![
+            coin(gas, 3, sender, 1000),
+            coin(sent, 4, sender, 50),
+            coin(deleted, 5, sender, 20),
+        ];
+        let outputs = vec![
+            coin(gas, LAMPORT_VERSION, sender, 900),
+            coin(sent, LAMPORT_VERSION, recipient, 50),
+            coin(split, LAMPORT_VERSION, recipient, 30),
+            coin(unwrapped, LAMPORT_VERSION, sender, 7),
+        ];
+
+        let myso = TypeTag::from(StructTag::myso());
+        let balance = StructTag::new(
+            Address::TWO,
+            "balance".parse().unwrap(),
+            "Balance".parse().unwrap(),
+            vec![myso.clone()],
+        );
+        let effects = TransactionEffects::V2(Box::new(TransactionEffectsV2 {
+            status: ExecutionStatus::Success,
+            epoch: 1,
+            gas_used: GasCostSummary::new(0, 0, 0, 0),
+            transaction_digest: Digest::ZERO,
+            gas_object_index: Some(0),
+            events_digest: None,
+            dependencies: vec![],
+            lamport_version: LAMPORT_VERSION,
+            changed_objects: vec![
+                changed(Some(&inputs[0]), Some(&outputs[0]), IdOperation::None),
+                changed(Some(&inputs[1]), Some(&outputs[1]), IdOperation::None),
+                changed(None, Some(&outputs[2]), IdOperation::Created),
+                changed(Some(&inputs[2]), None, IdOperation::Deleted),
+                changed(None, Some(&outputs[3]), IdOperation::None),
+                ChangedObject {
+                    object_id: address(6),
+                    input_state: ObjectIn::NotExist,
+                    output_state: ObjectOut::AccumulatorWrite(AccumulatorWrite::new(
+                        address(0xc),
+                        balance.into(),
+                        AccumulatorOperation::Merge,
+                        5,
+                    )),
+                    id_operation: IdOperation::None,
+                },
+            ],
+            unchanged_consensus_objects: vec![],
+            auxiliary_data_digest: None,
+        }));
+
+        let summary = EffectsSummary::new(&effects, &inputs, &outputs).unwrap();
+        let kinds = summary
+            .object_changes
+            .iter()
+            .map(|change| (change.object_id, change.kind))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            [
+                (gas, ObjectChangeKind::Mutated),
+                (sent, ObjectChangeKind::Transferred),
+                (split, ObjectChangeKind::Created),
+                (deleted, ObjectChangeKind::Deleted),
+                (unwrapped, ObjectChangeKind::Unwrapped),
+            ]
+        );
+
+        let transferred = summary
+            .changes(ObjectChangeKind::Transferred)
+            .next()
+            .unwrap();
+        assert_eq!(transferred.input_version, Some(4));
+        assert_eq!(transferred.input_owner, Some(Owner::Address(sender)));
+        assert_eq!(transferred.output_version, Some(LAMPORT_VERSION));
+        assert_eq!(transferred.output_owner, Some(Owner::Address(recipient)));
+        assert_eq!(transferred.output_digest, Some(outputs[1].digest()));
+        assert_eq!(
+            transferred.object_type,
+            Some(ObjectType::Struct(StructTag::gas_coin()))
+        );
+
+        let balances = summary
+            .balance_changes
+            .iter()
+            .map(|change| (change.address, change.amount))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            balances,
+            [(sender, -163), (recipient, 80), (address(0xc), 5)]
+        );
+        assert!(
+            summary
+                .balance_changes
+                .iter()
+                .all(|change| change.coin_type == myso)
+        );
+
+        assert_eq!(
+            EffectsSummary::new(&effects, &inputs[..2], &outputs),
+            Err(EffectsSummaryError::MissingObject {
+                object_id: deleted,
+                version: 5,
+            })
+        );
+    }
+}
@@ -80,3 +80,250 @@ pub struct BalanceChange {
     /// A negative amount means spending coin value and positive means receiving coin value.
     pub amount: i128,
 }
+
+/// A Rust type which can be decoded from the BCS contents of a Move event.
+///
+/// Implementing this trait for a type mirroring the layout of a Move struct allows events of
+/// that struct's type to be picked out of [`TransactionEvents`] and decoded with
+/// [`Event::decode`] and [`TransactionEvents::decode`].
+///
+/// ```
+/// use myso_sdk_types::Address;
+/// use myso_sdk_types::MoveEvent;
+///
+/// #[derive(serde_derive::Deserialize)]
+/// struct PoolCreated {
+///     pool_id: Address,
+///     fee_bps: u64,
+/// }
+///
+/// impl MoveEvent for PoolCreated {
+///     const ADDRESS: Address = Address::from_static("0xabc");
+///     const MODULE: &'static str = "pool";
+///     const NAME: &'static str = "PoolCreated";
+/// }
+/// ```
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+pub trait MoveEvent: serde::de::DeserializeOwned {
+    /// The address of the package which defines the event's type.
+    ///
+    /// Events are emitted with the type as defined by the first version of a package, so this
+    /// is the original ID of the package even if it has since been upgraded.
+    const ADDRESS: Address;
+
+    /// The name of the module which defines the event's type.
+    const MODULE: &'static str;
+
+    /// The name of the event's type.
+    const NAME: &'static str;
+
+    /// Whether events of type `type_` are of this event type.
+    ///
+    /// By default, the type parameters of generic events are ignored. Implementations can
+    /// override this to only match specific instantiations.
+    fn matches(type_: &StructTag) -> bool {
+        type_.address() == &Self::ADDRESS
+            && type_.module().as_str() == Self::MODULE
+            && type_.name().as_str() == Self::NAME
+    }
+}
+
+/// A set of [`MoveEvent`]s, typically an enum with a variant per event type, into which events
+/// can be decoded with [`TransactionEvents::decode_set`].
+///
+/// Implementations are usually generated with the [`move_events!`](crate::move_events) macro.
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+pub trait MoveEventSet: Sized {
+    /// Decode an event if it is of one of the types in the set.
+    ///
+    /// Returns `None` if the event isn't of any type in the set.
+    fn decode(event: &Event) -> Option<Result<Self, bcs::Error>>;
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+impl Event {
+    /// Whether this event is of the type of `E`.
+    pub fn is<E: MoveEvent>(&self) -> bool {
+        E::matches(&self.type_)
+    }
+
+    /// Decode the contents of this event as `E`.
+    ///
+    /// Returns `None` if this event isn't of the type of `E`.
+    pub fn decode<E: MoveEvent>(&self) -> Option<Result<E, bcs::Error>> {
+        self.is::<E>().then(|| bcs::from_bytes(&self.contents))
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+impl TransactionEvents {
+    /// Decode every event of the type of `E`, skipping events of other types.
+    pub fn decode<E: MoveEvent>(&self) -> impl Iterator<Item = Result<E, bcs::Error>> {
+        self.0.iter().filter_map(Event::decode)
+    }
+
+    /// Decode every event of a type in the set `S`, skipping events of other types.
+    pub fn decode_set<S: MoveEventSet>(&self) -> impl Iterator<Item = Result<S, bcs::Error>> {
+        self.0.iter().filter_map(S::decode)
+    }
+}
+
+/// Define an enum with a variant for each of a number of [`MoveEvent`] types, and implement
+/// [`MoveEventSet`] for it so that events can be dispatched to the matching variant.
+///
+/// ```
+/// # use myso_sdk_types::Address;
+/// # use myso_sdk_types::MoveEvent;
+/// # #[derive(Debug, serde_derive::Deserialize)]
+/// # struct Deposit { amount: u64 }
+/// # impl MoveEvent for Deposit {
+/// #     const ADDRESS: Address = Address::from_static("0xabc");
+/// #     const MODULE: &'static str = "vault";
+/// #     const NAME: &'static str = "Deposit";
+/// # }
+/// # #[derive(Debug, serde_derive::Deserialize)]
+/// # struct Withdraw { amount: u64 }
+/// # impl MoveEvent for Withdraw {
+/// #     const ADDRESS: Address = Address::from_static("0xabc");
+/// #     const MODULE: &'static str = "vault";
+/// #     const NAME: &'static str = "Withdraw";
+/// # }
+/// myso_sdk_types::move_events! {
+///     #[derive(Debug)]
+///     pub enum VaultEvent {
+///         Deposit(Deposit),
+///         Withdraw(Withdraw),
+///     }
+/// }
+///
+/// fn net_deposits(events: &myso_sdk_types::TransactionEvents) -> i128 {
+///     events
+///         .decode_set::<VaultEvent>()
+///         .map(|event| match event.unwrap() {
+///             VaultEvent::Deposit(deposit) => deposit.amount as i128,
+///             VaultEvent::Withdraw(withdraw) => -(withdraw.amount as i128),
+///         })
+///         .sum()
+/// }
+/// ```
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+#[macro_export]
+macro_rules! move_events {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $(
+                $(#[$variant_meta:meta])*
+                $variant:ident($event:ty)
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $(
+                $(#[$variant_meta])*
+                $variant($event),
+            )*
+        }
+
+        impl $crate::MoveEventSet for $name {
+            fn decode(
+                event: &$crate::Event,
+            ) -> ::core::option::Option<::core::result::Result<Self, $crate::bcs::Error>> {
+                $(
+                    if let ::core::option::Option::Some(result) = event.decode::<$event>() {
+                        return ::core::option::Option::Some(result.map(Self::$variant));
+                    }
+                )*
+                ::core::option::Option::None
+            }
+        }
+    };
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    #[derive(serde_derive::Deserialize, Debug, PartialEq)]
+    struct Transferred {
+        amount: u64,
+    }
+
+    impl MoveEvent for Transferred {
+        const ADDRESS: Address = Address::from_static("0xabc");
+        const MODULE: &'static str = "vault";
+        const NAME: &'static str = "Transferred";
+    }
+
+    #[derive(serde_derive::Deserialize, Debug, PartialEq)]
+    struct Closed {
+        owner: Address,
+    }
+
+    impl MoveEvent for Closed {
+        const ADDRESS: Address = Address::from_static("0xabc");
+        const MODULE: &'static str = "vault";
+        const NAME: &'static str = "Closed";
+    }
+
+    crate::move_events! {
+        #[derive(Debug, PartialEq)]
+        enum VaultEvent {
+            Transferred(Transferred),
+            Closed(Closed),
+        }
+    }
+
+    fn event(type_: &str, contents: Vec<u8>) -> Event {
+        Event {
+            package_id: Address::from_static("0xabc"),
+            module: Identifier::from_static("vault"),
+            sender: Address::TWO,
+            type_: type_.parse().unwrap(),
+            contents,
+        }
+    }
+
+    #[test]
+    fn decode() {
+        let events = TransactionEvents(vec![
+            event("0xabc::vault::Transferred", 5u64.to_le_bytes().to_vec()),
+            event("0xabc::other::Transferred", 6u64.to_le_bytes().to_vec()),
+            event("0xabc::vault::Closed", Address::THREE.into_inner().to_vec()),
+            event("0xabc::vault::Transferred", vec![1]),
+        ]);
+
+        assert!(events.0[0].is::<Transferred>());
+        assert!(!events.0[1].is::<Transferred>());
+        assert_eq!(events.0[1].decode::<Transferred>(), None);
+
+        let transferred = events.decode::<Transferred>().collect::<Vec<_>>();
+        assert_eq!(transferred.len(), 2);
+        assert_eq!(transferred[0], Ok(Transferred { amount: 5 }));
+        assert!(transferred[1].is_err());
+
+        let decoded = events
+            .decode_set::<VaultEvent>()
+            .take(2)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            decoded,
+            [
+                VaultEvent::Transferred(Transferred { amount: 5 }),
+                VaultEvent::Closed(Closed {
+                    owner: Address::THREE
+                }),
+            ]
+        );
+    }
+}
@@ -1,4 +1,9 @@
 //! Rust definitions of move/myso framework types.
+//!
+//! The structs in this module mirror the layout of the Move structs they are named after, so
+//! their BCS serialization is the same as that of the Move value. `0x1::option::Option<T>` and
+//! `0x1::string::String` have the same BCS serialization as Rust's `Option<T>` and `String` and
+//! so have no definitions of their own.
 
 use super::Address;
 use super::Object;
@@ -57,3 +62,395 @@ impl<'a> Coin<'a> {
         }
     }
 }
+
+/// Implement `try_from_object` for a framework type which is the contents of objects of the
+/// struct type `$address::$module::$name`, with any type parameters.
+macro_rules! impl_try_from_object {
+    ($type:ident $(<$($param:ident),+>)?, $address:expr, $module:literal, $name:literal) => {
+        #[cfg(feature = "serde")]
+        #[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+        impl $(<$($param: serde::de::DeserializeOwned),+>)? $type $(<$($param),+>)? {
+            #[doc = concat!(
+                "Decode the contents of an object of type `", $module, "::", $name, "`."
+            )]
+            pub fn try_from_object(object: &Object) -> Option<Self> {
+                let move_struct = object.as_struct()?;
+                let type_ = &move_struct.type_;
+                if type_.address() != &$address
+                    || type_.module().as_str() != $module
+                    || type_.name().as_str() != $name
+                {
+                    return None;
+                }
+                bcs::from_bytes(&move_struct.contents).ok()
+            }
+        }
+    };
+}
+
+/// `0x2::object::ID`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct Id {
+    pub bytes: Address,
+}
+
+impl Id {
+    pub fn address(&self) -> &Address {
+        &self.bytes
+    }
+}
+
+impl From<Address> for Id {
+    fn from(bytes: Address) -> Self {
+        Self { bytes }
+    }
+}
+
+/// `0x2::object::UID`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct Uid {
+    pub id: Id,
+}
+
+impl Uid {
+    pub fn address(&self) -> &Address {
+        self.id.address()
+    }
+}
+
+impl From<Address> for Uid {
+    fn from(address: Address) -> Self {
+        Self { id: address.into() }
+    }
+}
+
+/// `0x2::balance::Balance<T>`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct Balance {
+    pub value: u64,
+}
+
+/// `0x2::balance::Supply<T>`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct Supply {
+    pub value: u64,
+}
+
+/// `0x3::staking_pool::StakedMySo`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct StakedMySo {
+    pub id: Uid,
+    /// ID of the staking pool we are staking with.
+    pub pool_id: Id,
+    /// The epoch at which the stake becomes active.
+    pub stake_activation_epoch: u64,
+    /// The staked MYSO tokens.
+    pub principal: Balance,
+}
+
+impl_try_from_object!(StakedMySo, Address::THREE, "staking_pool", "StakedMySo");
+
+/// `0x2::url::Url`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct Url {
+    pub url: String,
+}
+
+/// `0x2::coin::CoinMetadata<T>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct CoinMetadata {
+    pub id: Uid,
+    /// Number of decimal places the coin uses.
+    pub decimals: u8,
+    /// Name for the token
+    pub name: String,
+    /// Symbol for the token
+    pub symbol: String,
+    /// Description of the token
+    pub description: String,
+    /// URL for the token logo
+    pub icon_url: Option<Url>,
+}
+
+impl_try_from_object!(CoinMetadata, Address::TWO, "coin", "CoinMetadata");
+
+/// `0x2::coin::TreasuryCap<T>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct TreasuryCap {
+    pub id: Uid,
+    pub total_supply: Supply,
+}
+
+impl_try_from_object!(TreasuryCap, Address::TWO, "coin", "TreasuryCap");
+
+/// `0x2::package::UpgradeCap`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct UpgradeCap {
+    pub id: Uid,
+    /// ID of the package that can be upgraded.
+    pub package: Id,
+    /// The number of upgrades that have been applied to the package.
+    pub version: u64,
+    /// The kind of upgrades which are allowed.
+    pub policy: u8,
+}
+
+impl_try_from_object!(UpgradeCap, Address::TWO, "package", "UpgradeCap");
+
+/// `0x2::display::Display<T>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct Display {
+    pub id: Uid,
+    /// The templates for the fields of the display.
+    pub fields: VecMap<String, String>,
+    /// The version of the display, incremented each time it is updated.
+    pub version: u16,
+}
+
+impl_try_from_object!(Display, Address::TWO, "display", "Display");
+
+/// `0x2::clock::Clock`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct Clock {
+    pub id: Uid,
+    /// The clock's timestamp, set by the last consensus commit.
+    pub timestamp_ms: u64,
+}
+
+impl_try_from_object!(Clock, Address::TWO, "clock", "Clock");
+
+/// `0x2::dynamic_field::Field<K, V>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct Field<K, V> {
+    pub id: Uid,
+    pub name: K,
+    pub value: V,
+}
+
+impl_try_from_object!(Field<K, V>, Address::TWO, "dynamic_field", "Field");
+
+/// `0x2::table::Table<K, V>`
+///
+/// The entries of a table are stored as dynamic fields of its `id`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct Table {
+    pub id: Uid,
+    pub size: u64,
+}
+
+/// `0x2::bag::Bag`
+///
+/// The entries of a bag are stored as dynamic fields of its `id`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct Bag {
+    pub id: Uid,
+    pub size: u64,
+}
+
+/// `0x2::vec_map::VecMap<K, V>`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct VecMap<K, V> {
+    pub contents: Vec<VecMapEntry<K, V>>,
+}
+
+impl<K: PartialEq, V> VecMap<K, V> {
+    /// Get the value of the entry with the given key.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.contents
+            .iter()
+            .find(|entry| &entry.key == key)
+            .map(|entry| &entry.value)
+    }
+}
+
+impl<K, V> Default for VecMap<K, V> {
+    fn default() -> Self {
+        Self {
+            contents: Vec::new(),
+        }
+    }
+}
+
+/// `0x2::vec_map::Entry<K, V>`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct VecMapEntry<K, V> {
+    pub key: K,
+    pub value: V,
+}
+
+/// `0x2::vec_set::VecSet<K>`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct VecSet<K> {
+    pub contents: Vec<K>,
+}
+
+impl<K: PartialEq> VecSet<K> {
+    pub fn contains(&self, key: &K) -> bool {
+        self.contents.contains(key)
+    }
+}
+
+impl<K> Default for VecSet<K> {
+    fn default() -> Self {
+        Self {
+            contents: Vec::new(),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+    use crate::Digest;
+    use crate::MoveStruct;
+    use crate::ObjectData;
+    use crate::Owner;
+    use crate::StructTag;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    fn object(type_: StructTag, contents: Vec<u8>) -> Object {
+        Object::new(
+            ObjectData::Struct(MoveStruct::new(type_, true, 1, contents).unwrap()),
+            Owner::Immutable,
+            Digest::ZERO,
+            0,
+        )
+    }
+
+    #[test]
+    fn try_from_object() {
+        let clock = Clock {
+            id: Address::from_static("0x6").into(),
+            timestamp_ms: 1234,
+        };
+        let object = object(StructTag::clock(), bcs::to_bytes(&clock).unwrap());
+        assert_eq!(Clock::try_from_object(&object), Some(clock));
+        assert_eq!(UpgradeCap::try_from_object(&object), None);
+
+        let metadata = CoinMetadata {
+            id: Address::TWO.into(),
+            decimals: 9,
+            name: "MySo".to_owned(),
+            symbol: "MYSO".to_owned(),
+            description: String::new(),
+            icon_url: Some(Url {
+                url: "https://mysocial.network".to_owned(),
+            }),
+        };
+        let object = self::object(
+            StructTag::coin_metadata(StructTag::myso().into()),
+            bcs::to_bytes(&metadata).unwrap(),
+        );
+        assert_eq!(CoinMetadata::try_from_object(&object), Some(metadata));
+
+        let field = Field {
+            id: Address::THREE.into(),
+            name: 7u64,
+            value: VecMap {
+                contents: vec![VecMapEntry {
+                    key: "name".to_owned(),
+                    value: "{name}".to_owned(),
+                }],
+            },
+        };
+        let object = self::object(
+            StructTag::dynamic_field(
+                crate::TypeTag::U64,
+                StructTag::vec_map(StructTag::string().into(), StructTag::string().into()).into(),
+            ),
+            bcs::to_bytes(&field).unwrap(),
+        );
+        let decoded = Field::<u64, VecMap<String, String>>::try_from_object(&object).unwrap();
+        assert_eq!(decoded.value.get(&"name".to_owned()).unwrap(), "{name}");
+        assert_eq!(decoded, field);
+    }
+
+    #[test]
+    fn struct_tags() {
+        for (tag, expected) in [
+            (StructTag::string(), "0x1::string::String"),
+            (StructTag::uid(), "0x2::object::UID"),
+            (StructTag::upgrade_cap(), "0x2::package::UpgradeCap"),
+            (
+                StructTag::treasury_cap(StructTag::myso().into()),
+                "0x2::coin::TreasuryCap<0x2::myso::MYSO>",
+            ),
+            (
+                StructTag::table(crate::TypeTag::Address, crate::TypeTag::U64),
+                "0x2::table::Table<address, u64>",
+            ),
+        ] {
+            assert_eq!(tag, expected.parse().unwrap());
+        }
+    }
+}
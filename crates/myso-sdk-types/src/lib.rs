@@ -111,10 +111,15 @@ mod gas;
 #[cfg(feature = "hash")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "hash")))]
 pub mod hash;
+mod move_value;
 mod object;
+pub mod render;
 mod transaction;
 mod type_tag;
 mod u256;
+#[cfg(all(feature = "hash", feature = "serde"))]
+#[cfg_attr(doc_cfg, doc(cfg(all(feature = "hash", feature = "serde"))))]
+pub mod verify;
 
 pub use address::Address;
 pub use address::AddressParseError;
@@ -175,8 +180,12 @@ pub use digest::SigningDigest;
 pub use effects::AccumulatorOperation;
 pub use effects::AccumulatorWrite;
 pub use effects::ChangedObject;
+pub use effects::EffectsSummary;
+pub use effects::EffectsSummaryError;
 pub use effects::IdOperation;
 pub use effects::ModifiedAtVersion;
+pub use effects::ObjectChange;
+pub use effects::ObjectChangeKind;
 pub use effects::ObjectIn;
 pub use effects::ObjectOut;
 pub use effects::ObjectReferenceWithOwner;
@@ -187,6 +196,12 @@ pub use effects::UnchangedConsensusKind;
 pub use effects::UnchangedConsensusObject;
 pub use events::BalanceChange;
 pub use events::Event;
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+pub use events::MoveEvent;
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+pub use events::MoveEventSet;
 pub use events::TransactionEvents;
 pub use execution_status::CommandArgumentError;
 pub use execution_status::ExecutionError;
@@ -195,6 +210,15 @@ pub use execution_status::MoveLocation;
 pub use execution_status::PackageUpgradeError;
 pub use execution_status::TypeArgumentError;
 pub use gas::GasCostSummary;
+pub use move_value::MoveEnumLayout;
+pub use move_value::MoveFieldLayout;
+pub use move_value::MoveStructLayout;
+pub use move_value::MoveStructValue;
+pub use move_value::MoveTypeLayout;
+pub use move_value::MoveValue;
+pub use move_value::MoveValueError;
+pub use move_value::MoveVariantLayout;
+pub use move_value::MoveVariantValue;
 pub use object::GenesisObject;
 pub use object::MovePackage;
 pub use object::MoveStruct;
@@ -247,9 +271,17 @@ pub use transaction::VersionAssignment;
 pub use transaction::VersionAssignmentV2;
 pub use transaction::WithdrawFrom;
 pub use type_tag::Identifier;
+pub use type_tag::StructPattern;
 pub use type_tag::StructTag;
+pub use type_tag::StructTagDisplay;
+pub use type_tag::TypeDisplayMode;
 pub use type_tag::TypeParseError;
+pub use type_tag::TypePattern;
 pub use type_tag::TypeTag;
+pub use type_tag::TypeTagDisplay;
+pub use u256::TryFromU256Error;
+pub use u256::U256;
+pub use u256::U256ParseError;
 
 #[cfg(feature = "serde")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
@@ -0,0 +1,617 @@
+//! Layouts of Move types and annotated Move values decoded from their BCS representation.
+
+use super::Address;
+use super::Identifier;
+use super::StructTag;
+use super::TypeTag;
+use super::U256;
+
+/// The layout of a Move type, describing how a value of the type is laid out in BCS.
+///
+/// Unlike a [`TypeTag`], the layout of a struct or enum includes the names and layouts of its
+/// fields, with all type parameters substituted, so that the BCS representation of a value can be
+/// decoded without any further information.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum MoveTypeLayout {
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    U256,
+    Address,
+    Vector(Box<MoveTypeLayout>),
+    Struct(Box<MoveStructLayout>),
+    Enum(Box<MoveEnumLayout>),
+}
+
+/// The layout of an instantiation of a Move struct.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MoveStructLayout {
+    /// The type of the struct, including its type arguments.
+    pub type_: StructTag,
+    /// The fields of the struct, in declaration order.
+    pub fields: Vec<MoveFieldLayout>,
+}
+
+/// The layout of an instantiation of a Move enum.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MoveEnumLayout {
+    /// The type of the enum, including its type arguments.
+    pub type_: StructTag,
+    /// The variants of the enum, in declaration order.
+    pub variants: Vec<MoveVariantLayout>,
+}
+
+/// The layout of a single variant of a Move enum.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MoveVariantLayout {
+    pub name: Identifier,
+    /// The fields of the variant, in declaration order.
+    pub fields: Vec<MoveFieldLayout>,
+}
+
+/// The layout of a single field of a Move struct or enum variant.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MoveFieldLayout {
+    pub name: Identifier,
+    pub layout: MoveTypeLayout,
+}
+
+impl MoveTypeLayout {
+    /// The type this is the layout of.
+    pub fn type_tag(&self) -> TypeTag {
+        match self {
+            MoveTypeLayout::Bool => TypeTag::Bool,
+            MoveTypeLayout::U8 => TypeTag::U8,
+            MoveTypeLayout::U16 => TypeTag::U16,
+            MoveTypeLayout::U32 => TypeTag::U32,
+            MoveTypeLayout::U64 => TypeTag::U64,
+            MoveTypeLayout::U128 => TypeTag::U128,
+            MoveTypeLayout::U256 => TypeTag::U256,
+            MoveTypeLayout::Address => TypeTag::Address,
+            MoveTypeLayout::Vector(element) => TypeTag::Vector(Box::new(element.type_tag())),
+            MoveTypeLayout::Struct(layout) => layout.type_.clone().into(),
+            MoveTypeLayout::Enum(layout) => layout.type_.clone().into(),
+        }
+    }
+
+    /// The layout of a primitive type, or a vector of primitive types, which doesn't require
+    /// knowing the definition of any datatype.
+    ///
+    /// Returns `None` for structs, enums and `signer`.
+    pub fn from_primitive(type_: &TypeTag) -> Option<Self> {
+        let layout = match type_ {
+            TypeTag::Bool => MoveTypeLayout::Bool,
+            TypeTag::U8 => MoveTypeLayout::U8,
+            TypeTag::U16 => MoveTypeLayout::U16,
+            TypeTag::U32 => MoveTypeLayout::U32,
+            TypeTag::U64 => MoveTypeLayout::U64,
+            TypeTag::U128 => MoveTypeLayout::U128,
+            TypeTag::U256 => MoveTypeLayout::U256,
+            TypeTag::Address => MoveTypeLayout::Address,
+            TypeTag::Vector(element) => {
+                MoveTypeLayout::Vector(Box::new(Self::from_primitive(element)?))
+            }
+            TypeTag::Signer | TypeTag::Struct(_) => return None,
+        };
+        Some(layout)
+    }
+}
+
+/// A Move value annotated with the names of its fields and types of its structs and enums.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum MoveValue {
+    Bool(bool),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    U256(U256),
+    Address(Address),
+    /// A vector, along with the layout of its elements so that the type of an empty vector is
+    /// known.
+    Vector(MoveTypeLayout, Vec<MoveValue>),
+    Struct(MoveStructValue),
+    Variant(MoveVariantValue),
+}
+
+/// The value of a Move struct.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MoveStructValue {
+    pub type_: StructTag,
+    /// The fields of the struct, in declaration order.
+    pub fields: Vec<(Identifier, MoveValue)>,
+}
+
+/// The value of a Move enum.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MoveVariantValue {
+    pub type_: StructTag,
+    /// The name of the variant.
+    pub name: Identifier,
+    /// The position of the variant in the enum's declaration.
+    pub tag: u16,
+    /// The fields of the variant, in declaration order.
+    pub fields: Vec<(Identifier, MoveValue)>,
+}
+
+impl MoveStructValue {
+    /// Lookup the value of a field by name.
+    pub fn field(&self, name: &str) -> Option<&MoveValue> {
+        lookup_field(&self.fields, name)
+    }
+}
+
+impl MoveVariantValue {
+    /// Lookup the value of a field by name.
+    pub fn field(&self, name: &str) -> Option<&MoveValue> {
+        lookup_field(&self.fields, name)
+    }
+}
+
+fn lookup_field<'a>(fields: &'a [(Identifier, MoveValue)], name: &str) -> Option<&'a MoveValue> {
+    fields
+        .iter()
+        .find(|(field, _)| field.as_str() == name)
+        .map(|(_, value)| value)
+}
+
+/// An error decoding a [`MoveValue`] from BCS.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MoveValueError {
+    message: String,
+}
+
+impl MoveValueError {
+    fn new<T: Into<String>>(message: T) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for MoveValueError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unable to decode move value: {}", self.message)
+    }
+}
+
+impl std::error::Error for MoveValueError {}
+
+impl MoveValue {
+    /// Decode the BCS representation of a value with the provided layout.
+    ///
+    /// Returns an error if the bytes are not a valid value of the layout or if there are bytes
+    /// remaining after decoding the value.
+    pub fn from_bcs(bytes: &[u8], layout: &MoveTypeLayout) -> Result<Self, MoveValueError> {
+        let mut reader = Reader { bytes };
+        let value = reader.value(layout)?;
+        if !reader.bytes.is_empty() {
+            return Err(MoveValueError::new(format!(
+                "{} trailing bytes",
+                reader.bytes.len()
+            )));
+        }
+        Ok(value)
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], MoveValueError> {
+        let Some((bytes, rest)) = self.bytes.split_first_chunk::<N>() else {
+            return Err(MoveValueError::new("unexpected end of input"));
+        };
+        self.bytes = rest;
+        Ok(*bytes)
+    }
+
+    fn uleb128(&mut self) -> Result<u32, MoveValueError> {
+        let mut value: u64 = 0;
+        for shift in (0..32).step_by(7) {
+            let [byte] = self.take::<1>()?;
+            let digit = (byte & 0x7f) as u64;
+            value |= digit << shift;
+
+            if byte & 0x80 == 0 {
+                if shift > 0 && digit == 0 {
+                    return Err(MoveValueError::new("non-canonical uleb128"));
+                }
+                return u32::try_from(value)
+                    .map_err(|_| MoveValueError::new("uleb128 overflows u32"));
+            }
+        }
+        Err(MoveValueError::new("uleb128 overflows u32"))
+    }
+
+    fn value(&mut self, layout: &MoveTypeLayout) -> Result<MoveValue, MoveValueError> {
+        let value = match layout {
+            MoveTypeLayout::Bool => match self.take::<1>()? {
+                [0] => MoveValue::Bool(false),
+                [1] => MoveValue::Bool(true),
+                [byte] => return Err(MoveValueError::new(format!("invalid bool {byte}"))),
+            },
+            MoveTypeLayout::U8 => MoveValue::U8(u8::from_le_bytes(self.take()?)),
+            MoveTypeLayout::U16 => MoveValue::U16(u16::from_le_bytes(self.take()?)),
+            MoveTypeLayout::U32 => MoveValue::U32(u32::from_le_bytes(self.take()?)),
+            MoveTypeLayout::U64 => MoveValue::U64(u64::from_le_bytes(self.take()?)),
+            MoveTypeLayout::U128 => MoveValue::U128(u128::from_le_bytes(self.take()?)),
+            MoveTypeLayout::U256 => MoveValue::U256(U256::from_le_bytes(self.take()?)),
+            MoveTypeLayout::Address => MoveValue::Address(Address::new(self.take()?)),
+            MoveTypeLayout::Vector(element) => {
+                let len = self.uleb128()? as usize;
+                // Every element takes up at least one byte
+                if len > self.bytes.len() {
+                    return Err(MoveValueError::new("unexpected end of input"));
+                }
+                let elements = (0..len)
+                    .map(|_| self.value(element))
+                    .collect::<Result<_, _>>()?;
+                MoveValue::Vector(element.as_ref().clone(), elements)
+            }
+            MoveTypeLayout::Struct(layout) => MoveValue::Struct(MoveStructValue {
+                type_: layout.type_.clone(),
+                fields: self.fields(&layout.fields)?,
+            }),
+            MoveTypeLayout::Enum(layout) => {
+                let tag = self.uleb128()?;
+                let variant = layout.variants.get(tag as usize).ok_or_else(|| {
+                    MoveValueError::new(format!("invalid variant {tag} of {}", layout.type_))
+                })?;
+                MoveValue::Variant(MoveVariantValue {
+                    type_: layout.type_.clone(),
+                    name: variant.name.clone(),
+                    tag: tag as u16,
+                    fields: self.fields(&variant.fields)?,
+                })
+            }
+        };
+        Ok(value)
+    }
+
+    fn fields(
+        &mut self,
+        fields: &[MoveFieldLayout],
+    ) -> Result<Vec<(Identifier, MoveValue)>, MoveValueError> {
+        fields
+            .iter()
+            .map(|field| Ok((field.name.clone(), self.value(&field.layout)?)))
+            .collect()
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+mod json {
+    use super::*;
+    use serde_json::Value;
+
+    impl MoveValue {
+        /// Render this value as JSON, where:
+        ///
+        /// - Addresses, IDs, and UIDs are represented in canonical form, as JSON strings.
+        /// - Bools are represented by JSON boolean literals.
+        /// - `u8`, `u16`, and `u32` are represented as JSON numbers.
+        /// - `u64`, `u128`, and `u256` are represented as JSON strings.
+        /// - Balances, Strings, and Urls are represented as JSON strings.
+        /// - Vectors of bytes are represented as Base64 blobs, and other vectors are represented
+        ///   by JSON arrays.
+        /// - Structs are represented by JSON objects.
+        /// - Enums are represented by JSON objects, with a field named `@variant` containing the
+        ///   variant name.
+        /// - Empty optional values are represented by `null`.
+        pub fn to_json(&self) -> Value {
+            match self {
+                MoveValue::Bool(value) => Value::Bool(*value),
+                MoveValue::U8(value) => Value::from(*value),
+                MoveValue::U16(value) => Value::from(*value),
+                MoveValue::U32(value) => Value::from(*value),
+                MoveValue::U64(value) => Value::String(value.to_string()),
+                MoveValue::U128(value) => Value::String(value.to_string()),
+                MoveValue::U256(value) => Value::String(value.to_string()),
+                MoveValue::Address(address) => Value::String(address.to_string()),
+                MoveValue::Vector(MoveTypeLayout::U8, elements) => {
+                    use base64ct::Encoding;
+                    let bytes = bytes(elements).unwrap_or_default();
+                    Value::String(base64ct::Base64::encode_string(&bytes))
+                }
+                MoveValue::Vector(_, elements) => {
+                    Value::Array(elements.iter().map(MoveValue::to_json).collect())
+                }
+                MoveValue::Struct(value) => {
+                    special_struct(value).unwrap_or_else(|| fields_to_json(&value.fields, None))
+                }
+                MoveValue::Variant(value) => fields_to_json(&value.fields, Some(&value.name)),
+            }
+        }
+    }
+
+    fn fields_to_json(fields: &[(Identifier, MoveValue)], variant: Option<&Identifier>) -> Value {
+        let mut object = serde_json::Map::new();
+        if let Some(variant) = variant {
+            object.insert("@variant".to_owned(), Value::String(variant.to_string()));
+        }
+        for (name, value) in fields {
+            object.insert(name.to_string(), value.to_json());
+        }
+        Value::Object(object)
+    }
+
+    /// The bytes of a `vector<u8>`, or `None` if the vector's elements aren't `u8`s.
+    fn bytes(elements: &[MoveValue]) -> Option<Vec<u8>> {
+        elements
+            .iter()
+            .map(|element| match element {
+                MoveValue::U8(byte) => Some(*byte),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The JSON representation of framework structs which aren't represented as JSON objects.
+    fn special_struct(value: &MoveStructValue) -> Option<Value> {
+        let type_ = &value.type_;
+        let single_field = match value.fields.as_slice() {
+            [(_, field)] => field,
+            _ => return None,
+        };
+
+        match (
+            type_.address(),
+            type_.module().as_str(),
+            type_.name().as_str(),
+        ) {
+            // `0x2::object::UID { id: ID }` and `0x2::object::ID { bytes: address }`
+            (&Address::TWO, "object", "UID" | "ID")
+            // `0x2::balance::Balance<T> { value: u64 }`
+            | (&Address::TWO, "balance", "Balance")
+            // `0x2::url::Url { url: 0x1::ascii::String }`
+            | (&Address::TWO, "url", "Url") => Some(single_field.to_json()),
+            // `0x1::string::String { bytes: vector<u8> }`
+            (address, "string" | "ascii", "String") if address == &STD => {
+                let MoveValue::Vector(_, elements) = single_field else {
+                    return None;
+                };
+                String::from_utf8(bytes(elements)?).ok().map(Value::String)
+            }
+            // `0x1::option::Option<T> { vec: vector<T> }`
+            (address, "option", "Option") if address == &STD => match single_field {
+                MoveValue::Vector(_, elements) => match elements.as_slice() {
+                    [] => Some(Value::Null),
+                    [element] => Some(element.to_json()),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    const STD: Address = Address::from_static("0x1");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn struct_layout(type_: &str, fields: Vec<(&str, MoveTypeLayout)>) -> MoveTypeLayout {
+        MoveTypeLayout::Struct(Box::new(MoveStructLayout {
+            type_: type_.parse().unwrap(),
+            fields: fields
+                .into_iter()
+                .map(|(name, layout)| MoveFieldLayout {
+                    name: Identifier::new(name).unwrap(),
+                    layout,
+                })
+                .collect(),
+        }))
+    }
+
+    fn uid() -> MoveTypeLayout {
+        struct_layout(
+            "0x2::object::UID",
+            vec![(
+                "id",
+                struct_layout("0x2::object::ID", vec![("bytes", MoveTypeLayout::Address)]),
+            )],
+        )
+    }
+
+    fn string() -> MoveTypeLayout {
+        struct_layout(
+            "0x1::string::String",
+            vec![(
+                "bytes",
+                MoveTypeLayout::Vector(Box::new(MoveTypeLayout::U8)),
+            )],
+        )
+    }
+
+    fn status() -> MoveTypeLayout {
+        MoveTypeLayout::Enum(Box::new(MoveEnumLayout {
+            type_: "0x5::counter::Status".parse().unwrap(),
+            variants: vec![
+                MoveVariantLayout {
+                    name: Identifier::new("Active").unwrap(),
+                    fields: vec![],
+                },
+                MoveVariantLayout {
+                    name: Identifier::new("Paused").unwrap(),
+                    fields: vec![MoveFieldLayout {
+                        name: Identifier::new("reason").unwrap(),
+                        layout: string(),
+                    }],
+                },
+            ],
+        }))
+    }
+
+    #[test]
+    fn primitives() {
+        let cases = [
+            (vec![1], MoveTypeLayout::Bool, MoveValue::Bool(true)),
+            (vec![7], MoveTypeLayout::U8, MoveValue::U8(7)),
+            (vec![1, 2], MoveTypeLayout::U16, MoveValue::U16(0x0201)),
+            (
+                vec![1, 0, 0, 0, 0, 0, 0, 0],
+                MoveTypeLayout::U64,
+                MoveValue::U64(1),
+            ),
+            (
+                vec![2, 5, 6],
+                MoveTypeLayout::Vector(Box::new(MoveTypeLayout::U8)),
+                MoveValue::Vector(MoveTypeLayout::U8, vec![MoveValue::U8(5), MoveValue::U8(6)]),
+            ),
+        ];
+
+        for (bytes, layout, expected) in cases {
+            assert_eq!(MoveValue::from_bcs(&bytes, &layout).unwrap(), expected);
+            assert_eq!(layout.type_tag(), expected_type(&layout));
+        }
+
+        fn expected_type(layout: &MoveTypeLayout) -> TypeTag {
+            MoveTypeLayout::from_primitive(&layout.type_tag())
+                .unwrap()
+                .type_tag()
+        }
+
+        // Invalid bools, trailing bytes, truncated input and non-canonical lengths are rejected
+        MoveValue::from_bcs(&[2], &MoveTypeLayout::Bool).unwrap_err();
+        MoveValue::from_bcs(&[1, 0], &MoveTypeLayout::Bool).unwrap_err();
+        MoveValue::from_bcs(&[1, 0], &MoveTypeLayout::U32).unwrap_err();
+        MoveValue::from_bcs(
+            &[0x80, 0x00],
+            &MoveTypeLayout::Vector(Box::new(MoveTypeLayout::U8)),
+        )
+        .unwrap_err();
+        MoveValue::from_bcs(
+            &[0xff, 0xff, 0xff, 0xff, 0x0f],
+            &MoveTypeLayout::Vector(Box::new(MoveTypeLayout::U8)),
+        )
+        .unwrap_err();
+    }
+
+    #[test]
+    fn structs_and_enums() {
+        let layout = struct_layout(
+            "0x5::counter::Counter",
+            vec![
+                ("id", uid()),
+                ("value", MoveTypeLayout::U64),
+                ("status", status()),
+            ],
+        );
+
+        let mut bytes = vec![0x12; 32];
+        bytes.extend(42u64.to_le_bytes());
+        bytes.extend([1, 2, b'o', b'k']);
+        let value = MoveValue::from_bcs(&bytes, &layout).unwrap();
+
+        let MoveValue::Struct(counter) = &value else {
+            panic!("expected a struct");
+        };
+        assert_eq!(counter.field("value"), Some(&MoveValue::U64(42)));
+        let Some(MoveValue::Variant(status)) = counter.field("status") else {
+            panic!("expected a variant");
+        };
+        assert_eq!(status.name.as_str(), "Paused");
+        assert_eq!(status.tag, 1);
+        assert!(status.field("reason").is_some());
+
+        // Variants which don't exist are rejected
+        let mut invalid = bytes.clone();
+        invalid[40] = 2;
+        MoveValue::from_bcs(&invalid, &layout).unwrap_err();
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json() {
+        use serde_json::json;
+
+        let option = |element| {
+            struct_layout(
+                "0x1::option::Option<u64>",
+                vec![("vec", MoveTypeLayout::Vector(Box::new(element)))],
+            )
+        };
+        let layout = struct_layout(
+            "0x5::counter::Counter",
+            vec![
+                ("id", uid()),
+                (
+                    "balance",
+                    struct_layout(
+                        "0x2::balance::Balance<0x2::myso::MYSO>",
+                        vec![("value", MoveTypeLayout::U64)],
+                    ),
+                ),
+                ("name", string()),
+                ("small", MoveTypeLayout::U32),
+                ("big", MoveTypeLayout::U256),
+                ("data", MoveTypeLayout::Vector(Box::new(MoveTypeLayout::U8))),
+                (
+                    "flags",
+                    MoveTypeLayout::Vector(Box::new(MoveTypeLayout::Bool)),
+                ),
+                (
+                    "no_data",
+                    MoveTypeLayout::Vector(Box::new(MoveTypeLayout::U8)),
+                ),
+                (
+                    "no_amounts",
+                    MoveTypeLayout::Vector(Box::new(MoveTypeLayout::U64)),
+                ),
+                (
+                    "no_owners",
+                    MoveTypeLayout::Vector(Box::new(MoveTypeLayout::Address)),
+                ),
+                ("none", option(MoveTypeLayout::U64)),
+                ("some", option(MoveTypeLayout::U64)),
+                ("status", status()),
+            ],
+        );
+
+        let mut bytes = vec![0x12; 32];
+        bytes.extend(1000u64.to_le_bytes());
+        bytes.extend([2, b'h', b'i']);
+        bytes.extend(7u32.to_le_bytes());
+        let mut big = [0; 32];
+        big[0] = 1;
+        big[1] = 1;
+        bytes.extend(big);
+        bytes.extend([3, 1, 2, 3]);
+        bytes.extend([2, 1, 0]);
+        bytes.extend([0, 0, 0]);
+        bytes.extend([0]);
+        bytes.extend([1]);
+        bytes.extend(5u64.to_le_bytes());
+        bytes.extend([0]);
+
+        let value = MoveValue::from_bcs(&bytes, &layout).unwrap();
+        assert_eq!(
+            value.to_json(),
+            json!({
+                "id": Address::new([0x12; 32]).to_string(),
+                "balance": "1000",
+                "name": "hi",
+                "small": 7,
+                "big": "257",
+                "data": "AQID",
+                "flags": [true, false],
+                "no_data": "",
+                "no_amounts": [],
+                "no_owners": [],
+                "none": null,
+                "some": "5",
+                "status": { "@variant": "Active" },
+            })
+        );
+    }
+}
@@ -0,0 +1,897 @@
+//! Human-readable rendering of transactions, for reviewing what a transaction does before signing
+//! it.
+//!
+//! Arguments are named after the inputs and command results they refer to, calls to well-known
+//! framework functions are described in English and pure inputs are decoded when their type can
+//! be inferred from how they are used. A [`TransactionDiff`] shows what changed between two
+//! versions of a transaction, for example before and after a sponsor added its gas payment.
+
+use std::collections::HashMap;
+
+use crate::Address;
+use crate::Argument;
+use crate::Command;
+use crate::Input;
+use crate::MoveCall;
+use crate::Mutability;
+use crate::ObjectReference;
+use crate::ProgrammableTransaction;
+use crate::Transaction;
+use crate::TransactionExpiration;
+use crate::TransactionKind;
+use crate::TypeTag;
+use crate::WithdrawFrom;
+
+/// A rendering of a [`Transaction`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde_derive::Serialize))]
+pub struct RenderedTransaction {
+    pub sender: String,
+    pub kind: String,
+    pub gas_owner: String,
+    pub gas_objects: Vec<String>,
+    pub gas_price: u64,
+    pub gas_budget: u64,
+    pub expiration: String,
+    /// The rendered inputs and commands, if this is a programmable transaction.
+    pub programmable: Option<RenderedProgrammableTransaction>,
+}
+
+/// A rendering of a [`ProgrammableTransaction`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde_derive::Serialize))]
+pub struct RenderedProgrammableTransaction {
+    pub inputs: Vec<RenderedInput>,
+    pub commands: Vec<RenderedCommand>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde_derive::Serialize))]
+pub struct RenderedInput {
+    /// The name commands refer to the input by, e.g. `input0`.
+    pub name: String,
+    /// The kind of input, e.g. `pure` or `shared object`.
+    pub kind: String,
+    pub value: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde_derive::Serialize))]
+pub struct RenderedCommand {
+    /// The name later commands refer to the result of the command by, e.g. `result0`.
+    pub result: String,
+    /// The command with its arguments resolved to names, e.g. `SplitCoins(gas, [input0])`.
+    pub command: String,
+    /// An English description of what the command does.
+    pub description: String,
+}
+
+impl RenderedTransaction {
+    pub fn new(transaction: &Transaction) -> Self {
+        let (kind, programmable) = match &transaction.kind {
+            TransactionKind::ProgrammableTransaction(ptb) => (
+                "programmable transaction",
+                Some(RenderedProgrammableTransaction::new(ptb)),
+            ),
+            TransactionKind::ProgrammableSystemTransaction(ptb) => (
+                "programmable system transaction",
+                Some(RenderedProgrammableTransaction::new(ptb)),
+            ),
+            TransactionKind::ChangeEpoch(_) => ("change epoch", None),
+            TransactionKind::Genesis(_) => ("genesis", None),
+            TransactionKind::ConsensusCommitPrologue(_)
+            | TransactionKind::ConsensusCommitPrologueV2(_)
+            | TransactionKind::ConsensusCommitPrologueV3(_)
+            | TransactionKind::ConsensusCommitPrologueV4(_) => ("consensus commit prologue", None),
+            TransactionKind::AuthenticatorStateUpdate(_) => ("authenticator state update", None),
+            TransactionKind::EndOfEpoch(_) => ("end of epoch", None),
+            TransactionKind::RandomnessStateUpdate(_) => ("randomness state update", None),
+        };
+
+        let expiration = match &transaction.expiration {
+            TransactionExpiration::None => "none".to_owned(),
+            TransactionExpiration::Epoch(epoch) => format!("after epoch {epoch}"),
+            TransactionExpiration::ValidDuring {
+                min_epoch,
+                max_epoch,
+                min_timestamp,
+                max_timestamp,
+                chain,
+                nonce,
+            } => {
+                let bound = |bound: &Option<u64>| match bound {
+                    Some(bound) => bound.to_string(),
+                    None => "unbounded".to_owned(),
+                };
+                let mut expiration = format!(
+                    "valid from epoch {} to epoch {}",
+                    bound(min_epoch),
+                    bound(max_epoch)
+                );
+                if min_timestamp.is_some() || max_timestamp.is_some() {
+                    expiration.push_str(&format!(
+                        " and from timestamp {} to timestamp {}",
+                        bound(min_timestamp),
+                        bound(max_timestamp)
+                    ));
+                }
+                expiration.push_str(&format!(" on chain {chain} with nonce {nonce}"));
+                expiration
+            }
+        };
+
+        Self {
+            sender: transaction.sender.to_string(),
+            kind: kind.to_owned(),
+            gas_owner: transaction.gas_payment.owner.to_string(),
+            gas_objects: transaction
+                .gas_payment
+                .objects
+                .iter()
+                .map(object_reference)
+                .collect(),
+            gas_price: transaction.gas_payment.price,
+            gas_budget: transaction.gas_payment.budget,
+            expiration,
+            programmable,
+        }
+    }
+
+    /// The structured rendering as JSON.
+    #[cfg(feature = "serde")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).expect("rendered transactions are valid json")
+    }
+
+    /// Every field of the rendering as `(path, value)` pairs, the granularity transactions are
+    /// diffed at.
+    fn fields(&self) -> Vec<(String, String)> {
+        let mut fields = vec![
+            ("sender".to_owned(), self.sender.clone()),
+            ("kind".to_owned(), self.kind.clone()),
+            ("gas.owner".to_owned(), self.gas_owner.clone()),
+            ("gas.price".to_owned(), self.gas_price.to_string()),
+            ("gas.budget".to_owned(), self.gas_budget.to_string()),
+        ];
+        for (i, object) in self.gas_objects.iter().enumerate() {
+            fields.push((format!("gas.objects[{i}]"), object.clone()));
+        }
+        fields.push(("expiration".to_owned(), self.expiration.clone()));
+
+        if let Some(programmable) = &self.programmable {
+            for (i, input) in programmable.inputs.iter().enumerate() {
+                fields.push((
+                    format!("inputs[{i}]"),
+                    format!("{} {}", input.kind, input.value),
+                ));
+            }
+            for (i, command) in programmable.commands.iter().enumerate() {
+                fields.push((format!("commands[{i}]"), command.command.clone()));
+            }
+        }
+
+        fields
+    }
+}
+
+impl std::fmt::Display for RenderedTransaction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} from {}", capitalize(&self.kind), self.sender)?;
+        writeln!(
+            f,
+            "Gas: budget of {} at a price of {}, paid by {}",
+            self.gas_budget, self.gas_price, self.gas_owner
+        )?;
+        for object in &self.gas_objects {
+            writeln!(f, "  {object}")?;
+        }
+        writeln!(f, "Expiration: {}", self.expiration)?;
+
+        if let Some(programmable) = &self.programmable {
+            write!(f, "{programmable}")?;
+        }
+        Ok(())
+    }
+}
+
+impl RenderedProgrammableTransaction {
+    pub fn new(ptb: &ProgrammableTransaction) -> Self {
+        let hints = pure_hints(ptb);
+        let names = Names { ptb, hints: &hints };
+
+        let inputs = ptb
+            .inputs
+            .iter()
+            .enumerate()
+            .map(|(i, input)| {
+                let (kind, value) = names.input(i as u16, input);
+                RenderedInput {
+                    name: format!("input{i}"),
+                    kind: kind.to_owned(),
+                    value,
+                }
+            })
+            .collect();
+
+        let commands = ptb
+            .commands
+            .iter()
+            .enumerate()
+            .map(|(i, command)| RenderedCommand {
+                result: format!("result{i}"),
+                command: names.command(command),
+                description: names.describe(command),
+            })
+            .collect();
+
+        Self { inputs, commands }
+    }
+
+    /// The structured rendering as JSON.
+    #[cfg(feature = "serde")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).expect("rendered transactions are valid json")
+    }
+}
+
+impl std::fmt::Display for RenderedProgrammableTransaction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Inputs:")?;
+        for input in &self.inputs {
+            writeln!(f, "  {}: {} {}", input.name, input.kind, input.value)?;
+        }
+        writeln!(f, "Commands:")?;
+        for command in &self.commands {
+            writeln!(f, "  {} = {}", command.result, command.command)?;
+            writeln!(f, "    {}", command.description)?;
+        }
+        Ok(())
+    }
+}
+
+/// The differences between two transactions.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde_derive::Serialize))]
+pub struct TransactionDiff {
+    pub changes: Vec<FieldChange>,
+}
+
+/// A field which differs between two transactions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde_derive::Serialize))]
+pub struct FieldChange {
+    /// The path of the field, e.g. `gas.owner` or `commands[2]`.
+    pub field: String,
+    /// The value before, or `None` if the field was added.
+    pub before: Option<String>,
+    /// The value after, or `None` if the field was removed.
+    pub after: Option<String>,
+}
+
+impl TransactionDiff {
+    pub fn new(before: &Transaction, after: &Transaction) -> Self {
+        Self::from_rendered(
+            &RenderedTransaction::new(before),
+            &RenderedTransaction::new(after),
+        )
+    }
+
+    pub fn from_rendered(before: &RenderedTransaction, after: &RenderedTransaction) -> Self {
+        let before = before.fields();
+        let mut after_fields = after.fields().into_iter().collect::<HashMap<_, _>>();
+
+        let mut changes = Vec::new();
+        for (field, value) in &before {
+            match after_fields.remove(field) {
+                Some(after) if &after == value => {}
+                after => changes.push(FieldChange {
+                    field: field.clone(),
+                    before: Some(value.clone()),
+                    after,
+                }),
+            }
+        }
+
+        // Fields which were added, in the order they appear in the transaction
+        for (field, _) in after.fields() {
+            if let Some(value) = after_fields.remove(&field) {
+                changes.push(FieldChange {
+                    field,
+                    before: None,
+                    after: Some(value),
+                });
+            }
+        }
+
+        Self { changes }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// The structured diff as JSON.
+    #[cfg(feature = "serde")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).expect("transaction diffs are valid json")
+    }
+}
+
+impl std::fmt::Display for TransactionDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for change in &self.changes {
+            match (&change.before, &change.after) {
+                (Some(before), Some(after)) => {
+                    writeln!(f, "~ {}: {before} -> {after}", change.field)?
+                }
+                (None, Some(after)) => writeln!(f, "+ {}: {after}", change.field)?,
+                (Some(before), None) => writeln!(f, "- {}: {before}", change.field)?,
+                (None, None) => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The type of a pure input, inferred from how it is used.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PureType {
+    U64,
+    Address,
+}
+
+/// Infer the types of pure inputs from the commands they are passed to.
+fn pure_hints(ptb: &ProgrammableTransaction) -> HashMap<u16, PureType> {
+    let mut hints = HashMap::new();
+    let mut hint = |argument: &Argument, type_: PureType| {
+        if let Argument::Input(i) = argument
+            && let Some(Input::Pure(_)) = ptb.inputs.get(*i as usize)
+        {
+            hints.entry(*i).or_insert(type_);
+        }
+    };
+
+    for command in &ptb.commands {
+        match command {
+            Command::TransferObjects(transfer) => hint(&transfer.address, PureType::Address),
+            Command::SplitCoins(split) => split
+                .amounts
+                .iter()
+                .for_each(|amount| hint(amount, PureType::U64)),
+            Command::MoveCall(call) => {
+                if let Some(known) = KnownCall::new(call) {
+                    for (argument, type_) in call.arguments.iter().zip(known.parameters()) {
+                        if let Some(type_) = type_ {
+                            hint(argument, *type_);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    hints
+}
+
+/// Calls to framework functions which are described in English.
+enum KnownCall {
+    Transfer,
+    CoinSplit,
+    CoinJoin,
+    CoinZero,
+    CoinDestroyZero,
+    SplitAndTransfer,
+    AddStake,
+    WithdrawStake,
+}
+
+impl KnownCall {
+    fn new(call: &MoveCall) -> Option<Self> {
+        let known = match (call.package, call.module.as_str(), call.function.as_str()) {
+            (Address::TWO, "transfer", "public_transfer" | "transfer") => KnownCall::Transfer,
+            (Address::TWO, "coin", "split") => KnownCall::CoinSplit,
+            (Address::TWO, "coin", "join") => KnownCall::CoinJoin,
+            (Address::TWO, "coin", "zero") => KnownCall::CoinZero,
+            (Address::TWO, "coin", "destroy_zero") => KnownCall::CoinDestroyZero,
+            (Address::TWO, "pay", "split_and_transfer") => KnownCall::SplitAndTransfer,
+            (Address::THREE, "myso_system", "request_add_stake") => KnownCall::AddStake,
+            (Address::THREE, "myso_system", "request_withdraw_stake") => KnownCall::WithdrawStake,
+            _ => return None,
+        };
+        Some(known)
+    }
+
+    /// The types of the function's pure parameters, by position.
+    fn parameters(&self) -> &'static [Option<PureType>] {
+        match self {
+            KnownCall::Transfer => &[None, Some(PureType::Address)],
+            KnownCall::CoinSplit => &[None, Some(PureType::U64)],
+            KnownCall::SplitAndTransfer => &[None, Some(PureType::U64), Some(PureType::Address)],
+            KnownCall::AddStake => &[None, None, Some(PureType::Address)],
+            KnownCall::CoinJoin
+            | KnownCall::CoinZero
+            | KnownCall::CoinDestroyZero
+            | KnownCall::WithdrawStake => &[],
+        }
+    }
+}
+
+/// Resolves arguments to the names and values they refer to.
+struct Names<'a> {
+    ptb: &'a ProgrammableTransaction,
+    hints: &'a HashMap<u16, PureType>,
+}
+
+impl Names<'_> {
+    fn input(&self, i: u16, input: &Input) -> (&'static str, String) {
+        match input {
+            Input::Pure(bytes) => ("pure", self.pure(i, bytes)),
+            Input::ImmutableOrOwned(reference) => ("object", object_reference(reference)),
+            Input::Shared(shared) => {
+                let mutability = match shared.mutability() {
+                    Mutability::Immutable => "immutable",
+                    Mutability::Mutable => "mutable",
+                    Mutability::NonExclusiveWrite => "non-exclusive write",
+                };
+                (
+                    "shared object",
+                    format!(
+                        "{} (initial version {}, {mutability})",
+                        shared.object_id(),
+                        shared.version()
+                    ),
+                )
+            }
+            Input::Receiving(reference) => ("receiving object", object_reference(reference)),
+            Input::FundsWithdrawal(withdrawal) => {
+                let amount = match withdrawal.amount() {
+                    Some(amount) => amount.to_string(),
+                    None => "an unknown amount".to_owned(),
+                };
+                let source = match withdrawal.source() {
+                    WithdrawFrom::Sender => "sender",
+                    WithdrawFrom::Sponsor => "sponsor",
+                };
+                (
+                    "withdrawal",
+                    format!(
+                        "{amount} of {} from the {source}",
+                        withdrawal.coin_type().to_short_string()
+                    ),
+                )
+            }
+        }
+    }
+
+    /// Decode a pure input if its type is known, otherwise render its bytes as hex.
+    fn pure(&self, i: u16, bytes: &[u8]) -> String {
+        match self.hints.get(&i) {
+            Some(PureType::U64) => {
+                if let Ok(bytes) = <[u8; 8]>::try_from(bytes) {
+                    return u64::from_le_bytes(bytes).to_string();
+                }
+            }
+            Some(PureType::Address) => {
+                if let Ok(bytes) = <[u8; Address::LENGTH]>::try_from(bytes) {
+                    return Address::new(bytes).to_string();
+                }
+            }
+            None => {}
+        }
+
+        let hex = bytes
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<String>();
+        format!("0x{hex} ({} bytes)", bytes.len())
+    }
+
+    /// The name of an argument, e.g. `input0`.
+    fn name(&self, argument: &Argument) -> String {
+        match argument {
+            Argument::Gas => "gas".to_owned(),
+            Argument::Input(i) => format!("input{i}"),
+            Argument::Result(i) => format!("result{i}"),
+            Argument::NestedResult(i, j) => format!("result{i}.{j}"),
+        }
+    }
+
+    fn names(&self, arguments: &[Argument]) -> String {
+        arguments
+            .iter()
+            .map(|argument| self.name(argument))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// An argument as it reads in an English description, using the value of inputs rather than
+    /// their names.
+    fn value(&self, argument: &Argument) -> String {
+        match argument {
+            Argument::Gas => "the gas coin".to_owned(),
+            Argument::Input(i) => match self.ptb.inputs.get(*i as usize) {
+                Some(Input::Pure(bytes)) => self.pure(*i, bytes),
+                Some(Input::ImmutableOrOwned(reference) | Input::Receiving(reference)) => {
+                    format!("object {}", reference.object_id())
+                }
+                Some(Input::Shared(shared)) => format!("shared object {}", shared.object_id()),
+                Some(Input::FundsWithdrawal(_)) | None => self.name(argument),
+            },
+            Argument::Result(_) | Argument::NestedResult(..) => self.name(argument),
+        }
+    }
+
+    fn values(&self, arguments: &[Argument]) -> String {
+        let values = arguments
+            .iter()
+            .map(|argument| self.value(argument))
+            .collect::<Vec<_>>();
+        match values.as_slice() {
+            [] => "nothing".to_owned(),
+            [value] => value.clone(),
+            [init @ .., last] => format!("{} and {last}", init.join(", ")),
+        }
+    }
+
+    fn command(&self, command: &Command) -> String {
+        match command {
+            Command::MoveCall(call) => format!(
+                "{}{}({})",
+                function_name(call),
+                type_arguments(&call.type_arguments),
+                self.names(&call.arguments)
+            ),
+            Command::TransferObjects(transfer) => format!(
+                "TransferObjects([{}], {})",
+                self.names(&transfer.objects),
+                self.name(&transfer.address)
+            ),
+            Command::SplitCoins(split) => format!(
+                "SplitCoins({}, [{}])",
+                self.name(&split.coin),
+                self.names(&split.amounts)
+            ),
+            Command::MergeCoins(merge) => format!(
+                "MergeCoins({}, [{}])",
+                self.name(&merge.coin),
+                self.names(&merge.coins_to_merge)
+            ),
+            Command::Publish(publish) => format!(
+                "Publish({} modules, [{}])",
+                publish.modules.len(),
+                addresses(&publish.dependencies)
+            ),
+            Command::MakeMoveVector(vector) => format!(
+                "MakeMoveVector{}([{}])",
+                type_arguments(vector.type_.as_slice()),
+                self.names(&vector.elements)
+            ),
+            Command::Upgrade(upgrade) => format!(
+                "Upgrade({} modules, [{}], {}, {})",
+                upgrade.modules.len(),
+                addresses(&upgrade.dependencies),
+                upgrade.package,
+                self.name(&upgrade.ticket)
+            ),
+        }
+    }
+
+    fn describe(&self, command: &Command) -> String {
+        match command {
+            Command::MoveCall(call) => self.describe_call(call),
+            Command::TransferObjects(transfer) => format!(
+                "Transfer {} to {}",
+                self.values(&transfer.objects),
+                self.value(&transfer.address)
+            ),
+            Command::SplitCoins(split) => format!(
+                "Split {} from {}",
+                self.values(&split.amounts),
+                self.value(&split.coin)
+            ),
+            Command::MergeCoins(merge) => format!(
+                "Merge {} into {}",
+                self.values(&merge.coins_to_merge),
+                self.value(&merge.coin)
+            ),
+            Command::Publish(publish) => {
+                format!("Publish a package of {} modules", publish.modules.len())
+            }
+            Command::MakeMoveVector(vector) => {
+                let type_ = match &vector.type_ {
+                    Some(type_) => format!(" of {}", type_.to_short_string()),
+                    None => String::new(),
+                };
+                format!(
+                    "Make a vector{type_} containing {}",
+                    self.values(&vector.elements)
+                )
+            }
+            Command::Upgrade(upgrade) => format!(
+                "Upgrade package {} to a package of {} modules, authorized by {}",
+                upgrade.package,
+                upgrade.modules.len(),
+                self.value(&upgrade.ticket)
+            ),
+        }
+    }
+
+    fn describe_call(&self, call: &MoveCall) -> String {
+        let argument = |i: usize| match call.arguments.get(i) {
+            Some(argument) => self.value(argument),
+            None => "nothing".to_owned(),
+        };
+        let type_argument = match call.type_arguments.first() {
+            Some(type_) => type_.to_short_string(),
+            None => "unknown".to_owned(),
+        };
+
+        match KnownCall::new(call) {
+            Some(KnownCall::Transfer) => format!(
+                "Transfer {} ({type_argument}) to {}",
+                argument(0),
+                argument(1)
+            ),
+            Some(KnownCall::CoinSplit) => format!(
+                "Split {} from {} ({type_argument})",
+                argument(1),
+                argument(0)
+            ),
+            Some(KnownCall::CoinJoin) => format!(
+                "Merge {} into {} ({type_argument})",
+                argument(1),
+                argument(0)
+            ),
+            Some(KnownCall::CoinZero) => {
+                format!("Create a {type_argument} coin with a balance of zero")
+            }
+            Some(KnownCall::CoinDestroyZero) => format!(
+                "Destroy {}, a {type_argument} coin with a balance of zero",
+                argument(0)
+            ),
+            Some(KnownCall::SplitAndTransfer) => format!(
+                "Split {} from {} ({type_argument}) and transfer it to {}",
+                argument(1),
+                argument(0),
+                argument(2)
+            ),
+            Some(KnownCall::AddStake) => {
+                format!("Stake {} with validator {}", argument(1), argument(2))
+            }
+            Some(KnownCall::WithdrawStake) => format!("Withdraw the stake {}", argument(1)),
+            None if call.arguments.is_empty() => format!("Call {}", function_name(call)),
+            None => format!(
+                "Call {} with {}",
+                function_name(call),
+                self.values(&call.arguments)
+            ),
+        }
+    }
+}
+
+fn object_reference(reference: &ObjectReference) -> String {
+    format!(
+        "{} (version {}, digest {})",
+        reference.object_id(),
+        reference.version(),
+        reference.digest()
+    )
+}
+
+fn function_name(call: &MoveCall) -> String {
+    format!(
+        "{}::{}::{}",
+        call.package.to_short_string(),
+        call.module,
+        call.function
+    )
+}
+
+fn type_arguments(types: &[TypeTag]) -> String {
+    if types.is_empty() {
+        return String::new();
+    }
+
+    let types = types
+        .iter()
+        .map(TypeTag::to_short_string)
+        .collect::<Vec<_>>();
+    format!("<{}>", types.join(", "))
+}
+
+fn addresses(addresses: &[Address]) -> String {
+    addresses
+        .iter()
+        .map(Address::to_short_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Digest;
+    use crate::GasPayment;
+    use crate::Identifier;
+    use crate::SplitCoins;
+    use crate::StructTag;
+    use crate::TransferObjects;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    fn transaction() -> Transaction {
+        let recipient = Address::from_static("0xabc");
+        let coin = ObjectReference::new(Address::from_static("0xc0"), 3, Digest::ZERO);
+        let ptb = ProgrammableTransaction {
+            inputs: vec![
+                Input::Pure(1000u64.to_le_bytes().to_vec()),
+                Input::Pure(recipient.into_inner().to_vec()),
+                Input::ImmutableOrOwned(coin),
+                Input::Pure(vec![1, 2, 3]),
+            ],
+            commands: vec![
+                Command::SplitCoins(SplitCoins {
+                    coin: Argument::Gas,
+                    amounts: vec![Argument::Input(0)],
+                }),
+                Command::MoveCall(MoveCall {
+                    package: Address::TWO,
+                    module: Identifier::from_static("coin"),
+                    function: Identifier::from_static("join"),
+                    type_arguments: vec![StructTag::myso().into()],
+                    arguments: vec![Argument::NestedResult(0, 0), Argument::Input(2)],
+                }),
+                Command::TransferObjects(TransferObjects {
+                    objects: vec![Argument::NestedResult(0, 0)],
+                    address: Argument::Input(1),
+                }),
+                Command::MoveCall(MoveCall {
+                    package: Address::from_static("0x1234"),
+                    module: Identifier::from_static("game"),
+                    function: Identifier::from_static("play"),
+                    type_arguments: vec![],
+                    arguments: vec![Argument::Input(3)],
+                }),
+            ],
+        };
+
+        Transaction {
+            kind: TransactionKind::ProgrammableTransaction(ptb),
+            sender: Address::from_static("0x5e"),
+            gas_payment: GasPayment {
+                objects: vec![],
+                owner: Address::from_static("0x5e"),
+                price: 1000,
+                budget: 5000000,
+            },
+            expiration: TransactionExpiration::None,
+        }
+    }
+
+    #[test]
+    fn render() {
+        let transaction = transaction();
+        let rendered = RenderedTransaction::new(&transaction);
+        let programmable = rendered.programmable.as_ref().unwrap();
+
+        let inputs = programmable
+            .inputs
+            .iter()
+            .map(|input| format!("{}: {} {}", input.name, input.kind, input.value))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            inputs,
+            [
+                "input0: pure 1000".to_owned(),
+                format!("input1: pure {}", Address::from_static("0xabc")),
+                format!(
+                    "input2: object {} (version 3, digest {})",
+                    Address::from_static("0xc0"),
+                    Digest::ZERO
+                ),
+                "input3: pure 0x010203 (3 bytes)".to_owned(),
+            ]
+        );
+
+        let commands = programmable
+            .commands
+            .iter()
+            .map(|command| (command.command.as_str(), command.description.as_str()))
+            .collect::<Vec<_>>();
+        let recipient = Address::from_static("0xabc").to_string();
+        let coin = Address::from_static("0xc0").to_string();
+        assert_eq!(
+            commands,
+            [
+                ("SplitCoins(gas, [input0])", "Split 1000 from the gas coin"),
+                (
+                    "0x2::coin::join<0x2::myso::MYSO>(result0.0, input2)",
+                    &format!("Merge object {coin} into result0.0 (0x2::myso::MYSO)")
+                ),
+                (
+                    "TransferObjects([result0.0], input1)",
+                    &format!("Transfer result0.0 to {recipient}")
+                ),
+                (
+                    "0x1234::game::play(input3)",
+                    "Call 0x1234::game::play with 0x010203 (3 bytes)"
+                ),
+            ]
+        );
+
+        let text = rendered.to_string();
+        assert!(text.starts_with("Programmable transaction from 0x"));
+        assert!(
+            text.contains(
+                "  result0 = SplitCoins(gas, [input0])\n    Split 1000 from the gas coin\n"
+            )
+        );
+
+        #[cfg(feature = "serde")]
+        {
+            let json = rendered.to_json();
+            assert_eq!(json["gas_budget"], 5000000);
+            assert_eq!(json["programmable"]["inputs"][0]["value"], "1000");
+            assert_eq!(json["programmable"]["commands"][2]["result"], "result2");
+        }
+    }
+
+    #[test]
+    fn diff() {
+        let before = transaction();
+        assert!(TransactionDiff::new(&before, &before).is_empty());
+
+        // A sponsor pays for gas
+        let sponsor = Address::from_static("0x5a");
+        let gas = ObjectReference::new(Address::from_static("0x6a5"), 7, Digest::ZERO);
+        let mut after = before.clone();
+        after.gas_payment.owner = sponsor;
+        after.gas_payment.objects.push(gas.clone());
+        after.expiration = TransactionExpiration::Epoch(10);
+
+        let diff = TransactionDiff::new(&before, &after);
+        let fields = diff
+            .changes
+            .iter()
+            .map(|change| change.field.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(fields, ["gas.owner", "expiration", "gas.objects[0]"]);
+        assert_eq!(
+            diff.changes[0],
+            FieldChange {
+                field: "gas.owner".to_owned(),
+                before: Some(Address::from_static("0x5e").to_string()),
+                after: Some(sponsor.to_string()),
+            }
+        );
+        assert_eq!(diff.changes[2].before, None);
+        assert_eq!(
+            diff.to_string().lines().nth(1),
+            Some("~ expiration: none -> after epoch 10")
+        );
+
+        // Removing a command
+        let mut removed = before.clone();
+        let TransactionKind::ProgrammableTransaction(ptb) = &mut removed.kind else {
+            unreachable!()
+        };
+        ptb.commands.pop();
+        let diff = TransactionDiff::new(&before, &removed);
+        assert_eq!(diff.changes.len(), 1);
+        assert_eq!(diff.changes[0].field, "commands[3]");
+        assert_eq!(diff.changes[0].after, None);
+    }
+}
@@ -1,4 +1,8 @@
 mod parse;
+mod pattern;
+
+pub use pattern::StructPattern;
+pub use pattern::TypePattern;
 
 #[cfg(feature = "serde")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
@@ -54,9 +58,68 @@ pub enum TypeTag {
     Struct(Box<StructTag>),
 }
 
+impl TypeTag {
+    /// Display this type with its addresses formatted according to `mode`.
+    pub fn display_with(&self, mode: TypeDisplayMode) -> TypeTagDisplay<'_> {
+        TypeTagDisplay {
+            type_tag: self,
+            mode,
+        }
+    }
+
+    /// Format this type with its addresses in their short form, e.g. `0x2::coin::Coin<u64>`.
+    pub fn to_short_string(&self) -> String {
+        self.display_with(TypeDisplayMode::Short).to_string()
+    }
+
+    /// Format this type with its addresses in their long, zero-padded form.
+    ///
+    /// This is the same as the [`Display`](std::fmt::Display) implementation.
+    pub fn to_canonical_string(&self) -> String {
+        self.display_with(TypeDisplayMode::Long).to_string()
+    }
+
+    /// Normalize a type in any accepted form into its canonical form.
+    ///
+    /// Addresses are zero-padded and whitespace between type parameters is normalized, so that
+    /// types can be compared as strings regardless of where they came from.
+    pub fn canonicalize(type_: &str) -> Result<String, TypeParseError> {
+        type_
+            .parse::<Self>()
+            .map(|type_| type_.to_canonical_string())
+    }
+}
+
 impl std::fmt::Display for TypeTag {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
+        self.display_with(TypeDisplayMode::Long).fmt(f)
+    }
+}
+
+/// How the addresses in a [`TypeTag`] or [`StructTag`] are formatted when it is displayed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum TypeDisplayMode {
+    /// Addresses are zero-padded to their full length, e.g.
+    /// `0x0000000000000000000000000000000000000000000000000000000000000002::coin::Coin`.
+    ///
+    /// This is the canonical form of a type.
+    #[default]
+    Long,
+
+    /// Addresses have their leading zeros stripped, e.g. `0x2::coin::Coin`.
+    Short,
+}
+
+/// Displays a [`TypeTag`] in a particular [`TypeDisplayMode`].
+#[derive(Clone, Copy, Debug)]
+pub struct TypeTagDisplay<'a> {
+    type_tag: &'a TypeTag,
+    mode: TypeDisplayMode,
+}
+
+impl std::fmt::Display for TypeTagDisplay<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.type_tag {
             TypeTag::U8 => write!(f, "u8"),
             TypeTag::U16 => write!(f, "u16"),
             TypeTag::U32 => write!(f, "u32"),
@@ -67,9 +130,9 @@ impl std::fmt::Display for TypeTag {
             TypeTag::Address => write!(f, "address"),
             TypeTag::Signer => write!(f, "signer"),
             TypeTag::Vector(t) => {
-                write!(f, "vector<{t}>")
+                write!(f, "vector<{}>", t.display_with(self.mode))
             }
-            TypeTag::Struct(s) => s.fmt(f),
+            TypeTag::Struct(s) => s.display_with(self.mode).fmt(f),
         }
     }
 }
@@ -294,6 +357,100 @@ impl StructTag {
         }
     }
 
+    /// Returns the struct tag for `0x1::string::String`.
+    pub fn string() -> Self {
+        Self::framework(Address::ONE, "string", "String", vec![])
+    }
+
+    /// Returns the struct tag for `0x1::option::Option<T>`.
+    pub fn option(type_tag: TypeTag) -> Self {
+        Self::framework(Address::ONE, "option", "Option", vec![type_tag])
+    }
+
+    /// Returns the struct tag for `0x2::object::UID`.
+    pub fn uid() -> Self {
+        Self::framework(Address::TWO, "object", "UID", vec![])
+    }
+
+    /// Returns the struct tag for `0x2::object::ID`.
+    pub fn id() -> Self {
+        Self::framework(Address::TWO, "object", "ID", vec![])
+    }
+
+    /// Returns the struct tag for `0x2::balance::Balance<T>`.
+    pub fn balance(type_tag: TypeTag) -> Self {
+        Self::framework(Address::TWO, "balance", "Balance", vec![type_tag])
+    }
+
+    /// Returns the struct tag for `0x2::balance::Supply<T>`.
+    pub fn supply(type_tag: TypeTag) -> Self {
+        Self::framework(Address::TWO, "balance", "Supply", vec![type_tag])
+    }
+
+    /// Returns the struct tag for `0x2::coin::CoinMetadata<T>`.
+    pub fn coin_metadata(type_tag: TypeTag) -> Self {
+        Self::framework(Address::TWO, "coin", "CoinMetadata", vec![type_tag])
+    }
+
+    /// Returns the struct tag for `0x2::coin::TreasuryCap<T>`.
+    pub fn treasury_cap(type_tag: TypeTag) -> Self {
+        Self::framework(Address::TWO, "coin", "TreasuryCap", vec![type_tag])
+    }
+
+    /// Returns the struct tag for `0x2::package::UpgradeCap`.
+    pub fn upgrade_cap() -> Self {
+        Self::framework(Address::TWO, "package", "UpgradeCap", vec![])
+    }
+
+    /// Returns the struct tag for `0x2::display::Display<T>`.
+    pub fn display(type_tag: TypeTag) -> Self {
+        Self::framework(Address::TWO, "display", "Display", vec![type_tag])
+    }
+
+    /// Returns the struct tag for `0x2::clock::Clock`.
+    pub fn clock() -> Self {
+        Self::framework(Address::TWO, "clock", "Clock", vec![])
+    }
+
+    /// Returns the struct tag for `0x2::dynamic_field::Field<K, V>`.
+    pub fn dynamic_field(key: TypeTag, value: TypeTag) -> Self {
+        Self::framework(Address::TWO, "dynamic_field", "Field", vec![key, value])
+    }
+
+    /// Returns the struct tag for `0x2::table::Table<K, V>`.
+    pub fn table(key: TypeTag, value: TypeTag) -> Self {
+        Self::framework(Address::TWO, "table", "Table", vec![key, value])
+    }
+
+    /// Returns the struct tag for `0x2::bag::Bag`.
+    pub fn bag() -> Self {
+        Self::framework(Address::TWO, "bag", "Bag", vec![])
+    }
+
+    /// Returns the struct tag for `0x2::vec_map::VecMap<K, V>`.
+    pub fn vec_map(key: TypeTag, value: TypeTag) -> Self {
+        Self::framework(Address::TWO, "vec_map", "VecMap", vec![key, value])
+    }
+
+    /// Returns the struct tag for `0x2::vec_set::VecSet<K>`.
+    pub fn vec_set(key: TypeTag) -> Self {
+        Self::framework(Address::TWO, "vec_set", "VecSet", vec![key])
+    }
+
+    fn framework(
+        address: Address,
+        module: &'static str,
+        name: &'static str,
+        type_params: Vec<TypeTag>,
+    ) -> Self {
+        Self {
+            address,
+            module: Identifier::from_static(module),
+            name: Identifier::from_static(name),
+            type_params,
+        }
+    }
+
     /// Checks if this is a Coin type
     pub fn is_coin(&self) -> Option<&TypeTag> {
         let Self {
@@ -392,15 +549,68 @@ impl StructTag {
     }
 }
 
+impl StructTag {
+    /// Display this type with its addresses formatted according to `mode`.
+    pub fn display_with(&self, mode: TypeDisplayMode) -> StructTagDisplay<'_> {
+        StructTagDisplay {
+            struct_tag: self,
+            mode,
+        }
+    }
+
+    /// Format this type with its addresses in their short form, e.g. `0x2::coin::Coin<u64>`.
+    pub fn to_short_string(&self) -> String {
+        self.display_with(TypeDisplayMode::Short).to_string()
+    }
+
+    /// Format this type with its addresses in their long, zero-padded form.
+    ///
+    /// This is the same as the [`Display`](std::fmt::Display) implementation.
+    pub fn to_canonical_string(&self) -> String {
+        self.display_with(TypeDisplayMode::Long).to_string()
+    }
+
+    /// Normalize a struct type in any accepted form into its canonical form.
+    pub fn canonicalize(type_: &str) -> Result<String, TypeParseError> {
+        type_
+            .parse::<Self>()
+            .map(|type_| type_.to_canonical_string())
+    }
+}
+
 impl std::fmt::Display for StructTag {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}::{}::{}", self.address, self.module, self.name)?;
+        self.display_with(TypeDisplayMode::Long).fmt(f)
+    }
+}
+
+/// Displays a [`StructTag`] in a particular [`TypeDisplayMode`].
+#[derive(Clone, Copy, Debug)]
+pub struct StructTagDisplay<'a> {
+    struct_tag: &'a StructTag,
+    mode: TypeDisplayMode,
+}
+
+impl std::fmt::Display for StructTagDisplay<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let StructTag {
+            address,
+            module,
+            name,
+            type_params,
+        } = self.struct_tag;
+
+        match self.mode {
+            TypeDisplayMode::Long => write!(f, "{address}")?,
+            TypeDisplayMode::Short => write!(f, "{}", address.to_short_string())?,
+        }
+        write!(f, "::{module}::{name}")?;
 
-        if let Some(first_type) = self.type_params.first() {
+        if let Some(first_type) = type_params.first() {
             write!(f, "<")?;
-            write!(f, "{first_type}")?;
-            for ty in self.type_params.iter().skip(1) {
-                write!(f, ", {ty}")?;
+            write!(f, "{}", first_type.display_with(self.mode))?;
+            for ty in type_params.iter().skip(1) {
+                write!(f, ", {}", ty.display_with(self.mode))?;
             }
             write!(f, ">")?;
         }
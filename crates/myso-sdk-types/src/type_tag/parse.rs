@@ -23,7 +23,7 @@ pub(super) fn parse_identifier(mut input: &str) -> ModalResult<&str> {
     (identifier, eof).take().parse_next(&mut input)
 }
 
-fn identifier<'s>(input: &mut &'s str) -> ModalResult<&'s str> {
+pub(super) fn identifier<'s>(input: &mut &'s str) -> ModalResult<&'s str> {
     alt((
         (one_of(|c: char| c.is_alpha()), valid_remainder(0)),
         ('_', valid_remainder(1)),
@@ -45,7 +45,7 @@ fn valid_remainder<'a>(
     }
 }
 
-fn parse_address<'s>(input: &mut &'s str) -> ModalResult<&'s str> {
+pub(super) fn parse_address<'s>(input: &mut &'s str) -> ModalResult<&'s str> {
     ("0x", take_while(1..=64, AsChar::is_hex_digit))
         .take()
         .parse_next(input)
@@ -262,6 +262,43 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_display_modes() {
+        let long = "0x0000000000000000000000000000000000000000000000000000000000000002::coin::Coin<vector<0x00000000000000000000000000000000000000000000000000000000000000ab::a::B>>";
+        let short = "0x2::coin::Coin<vector<0xab::a::B>>";
+
+        let tag = TypeTag::from_str(short).unwrap();
+        assert_eq!(tag.to_short_string(), short);
+        assert_eq!(tag.to_canonical_string(), long);
+        assert_eq!(tag.to_string(), long);
+        assert_eq!(
+            tag.display_with(super::super::TypeDisplayMode::Short)
+                .to_string(),
+            short
+        );
+
+        let TypeTag::Struct(struct_tag) = &tag else {
+            unreachable!()
+        };
+        assert_eq!(struct_tag.to_short_string(), short);
+        assert_eq!(struct_tag.to_canonical_string(), long);
+
+        assert_eq!(TypeTag::canonicalize(short).unwrap(), long);
+        assert_eq!(TypeTag::canonicalize(long).unwrap(), long);
+        assert_eq!(
+            StructTag::canonicalize("0x2::coin::Coin<u8,   u64>").unwrap(),
+            "0x0000000000000000000000000000000000000000000000000000000000000002::coin::Coin<u8, u64>"
+        );
+        assert_eq!(TypeTag::canonicalize("vector<u8>").unwrap(), "vector<u8>");
+        TypeTag::canonicalize("0x2::coin").unwrap_err();
+
+        assert_eq!(Address::ZERO.to_short_string(), "0x0");
+        assert_eq!(
+            Address::from_str("0x0100").unwrap().to_short_string(),
+            "0x100"
+        );
+    }
+
     #[test_strategy::proptest]
     fn test_identifier_parsing_matches(s: String) {
         match Identifier::new(&s) {
@@ -0,0 +1,415 @@
+use super::Address;
+use super::Identifier;
+use super::StructTag;
+use super::TypeParseError;
+use super::TypeTag;
+use super::parse::identifier;
+use super::parse::parse_address;
+
+use winnow::ModalResult;
+use winnow::Parser;
+use winnow::ascii::space0;
+use winnow::combinator::alt;
+use winnow::combinator::delimited;
+use winnow::combinator::eof;
+use winnow::combinator::opt;
+use winnow::combinator::preceded;
+use winnow::combinator::separated;
+
+/// A pattern which matches a set of types
+///
+/// Patterns are written like types, with `*` standing in for any type, module or name:
+///
+/// - `*` matches any type.
+/// - `0x2` matches any struct defined in the package at `0x2`.
+/// - `0x2::coin` and `0x2::coin::*` match any struct defined in the `coin` module.
+/// - `0x2::coin::Coin` matches `Coin` with any type parameters.
+/// - `0x2::coin::Coin<*>` matches `Coin` with exactly one type parameter, which matches `*`.
+/// - `vector<0x2::coin::Coin<0x2::myso::MYSO>>` matches only that exact type.
+///
+/// Addresses may be written in their short or long form.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TypePattern {
+    Any,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    U256,
+    Bool,
+    Address,
+    Signer,
+    Vector(Box<TypePattern>),
+    Struct(Box<StructPattern>),
+}
+
+impl TypePattern {
+    /// Whether `type_tag` matches this pattern.
+    pub fn matches(&self, type_tag: &TypeTag) -> bool {
+        match (self, type_tag) {
+            (TypePattern::Any, _)
+            | (TypePattern::U8, TypeTag::U8)
+            | (TypePattern::U16, TypeTag::U16)
+            | (TypePattern::U32, TypeTag::U32)
+            | (TypePattern::U64, TypeTag::U64)
+            | (TypePattern::U128, TypeTag::U128)
+            | (TypePattern::U256, TypeTag::U256)
+            | (TypePattern::Bool, TypeTag::Bool)
+            | (TypePattern::Address, TypeTag::Address)
+            | (TypePattern::Signer, TypeTag::Signer) => true,
+            (TypePattern::Vector(pattern), TypeTag::Vector(type_tag)) => pattern.matches(type_tag),
+            (TypePattern::Struct(pattern), TypeTag::Struct(struct_tag)) => {
+                pattern.matches(struct_tag)
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether the struct type `struct_tag` matches this pattern.
+    ///
+    /// This is useful for filtering events and objects, whose types are always structs.
+    pub fn matches_struct(&self, struct_tag: &StructTag) -> bool {
+        match self {
+            TypePattern::Any => true,
+            TypePattern::Struct(pattern) => pattern.matches(struct_tag),
+            _ => false,
+        }
+    }
+}
+
+impl From<TypeTag> for TypePattern {
+    /// A pattern matching exactly `type_tag`.
+    fn from(type_tag: TypeTag) -> Self {
+        match type_tag {
+            TypeTag::U8 => TypePattern::U8,
+            TypeTag::U16 => TypePattern::U16,
+            TypeTag::U32 => TypePattern::U32,
+            TypeTag::U64 => TypePattern::U64,
+            TypeTag::U128 => TypePattern::U128,
+            TypeTag::U256 => TypePattern::U256,
+            TypeTag::Bool => TypePattern::Bool,
+            TypeTag::Address => TypePattern::Address,
+            TypeTag::Signer => TypePattern::Signer,
+            TypeTag::Vector(type_tag) => TypePattern::Vector(Box::new((*type_tag).into())),
+            TypeTag::Struct(struct_tag) => TypePattern::Struct(Box::new((*struct_tag).into())),
+        }
+    }
+}
+
+impl From<StructPattern> for TypePattern {
+    fn from(value: StructPattern) -> Self {
+        Self::Struct(Box::new(value))
+    }
+}
+
+impl std::fmt::Display for TypePattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TypePattern::Any => write!(f, "*"),
+            TypePattern::U8 => write!(f, "u8"),
+            TypePattern::U16 => write!(f, "u16"),
+            TypePattern::U32 => write!(f, "u32"),
+            TypePattern::U64 => write!(f, "u64"),
+            TypePattern::U128 => write!(f, "u128"),
+            TypePattern::U256 => write!(f, "u256"),
+            TypePattern::Bool => write!(f, "bool"),
+            TypePattern::Address => write!(f, "address"),
+            TypePattern::Signer => write!(f, "signer"),
+            TypePattern::Vector(pattern) => write!(f, "vector<{pattern}>"),
+            TypePattern::Struct(pattern) => pattern.fmt(f),
+        }
+    }
+}
+
+impl std::str::FromStr for TypePattern {
+    type Err = TypeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_type_pattern(s).map_err(|_| TypeParseError { source: s.into() })
+    }
+}
+
+/// A pattern which matches a set of struct types
+///
+/// A missing module or name matches any module or name, while missing type parameters match
+/// any type parameters. See [`TypePattern`] for the syntax of patterns.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct StructPattern {
+    address: Address,
+    module: Option<Identifier>,
+    name: Option<Identifier>,
+    type_params: Option<Vec<TypePattern>>,
+}
+
+impl StructPattern {
+    pub fn new(
+        address: Address,
+        module: Option<Identifier>,
+        name: Option<Identifier>,
+        type_params: Option<Vec<TypePattern>>,
+    ) -> Self {
+        Self {
+            address,
+            module,
+            name,
+            type_params,
+        }
+    }
+
+    pub fn address(&self) -> &Address {
+        &self.address
+    }
+
+    pub fn module(&self) -> Option<&Identifier> {
+        self.module.as_ref()
+    }
+
+    pub fn name(&self) -> Option<&Identifier> {
+        self.name.as_ref()
+    }
+
+    pub fn type_params(&self) -> Option<&[TypePattern]> {
+        self.type_params.as_deref()
+    }
+
+    /// Whether `struct_tag` matches this pattern.
+    pub fn matches(&self, struct_tag: &StructTag) -> bool {
+        self.address == struct_tag.address
+            && self
+                .module
+                .as_ref()
+                .is_none_or(|module| module == &struct_tag.module)
+            && self
+                .name
+                .as_ref()
+                .is_none_or(|name| name == &struct_tag.name)
+            && self.type_params.as_ref().is_none_or(|patterns| {
+                patterns.len() == struct_tag.type_params.len()
+                    && patterns
+                        .iter()
+                        .zip(&struct_tag.type_params)
+                        .all(|(pattern, type_tag)| pattern.matches(type_tag))
+            })
+    }
+}
+
+impl From<StructTag> for StructPattern {
+    /// A pattern matching exactly `struct_tag`.
+    fn from(struct_tag: StructTag) -> Self {
+        let StructTag {
+            address,
+            module,
+            name,
+            type_params,
+        } = struct_tag;
+        Self {
+            address,
+            module: Some(module),
+            name: Some(name),
+            type_params: Some(type_params.into_iter().map(Into::into).collect()),
+        }
+    }
+}
+
+impl std::fmt::Display for StructPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let wildcard = |identifier: &Option<Identifier>| {
+            identifier
+                .as_ref()
+                .map(Identifier::as_str)
+                .unwrap_or("*")
+                .to_owned()
+        };
+
+        write!(f, "{}", self.address)?;
+        if self.module.is_some() || self.name.is_some() || self.type_params.is_some() {
+            write!(f, "::{}", wildcard(&self.module))?;
+        }
+        if self.name.is_some() || self.type_params.is_some() {
+            write!(f, "::{}", wildcard(&self.name))?;
+        }
+
+        if let Some(type_params) = &self.type_params
+            && let Some(first) = type_params.first()
+        {
+            write!(f, "<{first}")?;
+            for pattern in type_params.iter().skip(1) {
+                write!(f, ", {pattern}")?;
+            }
+            write!(f, ">")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for StructPattern {
+    type Err = TypeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_struct_pattern(s).map_err(|_| TypeParseError { source: s.into() })
+    }
+}
+
+fn parse_type_pattern(mut input: &str) -> ModalResult<TypePattern> {
+    (type_pattern, eof).parse_next(&mut input).map(|(p, _)| p)
+}
+
+fn type_pattern(input: &mut &str) -> ModalResult<TypePattern> {
+    alt((
+        "*".value(TypePattern::Any),
+        "u8".value(TypePattern::U8),
+        "u16".value(TypePattern::U16),
+        "u32".value(TypePattern::U32),
+        "u64".value(TypePattern::U64),
+        "u128".value(TypePattern::U128),
+        "u256".value(TypePattern::U256),
+        "bool".value(TypePattern::Bool),
+        "address".value(TypePattern::Address),
+        "signer".value(TypePattern::Signer),
+        delimited("vector<", type_pattern, ">").map(|p| TypePattern::Vector(Box::new(p))),
+        struct_pattern.map(|p| TypePattern::Struct(Box::new(p))),
+    ))
+    .parse_next(input)
+}
+
+fn parse_struct_pattern(mut input: &str) -> ModalResult<StructPattern> {
+    (struct_pattern, eof).parse_next(&mut input).map(|(p, _)| p)
+}
+
+fn struct_pattern(input: &mut &str) -> ModalResult<StructPattern> {
+    let address = parse_address
+        .try_map(|s| s.parse::<Address>())
+        .parse_next(input)?;
+
+    let mut pattern = StructPattern {
+        address,
+        module: None,
+        name: None,
+        type_params: None,
+    };
+    let Some(module) = opt(preceded("::", wildcard_identifier)).parse_next(input)? else {
+        return Ok(pattern);
+    };
+    pattern.module = module;
+    let Some(name) = opt(preceded("::", wildcard_identifier)).parse_next(input)? else {
+        return Ok(pattern);
+    };
+    pattern.name = name;
+    pattern.type_params = opt(delimited("<", generics, ">")).parse_next(input)?;
+
+    Ok(pattern)
+}
+
+fn wildcard_identifier(input: &mut &str) -> ModalResult<Option<Identifier>> {
+    alt((
+        "*".value(None),
+        identifier.map(|ident| Some(Identifier(ident.into()))),
+    ))
+    .parse_next(input)
+}
+
+fn generics(input: &mut &str) -> ModalResult<Vec<TypePattern>> {
+    separated(1.., delimited(space0, type_pattern, space0), ",").parse_next(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    fn matches(pattern: &str, type_: &str) -> bool {
+        let pattern = pattern.parse::<TypePattern>().unwrap();
+        pattern.matches(&type_.parse().unwrap())
+    }
+
+    #[test]
+    fn matching() {
+        let coin = "0x2::coin::Coin<0x2::myso::MYSO>";
+        for pattern in [
+            "*",
+            "0x2",
+            "0x2::*",
+            "0x2::coin",
+            "0x2::coin::*",
+            "0x2::*::Coin",
+            "0x2::coin::Coin",
+            "0x2::coin::Coin<*>",
+            "0x2::coin::Coin<0x2>",
+            "0x2::coin::Coin<0x2::myso::MYSO>",
+            "0x0000000000000000000000000000000000000000000000000000000000000002::coin::Coin<*>",
+        ] {
+            assert!(matches(pattern, coin), "{pattern} should match {coin}");
+        }
+
+        for pattern in [
+            "u64",
+            "vector<*>",
+            "0x3",
+            "0x2::balance",
+            "0x2::coin::TreasuryCap",
+            "0x2::coin::Coin<*, *>",
+            "0x2::coin::Coin<u64>",
+            "0x2::coin::Coin<0x2::myso::MYSO<*>>",
+        ] {
+            assert!(!matches(pattern, coin), "{pattern} should not match {coin}");
+        }
+
+        assert!(matches("vector<u8>", "vector<u8>"));
+        assert!(matches("vector<*>", "vector<vector<u8>>"));
+        assert!(!matches("vector<u64>", "vector<u8>"));
+
+        let pattern = "0x2::coin::*".parse::<TypePattern>().unwrap();
+        assert!(pattern.matches_struct(&StructTag::gas_coin()));
+        assert!(!pattern.matches_struct(&StructTag::myso()));
+        assert!(!TypePattern::U64.matches_struct(&StructTag::myso()));
+
+        let exact = TypePattern::from(TypeTag::from(StructTag::gas_coin()));
+        assert!(exact.matches(&StructTag::gas_coin().into()));
+        assert!(!exact.matches(&StructTag::coin(TypeTag::U64).into()));
+    }
+
+    #[test]
+    fn parsing() {
+        for (pattern, expected) in [
+            ("*", "*"),
+            ("vector<*>", "vector<*>"),
+            (
+                "0x2",
+                "0x0000000000000000000000000000000000000000000000000000000000000002",
+            ),
+            (
+                "0x2::*",
+                "0x0000000000000000000000000000000000000000000000000000000000000002",
+            ),
+            (
+                "0x2::coin::*",
+                "0x0000000000000000000000000000000000000000000000000000000000000002::coin",
+            ),
+            (
+                "0x2::*::Coin<*,u64>",
+                "0x0000000000000000000000000000000000000000000000000000000000000002::*::Coin<*, u64>",
+            ),
+        ] {
+            let parsed = pattern.parse::<TypePattern>().unwrap();
+            assert_eq!(parsed.to_string(), expected);
+            assert_eq!(expected.parse::<TypePattern>().unwrap(), parsed);
+        }
+
+        for pattern in [
+            "",
+            "**",
+            "0x2::",
+            "0x2::coin::Coin<>",
+            "coin::Coin",
+            "vector<*",
+        ] {
+            pattern.parse::<TypePattern>().unwrap_err();
+        }
+
+        "0x2::coin::Coin<*>".parse::<StructPattern>().unwrap();
+        "u64".parse::<StructPattern>().unwrap_err();
+    }
+}
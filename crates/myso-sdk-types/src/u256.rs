@@ -1,11 +1,245 @@
-// Before we can expose this in the public interface it likely needs to be wrapped so that the type
-// from our dependency doesn't leak
-pub(crate) type U256 = bnum::BUintD8<32>;
+/// The backing storage of [`U256`], kept private so that the type from our dependency doesn't leak
+/// into the public interface.
+pub(crate) type Uint = bnum::BUintD8<32>;
+
+/// An unsigned 256-bit integer, the type of Move's `u256`.
+///
+/// # BCS
+///
+/// A `U256`'s BCS serialized form is its 32 little-endian bytes:
+///
+/// ```text
+/// u256 = 32OCTET
+/// ```
+///
+/// In human-readable formats, like JSON, a `U256` is serialized as a decimal string.
+#[derive(Clone, Copy, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct U256(Uint);
+
+impl U256 {
+    pub const LENGTH: usize = 32;
+    pub const ZERO: Self = Self(Uint::ZERO);
+    pub const ONE: Self = Self(Uint::ONE);
+    pub const MAX: Self = Self(Uint::MAX);
+
+    pub const fn from_le_bytes(bytes: [u8; Self::LENGTH]) -> Self {
+        Self(Uint::from_le(Uint::from_digits(bytes)))
+    }
+
+    pub const fn from_be_bytes(bytes: [u8; Self::LENGTH]) -> Self {
+        Self(Uint::from_be(Uint::from_digits(bytes)))
+    }
+
+    pub const fn to_le_bytes(self) -> [u8; Self::LENGTH] {
+        *self.0.to_le().digits()
+    }
+
+    pub const fn to_be_bytes(self) -> [u8; Self::LENGTH] {
+        *self.0.to_be().digits()
+    }
+
+    pub const fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    /// Parse a `U256` from a string in the provided radix, which must be between 2 and 36.
+    pub fn from_str_radix(s: &str, radix: u32) -> Result<Self, U256ParseError> {
+        Uint::from_str_radix(s, radix)
+            .map(Self)
+            .map_err(U256ParseError)
+    }
+
+    /// Format the `U256` as a string in the provided radix, which must be between 2 and 36.
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        self.0.to_str_radix(radix)
+    }
+
+    pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_add(rhs.0) {
+            Some(value) => Some(Self(value)),
+            None => None,
+        }
+    }
+
+    pub const fn checked_sub(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_sub(rhs.0) {
+            Some(value) => Some(Self(value)),
+            None => None,
+        }
+    }
+
+    pub const fn checked_mul(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_mul(rhs.0) {
+            Some(value) => Some(Self(value)),
+            None => None,
+        }
+    }
+
+    /// Returns `None` if `rhs` is zero.
+    pub const fn checked_div(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_div(rhs.0) {
+            Some(value) => Some(Self(value)),
+            None => None,
+        }
+    }
+
+    /// Returns `None` if `rhs` is zero.
+    pub const fn checked_rem(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_rem(rhs.0) {
+            Some(value) => Some(Self(value)),
+            None => None,
+        }
+    }
+
+    pub const fn checked_pow(self, exp: u32) -> Option<Self> {
+        match self.0.checked_pow(exp) {
+            Some(value) => Some(Self(value)),
+            None => None,
+        }
+    }
+
+    pub const fn saturating_add(self, rhs: Self) -> Self {
+        Self(self.0.saturating_add(rhs.0))
+    }
+
+    pub const fn saturating_sub(self, rhs: Self) -> Self {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+
+    pub const fn saturating_mul(self, rhs: Self) -> Self {
+        Self(self.0.saturating_mul(rhs.0))
+    }
+}
+
+/// Parses either a decimal string or a `0x` prefixed hex string.
+impl std::str::FromStr for U256 {
+    type Err = U256ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_prefix("0x") {
+            Some(hex) => Self::from_str_radix(hex, 16),
+            None => Self::from_str_radix(s, 10),
+        }
+    }
+}
+
+impl std::fmt::Display for U256 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl std::fmt::Debug for U256 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("U256")
+            .field(&format_args!("{self}"))
+            .finish()
+    }
+}
+
+impl std::fmt::LowerHex for U256 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::LowerHex::fmt(&self.0, f)
+    }
+}
+
+impl std::fmt::UpperHex for U256 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::UpperHex::fmt(&self.0, f)
+    }
+}
+
+macro_rules! impl_from_primitive {
+    ($($primitive:ty),*) => {
+        $(
+            impl From<$primitive> for U256 {
+                fn from(value: $primitive) -> Self {
+                    Self(Uint::from(value))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_primitive!(u8, u16, u32, u64, u128);
+
+macro_rules! impl_try_into_primitive {
+    ($($primitive:ty),*) => {
+        $(
+            impl TryFrom<U256> for $primitive {
+                type Error = TryFromU256Error;
+
+                fn try_from(value: U256) -> Result<Self, Self::Error> {
+                    <$primitive>::try_from(value.0).map_err(|_| TryFromU256Error(()))
+                }
+            }
+        )*
+    };
+}
+
+impl_try_into_primitive!(u8, u16, u32, u64, u128);
+
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+impl serde::Serialize for U256 {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde_with::SerializeAs;
+
+        if serializer.is_human_readable() {
+            serde_with::DisplayFromStr::serialize_as(self, serializer)
+        } else {
+            self.to_le_bytes().serialize(serializer)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+impl<'de> serde::Deserialize<'de> for U256 {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde_with::DeserializeAs;
+
+        if deserializer.is_human_readable() {
+            serde_with::DisplayFromStr::deserialize_as(deserializer)
+        } else {
+            <[u8; Self::LENGTH]>::deserialize(deserializer).map(Self::from_le_bytes)
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct U256ParseError(bnum::errors::ParseIntError);
+
+impl std::fmt::Display for U256ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unable to parse U256: {}", self.0)
+    }
+}
+
+impl std::error::Error for U256ParseError {}
+
+/// The error returned when a [`U256`] doesn't fit in a smaller integer type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TryFromU256Error(());
+
+impl std::fmt::Display for TryFromU256Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("U256 value is out of range of the target type")
+    }
+}
+
+impl std::error::Error for TryFromU256Error {}
 
 // This is a constant time assert to ensure that the backing storage for U256 is 32 bytes long
 #[allow(unused)]
 const ASSERT_32_BYTES: () = {
-    let u256 = U256::ZERO;
+    let u256 = Uint::ZERO;
 
     let _digits: &[u8; 32] = u256.digits();
 };
@@ -27,7 +261,7 @@ const ASSERT_ENDIANNESS: () = {
         true
     }
 
-    let one_platform = U256::ONE;
+    let one_platform = Uint::ONE;
     let one_le = {
         let mut buf = [0; 32];
         buf[0] = 1;
@@ -51,13 +285,13 @@ const ASSERT_ENDIANNESS: () = {
     // From little endian
     assert!(const_bytes_equal(
         one_platform.digits().as_slice(),
-        U256::from_le(U256::from_digits(one_le)).digits().as_slice()
+        Uint::from_le(Uint::from_digits(one_le)).digits().as_slice()
     ));
 
     // From big endian
     assert!(const_bytes_equal(
         one_platform.digits().as_slice(),
-        U256::from_be(U256::from_digits(one_be)).digits().as_slice()
+        Uint::from_be(Uint::from_digits(one_be)).digits().as_slice()
     ));
 };
 
@@ -74,7 +308,7 @@ mod test {
 
     #[test]
     fn endianness() {
-        let one_platform = U256::ONE;
+        let one_platform = Uint::ONE;
         let one_le = {
             let mut buf = [0; 32];
             buf[0] = 1;
@@ -96,9 +330,9 @@ mod test {
         assert_eq!(one_be, *be.digits());
 
         // From little endian
-        assert_eq!(one_platform, U256::from_le(U256::from_digits(one_le)));
+        assert_eq!(one_platform, Uint::from_le(Uint::from_digits(one_le)));
         // From big endian
-        assert_eq!(one_platform, U256::from_be(U256::from_digits(one_be)));
+        assert_eq!(one_platform, Uint::from_be(Uint::from_digits(one_be)));
     }
 
     #[proptest]
@@ -127,4 +361,67 @@ mod test {
         assert_eq!(from_str, u256);
         assert_eq!(radix10, from_str.to_string());
     }
+
+    #[test]
+    fn arithmetic() {
+        let two = U256::from(2u8);
+        assert_eq!(U256::ONE.checked_add(U256::ONE), Some(two));
+        assert_eq!(U256::MAX.checked_add(U256::ONE), None);
+        assert_eq!(U256::ZERO.checked_sub(U256::ONE), None);
+        assert_eq!(U256::MAX.checked_mul(two), None);
+        assert_eq!(U256::ONE.checked_div(U256::ZERO), None);
+        assert_eq!(U256::from(7u8).checked_rem(two), Some(U256::ONE));
+        assert_eq!(
+            two.checked_pow(255),
+            Some(U256::from_be_bytes({
+                let mut bytes = [0; 32];
+                bytes[0] = 0x80;
+                bytes
+            }))
+        );
+        assert_eq!(two.checked_pow(256), None);
+        assert_eq!(U256::MAX.saturating_add(U256::ONE), U256::MAX);
+        assert_eq!(U256::ZERO.saturating_sub(U256::ONE), U256::ZERO);
+    }
+
+    #[test]
+    fn parsing_and_conversions() {
+        let value = U256::from(u128::MAX).checked_add(U256::ONE).unwrap();
+        assert_eq!(value.to_string(), "340282366920938463463374607431768211456");
+        assert_eq!(
+            U256::from_str("0x100000000000000000000000000000000").unwrap(),
+            value
+        );
+        assert_eq!(format!("{value:x}"), "100000000000000000000000000000000");
+        U256::from_str("0x").unwrap_err();
+        U256::from_str("-1").unwrap_err();
+        U256::from_str(&format!("{}0", U256::MAX)).unwrap_err();
+
+        assert_eq!(u128::try_from(U256::from(u128::MAX)), Ok(u128::MAX));
+        u128::try_from(value).unwrap_err();
+        u64::try_from(U256::from(u64::MAX as u128 + 1)).unwrap_err();
+
+        let mut le = [0; 32];
+        le[16] = 1;
+        assert_eq!(value.to_le_bytes(), le);
+        assert_eq!(U256::from_le_bytes(le), value);
+        le.reverse();
+        assert_eq!(value.to_be_bytes(), le);
+        assert_eq!(U256::from_be_bytes(le), value);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let value = U256::from(0x0102u16);
+        let mut bytes = [0; 32];
+        bytes[0] = 2;
+        bytes[1] = 1;
+        assert_eq!(bcs::to_bytes(&value).unwrap(), bytes);
+        assert_eq!(bcs::from_bytes::<U256>(&bytes).unwrap(), value);
+
+        assert_eq!(serde_json::to_string(&value).unwrap(), "\"258\"");
+        assert_eq!(serde_json::from_str::<U256>("\"258\"").unwrap(), value);
+        assert_eq!(serde_json::from_str::<U256>("\"0x102\"").unwrap(), value);
+    }
 }
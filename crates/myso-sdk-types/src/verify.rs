@@ -0,0 +1,346 @@
+//! Checking that objects and effects received from an untrusted source are consistent with the
+//! digests which commit to them.
+//!
+//! An [`Object`] is committed to by the [`ObjectReference`] of its version in the effects of the
+//! transaction which wrote it, while [`TransactionEffects`] are committed to by the
+//! [`CheckpointTransactionInfo`] of the checkpoint which includes the transaction. Recomputing
+//! the digests and comparing them against these commitments ensures that a node hasn't tampered
+//! with the data it served.
+
+use crate::Address;
+use crate::CheckpointTransactionInfo;
+use crate::Digest;
+use crate::Object;
+use crate::ObjectOut;
+use crate::ObjectReference;
+use crate::TransactionEffects;
+use crate::Version;
+
+/// The reference to the version of an object written by a transaction, if the transaction wrote
+/// the object.
+///
+/// Objects which were deleted or wrapped by the transaction have no written version.
+pub fn written_object_reference(
+    effects: &TransactionEffects,
+    object_id: &Address,
+) -> Option<ObjectReference> {
+    match effects {
+        TransactionEffects::V1(effects) => effects
+            .created
+            .iter()
+            .chain(&effects.mutated)
+            .chain(&effects.unwrapped)
+            .chain(std::iter::once(&effects.gas_object))
+            .map(|object| &object.reference)
+            .find(|reference| reference.object_id() == object_id)
+            .cloned(),
+        TransactionEffects::V2(effects) => {
+            let changed = effects
+                .changed_objects
+                .iter()
+                .find(|changed| &changed.object_id == object_id)?;
+            match &changed.output_state {
+                ObjectOut::ObjectWrite { digest, .. } => Some(ObjectReference::new(
+                    *object_id,
+                    effects.lamport_version,
+                    *digest,
+                )),
+                ObjectOut::PackageWrite { version, digest } => {
+                    Some(ObjectReference::new(*object_id, *version, *digest))
+                }
+                ObjectOut::NotExist | ObjectOut::AccumulatorWrite(_) => None,
+            }
+        }
+    }
+}
+
+/// Verify that an object matches a reference to it.
+pub fn verify_object_reference(
+    object: &Object,
+    reference: &ObjectReference,
+) -> Result<(), VerificationError> {
+    let object_id = object.object_id();
+    if &object_id != reference.object_id() {
+        return Err(VerificationError::ObjectIdMismatch {
+            expected: *reference.object_id(),
+            actual: object_id,
+        });
+    }
+
+    if object.version() != reference.version() {
+        return Err(VerificationError::ObjectVersionMismatch {
+            object_id,
+            expected: reference.version(),
+            actual: object.version(),
+        });
+    }
+
+    let digest = object.digest();
+    if &digest != reference.digest() {
+        return Err(VerificationError::ObjectDigestMismatch {
+            object_id,
+            expected: *reference.digest(),
+            actual: digest,
+        });
+    }
+
+    Ok(())
+}
+
+/// Verify that an object is the version of the object written by a transaction.
+pub fn verify_object(
+    object: &Object,
+    effects: &TransactionEffects,
+) -> Result<(), VerificationError> {
+    let object_id = object.object_id();
+    let reference = written_object_reference(effects, &object_id)
+        .ok_or(VerificationError::ObjectNotWritten { object_id })?;
+    verify_object_reference(object, &reference)
+}
+
+/// Verify that effects have the expected digest.
+pub fn verify_effects_digest(
+    effects: &TransactionEffects,
+    expected: &Digest,
+) -> Result<(), VerificationError> {
+    let digest = effects.digest();
+    if &digest != expected {
+        return Err(VerificationError::EffectsDigestMismatch {
+            expected: *expected,
+            actual: digest,
+        });
+    }
+
+    Ok(())
+}
+
+/// Verify that effects are the effects of a transaction included in a checkpoint.
+///
+/// Both the digest of the effects and the digest of the transaction they are the effects of must
+/// match the ones committed to by the checkpoint.
+pub fn verify_effects(
+    effects: &TransactionEffects,
+    info: &CheckpointTransactionInfo,
+) -> Result<(), VerificationError> {
+    let transaction = match effects {
+        TransactionEffects::V1(effects) => &effects.transaction_digest,
+        TransactionEffects::V2(effects) => &effects.transaction_digest,
+    };
+    if transaction != info.transaction() {
+        return Err(VerificationError::TransactionDigestMismatch {
+            expected: *info.transaction(),
+            actual: *transaction,
+        });
+    }
+
+    verify_effects_digest(effects, info.effects())
+}
+
+/// An inconsistency between an object or effects and the digest committing to them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum VerificationError {
+    /// The object's ID differs from the ID in the reference.
+    ObjectIdMismatch { expected: Address, actual: Address },
+    /// The object's version differs from the version in the reference.
+    ObjectVersionMismatch {
+        object_id: Address,
+        expected: Version,
+        actual: Version,
+    },
+    /// The object's digest differs from the digest in the reference.
+    ObjectDigestMismatch {
+        object_id: Address,
+        expected: Digest,
+        actual: Digest,
+    },
+    /// The object wasn't written by the transaction.
+    ObjectNotWritten { object_id: Address },
+    /// The digest of the effects differs from the expected digest.
+    EffectsDigestMismatch { expected: Digest, actual: Digest },
+    /// The effects are for a different transaction than expected.
+    TransactionDigestMismatch { expected: Digest, actual: Digest },
+}
+
+impl std::fmt::Display for VerificationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VerificationError::ObjectIdMismatch { expected, actual } => {
+                write!(f, "expected object {expected}, found object {actual}")
+            }
+            VerificationError::ObjectVersionMismatch {
+                object_id,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "expected version {expected} of object {object_id}, found version {actual}"
+            ),
+            VerificationError::ObjectDigestMismatch {
+                object_id,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "expected object {object_id} to have digest {expected}, computed digest {actual}"
+            ),
+            VerificationError::ObjectNotWritten { object_id } => {
+                write!(f, "object {object_id} was not written by the transaction")
+            }
+            VerificationError::EffectsDigestMismatch { expected, actual } => write!(
+                f,
+                "expected effects digest {expected}, computed digest {actual}"
+            ),
+            VerificationError::TransactionDigestMismatch { expected, actual } => write!(
+                f,
+                "expected effects of transaction {expected}, found effects of transaction {actual}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for VerificationError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ChangedObject;
+    use crate::ExecutionStatus;
+    use crate::GasCostSummary;
+    use crate::IdOperation;
+    use crate::MoveStruct;
+    use crate::ObjectData;
+    use crate::ObjectIn;
+    use crate::Owner;
+    use crate::StructTag;
+    use crate::TransactionEffectsV2;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    const TRANSACTION: Digest = Digest::new([7; 32]);
+
+    fn coin(id: Address, version: Version, balance: u64) -> Object {
+        let mut contents = id.into_inner().to_vec();
+        contents.extend(balance.to_le_bytes());
+        Object::new(
+            ObjectData::Struct(
+                MoveStruct::new(StructTag::gas_coin(), true, version, contents).unwrap(),
+            ),
+            Owner::Address(Address::TWO),
+            TRANSACTION,
+            0,
+        )
+    }
+
+    fn effects(changed_objects: Vec<ChangedObject>) -> TransactionEffects {
+        TransactionEffects::V2(Box::new(TransactionEffectsV2 {
+            status: ExecutionStatus::Success,
+            epoch: 1,
+            gas_used: GasCostSummary::new(0, 0, 0, 0),
+            transaction_digest: TRANSACTION,
+            gas_object_index: None,
+            events_digest: None,
+            dependencies: vec![],
+            lamport_version: 5,
+            changed_objects,
+            unchanged_consensus_objects: vec![],
+            auxiliary_data_digest: None,
+        }))
+    }
+
+    #[test]
+    fn objects() {
+        let id = Address::from_static("0x1234");
+        let deleted = Address::from_static("0x5678");
+        let object = coin(id, 5, 100);
+        let effects = effects(vec![
+            ChangedObject {
+                object_id: id,
+                input_state: ObjectIn::NotExist,
+                output_state: ObjectOut::ObjectWrite {
+                    digest: object.digest(),
+                    owner: Owner::Address(Address::TWO),
+                },
+                id_operation: IdOperation::Created,
+            },
+            ChangedObject {
+                object_id: deleted,
+                input_state: ObjectIn::Exist {
+                    version: 3,
+                    digest: Digest::ZERO,
+                    owner: Owner::Address(Address::TWO),
+                },
+                output_state: ObjectOut::NotExist,
+                id_operation: IdOperation::Deleted,
+            },
+        ]);
+
+        assert_eq!(
+            written_object_reference(&effects, &id),
+            Some(ObjectReference::new(id, 5, object.digest()))
+        );
+        verify_object(&object, &effects).unwrap();
+
+        let tampered = coin(id, 5, 1000);
+        assert_eq!(
+            verify_object(&tampered, &effects),
+            Err(VerificationError::ObjectDigestMismatch {
+                object_id: id,
+                expected: object.digest(),
+                actual: tampered.digest(),
+            })
+        );
+        assert_eq!(
+            verify_object(&coin(id, 4, 100), &effects),
+            Err(VerificationError::ObjectVersionMismatch {
+                object_id: id,
+                expected: 5,
+                actual: 4,
+            })
+        );
+        assert_eq!(
+            verify_object(&coin(deleted, 5, 100), &effects),
+            Err(VerificationError::ObjectNotWritten { object_id: deleted })
+        );
+        assert_eq!(
+            verify_object_reference(&object, &ObjectReference::new(deleted, 5, object.digest())),
+            Err(VerificationError::ObjectIdMismatch {
+                expected: deleted,
+                actual: id,
+            })
+        );
+    }
+
+    #[test]
+    fn effects_digest() {
+        let effects = effects(vec![]);
+        let digest = effects.digest();
+
+        verify_effects(
+            &effects,
+            &CheckpointTransactionInfo::new(TRANSACTION, digest, vec![]),
+        )
+        .unwrap();
+        assert_eq!(
+            verify_effects(
+                &effects,
+                &CheckpointTransactionInfo::new(TRANSACTION, Digest::ZERO, vec![]),
+            ),
+            Err(VerificationError::EffectsDigestMismatch {
+                expected: Digest::ZERO,
+                actual: digest,
+            })
+        );
+        assert_eq!(
+            verify_effects(
+                &effects,
+                &CheckpointTransactionInfo::new(Digest::ZERO, digest, vec![]),
+            ),
+            Err(VerificationError::TransactionDigestMismatch {
+                expected: Digest::ZERO,
+                actual: TRANSACTION,
+            })
+        );
+    }
+}
@@ -0,0 +1,524 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::Argument;
+use crate::ObjectInput;
+use crate::TransactionBuilder;
+use crate::builder::CommandKind;
+use crate::builder::InputArg;
+use crate::error::Error;
+use crate::limits::Limit;
+use crate::limits::TransactionLimits;
+use myso_sdk_types::Address;
+use myso_sdk_types::ObjectOut;
+use myso_sdk_types::TransactionEffects;
+use std::collections::VecDeque;
+
+/// Commands reserved in each transaction for resolving intents, e.g. merging and splitting coins.
+const RESERVED_COMMANDS: usize = 32;
+/// Object inputs reserved in each transaction for coins selected when resolving intents.
+const RESERVED_INPUT_OBJECTS: usize = 256;
+/// Bytes reserved in each transaction for the sender, gas payment and resolving intents.
+const RESERVED_SIZE: usize = 16 * 1024;
+
+type Operation = Box<dyn Fn(&mut TransactionBuilder) + Send + Sync>;
+
+/// A list of operations to be split across as few transactions as possible.
+///
+/// Each operation adds some commands to a [`TransactionBuilder`] and operations are packed, in
+/// order, into transactions until adding another would exceed the transaction's
+/// [`TransactionLimits`]. An operation is never split across transactions, so operations should
+/// be independent of each other.
+///
+/// Since the commands and coins needed to resolve intents, such as a
+/// [`CoinWithBalance`](crate::intent::CoinWithBalance), aren't known until a transaction is built
+/// a fixed number of commands, object inputs and bytes are kept in reserve in every transaction
+/// for resolving them.
+///
+/// ```
+/// use myso_sdk_types::Address;
+/// use myso_transaction_builder::TransactionBatch;
+///
+/// let recipient = Address::from_static("0xa11ce");
+///
+/// let mut batch = TransactionBatch::new();
+/// for amount in 1..=5000u64 {
+///     batch.add(move |tx| {
+///         let amount = tx.pure(&amount);
+///         let coin = tx.gas();
+///         let coins = tx.split_coins(coin, vec![amount]);
+///         let recipient = tx.pure(&recipient);
+///         tx.transfer_objects(coins, recipient);
+///     });
+/// }
+///
+/// let transactions = batch.split().unwrap();
+/// assert!(transactions.len() > 1);
+/// ```
+#[derive(Default)]
+pub struct TransactionBatch {
+    operations: Vec<Operation>,
+    limits: TransactionLimits,
+}
+
+impl TransactionBatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the limits transactions are packed to. Defaults to [`TransactionLimits::default`].
+    pub fn with_limits(self, limits: TransactionLimits) -> Self {
+        Self { limits, ..self }
+    }
+
+    /// Add an operation which adds commands to a transaction.
+    ///
+    /// The operation may be invoked more than once, each time on a different builder, when it
+    /// doesn't fit into the transaction it was first tried in.
+    pub fn add<F>(&mut self, operation: F)
+    where
+        F: Fn(&mut TransactionBuilder) + Send + Sync + 'static,
+    {
+        self.operations.push(Box::new(operation));
+    }
+
+    /// Add a transfer of a coin with the provided balance to `recipient`.
+    #[cfg(feature = "intents")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "intents")))]
+    pub fn transfer(&mut self, recipient: Address, coin: crate::intent::CoinWithBalance) {
+        self.add(move |tx| {
+            let coin = tx.intent(coin.clone());
+            let recipient = tx.pure(&recipient);
+            tx.transfer_objects(vec![coin], recipient);
+        });
+    }
+
+    /// The number of operations in the batch.
+    pub fn len(&self) -> usize {
+        self.operations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    /// Pack the operations into as few transactions as possible.
+    ///
+    /// Returns an error if a single operation doesn't fit into a transaction on its own.
+    pub fn split(self) -> Result<BatchedTransactions, Error> {
+        let mut transactions = VecDeque::new();
+        let mut current = TransactionBuilder::new();
+        let mut operations_in_current = 0;
+
+        for operation in &self.operations {
+            let checkpoint = current.checkpoint();
+            operation(&mut current);
+            let Err(error) = self.fits(&current) else {
+                operations_in_current += 1;
+                continue;
+            };
+
+            // The operation doesn't fit, so undo it and start a new transaction with it, unless
+            // it was the only operation in the transaction
+            if operations_in_current == 0 {
+                return Err(error);
+            }
+            current.rollback(checkpoint);
+            transactions.push_back(std::mem::take(&mut current));
+
+            operation(&mut current);
+            self.fits(&current)?;
+            operations_in_current = 1;
+        }
+
+        if operations_in_current > 0 {
+            transactions.push_back(current);
+        }
+
+        Ok(BatchedTransactions {
+            transactions,
+            limits: self.limits,
+            sender: None,
+            sponsor: None,
+            gas: Vec::new(),
+            gas_budget: None,
+            gas_price: None,
+        })
+    }
+
+    /// Check that a transaction, with room reserved for resolving it, is within the limits.
+    fn fits(&self, builder: &TransactionBuilder) -> Result<(), Error> {
+        let check = |limit: Limit, actual: usize, max: usize| {
+            if actual > max {
+                Err(Error::LimitExceeded { limit, actual, max })
+            } else {
+                Ok(())
+            }
+        };
+
+        // Every intent is assumed to resolve to at least one command
+        check(
+            Limit::Commands,
+            builder.commands.len() + builder.intents.len() + RESERVED_COMMANDS,
+            self.limits.max_commands,
+        )?;
+
+        let input_objects = builder
+            .inputs
+            .values()
+            .filter(|(_, input)| matches!(input, InputArg::Object(_)))
+            .count();
+        check(
+            Limit::InputObjects,
+            input_objects + RESERVED_INPUT_OBJECTS,
+            self.limits.max_input_objects,
+        )?;
+
+        check(
+            Limit::TransactionSize,
+            estimated_size(builder) + RESERVED_SIZE,
+            self.limits.max_tx_size_bytes,
+        )
+    }
+}
+
+/// An upper bound on the BCS serialized size of the inputs and commands of a transaction.
+fn estimated_size(builder: &TransactionBuilder) -> usize {
+    // Arguments are at most 5 bytes and vector lengths at most 3 bytes for transactions within
+    // the protocol's limits
+    const ARGUMENT: usize = 5;
+    const LENGTH: usize = 3;
+    const OBJECT_REFERENCE: usize = Address::LENGTH + 8 + 1 + 32;
+
+    let arguments = |arguments: &[Argument]| LENGTH + arguments.len() * ARGUMENT;
+
+    let inputs = builder
+        .inputs
+        .values()
+        .map(|(_, input)| match input {
+            InputArg::Gas => 0,
+            InputArg::Pure(bytes) => 1 + LENGTH + bytes.len(),
+            InputArg::Object(_) => 2 + OBJECT_REFERENCE,
+            InputArg::FundsWithdrawal(withdrawal) => {
+                2 + 8 + 1 + withdrawal.coin_type().to_string().len()
+            }
+        })
+        .sum::<usize>();
+
+    let commands = builder
+        .commands
+        .values()
+        .map(|command| {
+            1 + match &command.kind {
+                CommandKind::MoveCall(call) => {
+                    Address::LENGTH
+                        + LENGTH
+                        + call.module.as_str().len()
+                        + LENGTH
+                        + call.function.as_str().len()
+                        + LENGTH
+                        + call
+                            .type_arguments
+                            .iter()
+                            .map(|type_| type_.to_string().len())
+                            .sum::<usize>()
+                        + arguments(&call.arguments)
+                }
+                CommandKind::TransferObjects(transfer) => arguments(&transfer.objects) + ARGUMENT,
+                CommandKind::SplitCoins(split) => ARGUMENT + arguments(&split.amounts),
+                CommandKind::MergeCoins(merge) => ARGUMENT + arguments(&merge.coins_to_merge),
+                CommandKind::MakeMoveVector(vector) => {
+                    1 + vector
+                        .type_
+                        .as_ref()
+                        .map(|type_| type_.to_string().len())
+                        .unwrap_or(0)
+                        + arguments(&vector.elements)
+                }
+                CommandKind::Publish(publish) => {
+                    LENGTH
+                        + publish
+                            .modules
+                            .iter()
+                            .map(|module| LENGTH + module.len())
+                            .sum::<usize>()
+                        + LENGTH
+                        + publish.dependencies.len() * Address::LENGTH
+                }
+                CommandKind::Upgrade(upgrade) => {
+                    LENGTH
+                        + upgrade
+                            .modules
+                            .iter()
+                            .map(|module| LENGTH + module.len())
+                            .sum::<usize>()
+                        + LENGTH
+                        + upgrade.dependencies.len() * Address::LENGTH
+                        + Address::LENGTH
+                        + ARGUMENT
+                }
+            }
+        })
+        .sum::<usize>();
+
+    // Every intent is assumed to resolve to a command splitting off a single coin
+    let intents = builder.intents.len() * (1 + 2 * ARGUMENT + LENGTH + 1 + LENGTH + 8);
+
+    LENGTH + inputs + LENGTH + commands + intents
+}
+
+/// The transactions produced by splitting a [`TransactionBatch`], to be built and executed one
+/// after another.
+///
+/// The gas objects used to pay for the transactions are chained between them: after executing
+/// a transaction, passing its effects to [`BatchedTransactions::update_gas`] updates the gas
+/// payment of the following transactions to the new version of the gas coin. Since all gas
+/// objects of a transaction are merged into the first one during execution, only the first gas
+/// object is used after the first transaction.
+pub struct BatchedTransactions {
+    transactions: VecDeque<TransactionBuilder>,
+    limits: TransactionLimits,
+    sender: Option<Address>,
+    sponsor: Option<Address>,
+    gas: Vec<ObjectInput>,
+    gas_budget: Option<u64>,
+    gas_price: Option<u64>,
+}
+
+impl BatchedTransactions {
+    /// The number of transactions remaining.
+    pub fn len(&self) -> usize {
+        self.transactions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.transactions.is_empty()
+    }
+
+    /// Set the sender of every transaction.
+    pub fn set_sender(&mut self, sender: Address) {
+        self.sender = Some(sender);
+    }
+
+    /// Set the sponsor of every transaction.
+    pub fn set_sponsor(&mut self, sponsor: Address) {
+        self.sponsor = Some(sponsor);
+    }
+
+    /// Set the gas objects used to pay for the next transaction.
+    pub fn set_gas_objects<O, I>(&mut self, gas: I)
+    where
+        O: Into<ObjectInput>,
+        I: IntoIterator<Item = O>,
+    {
+        self.gas = gas.into_iter().map(Into::into).collect();
+    }
+
+    /// Set the gas budget of every transaction.
+    pub fn set_gas_budget(&mut self, budget: u64) {
+        self.gas_budget = Some(budget);
+    }
+
+    /// Set the gas price of every transaction.
+    pub fn set_gas_price(&mut self, price: u64) {
+        self.gas_price = Some(price);
+    }
+
+    /// Take the next transaction, configured with the sender, sponsor, gas objects, budget and
+    /// price which have been set.
+    ///
+    /// Transactions which pay for gas with a coin owned by the sender should be executed before
+    /// the following transaction is taken, with its effects passed to
+    /// [`BatchedTransactions::update_gas`].
+    pub fn next_transaction(&mut self) -> Option<TransactionBuilder> {
+        let mut builder = self.transactions.pop_front()?;
+        builder.set_limits(self.limits.clone());
+        if let Some(sender) = self.sender {
+            builder.set_sender(sender);
+        }
+        if let Some(sponsor) = self.sponsor {
+            builder.set_sponsor(sponsor);
+        }
+        builder.add_gas_objects(self.gas.iter().cloned());
+        if let Some(budget) = self.gas_budget {
+            builder.set_gas_budget(budget);
+        }
+        if let Some(price) = self.gas_price {
+            builder.set_gas_price(price);
+        }
+        Some(builder)
+    }
+
+    /// Update the gas payment of the following transactions to use the new version of the gas
+    /// coin written by an executed transaction.
+    pub fn update_gas(&mut self, effects: &TransactionEffects) -> Result<(), Error> {
+        let (object_id, version, digest) = match effects {
+            TransactionEffects::V1(effects) => effects.gas_object.reference.clone().into_parts(),
+            TransactionEffects::V2(effects) => {
+                let gas_object = effects
+                    .gas_object_index
+                    .and_then(|index| effects.changed_objects.get(index as usize))
+                    .ok_or_else(|| Error::Input("effects are missing a gas object".to_owned()))?;
+                let ObjectOut::ObjectWrite { digest, .. } = gas_object.output_state else {
+                    return Err(Error::Input("gas object was not written".to_owned()));
+                };
+                (gas_object.object_id, effects.lamport_version, digest)
+            }
+        };
+
+        self.gas = vec![ObjectInput::owned(object_id, version, digest)];
+        Ok(())
+    }
+
+    /// Take all of the remaining transactions, without configuring them.
+    pub fn into_transactions(self) -> Vec<TransactionBuilder> {
+        self.transactions.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::InputArgKind;
+    use myso_sdk_types::Digest;
+    use myso_sdk_types::Identifier;
+    use myso_sdk_types::TransactionKind;
+
+    fn address(i: u64) -> Address {
+        let mut bytes = [0; Address::LENGTH];
+        bytes[Address::LENGTH - 8..].copy_from_slice(&i.to_be_bytes());
+        Address::new(bytes)
+    }
+
+    fn transfer_batch(count: u64) -> TransactionBatch {
+        let mut batch = TransactionBatch::new();
+        for i in 0..count {
+            batch.add(move |tx| {
+                let coin = tx.object(ObjectInput::owned(
+                    address(i + 1),
+                    1,
+                    Digest::from_static("7opR9rFUYivSTqoJHvFb9p6p54THyHTatMG6id4JKZR9"),
+                ));
+                let recipient = tx.pure(&Address::TWO);
+                tx.transfer_objects(vec![coin], recipient);
+            });
+        }
+        batch
+    }
+
+    #[test]
+    fn split_batch() {
+        let gas = ObjectInput::owned(
+            Address::from_static("0xd879"),
+            2,
+            Digest::from_static("2ZigdvsZn5BMeszscPQZq9z8ebnS2FpmAuRbAi9ednCk"),
+        );
+
+        // Limited by the size of the transactions
+        let mut transactions = transfer_batch(5000).split().unwrap();
+        assert!(transactions.len() > 1);
+
+        transactions.set_sender(Address::from_static("0xc574"));
+        transactions.set_gas_objects([gas]);
+        transactions.set_gas_budget(500000000);
+        transactions.set_gas_price(1000);
+
+        let mut transferred = 0;
+        while let Some(tx) = transactions.next_transaction() {
+            let transaction = tx.try_build().unwrap();
+            let TransactionKind::ProgrammableTransaction(ptb) = &transaction.kind else {
+                panic!("expected a programmable transaction");
+            };
+            transferred += ptb.commands.len();
+            assert!(
+                bcs::to_bytes(&transaction).unwrap().len() + RESERVED_SIZE
+                    <= TransactionLimits::default().max_tx_size_bytes
+            );
+        }
+        assert_eq!(transferred, 5000);
+
+        // Limited by the number of input objects
+        let limits = TransactionLimits {
+            max_tx_size_bytes: usize::MAX,
+            max_commands: usize::MAX,
+            ..Default::default()
+        };
+        let transactions = transfer_batch(5000).with_limits(limits).split().unwrap();
+        let per_transaction =
+            TransactionLimits::default().max_input_objects - RESERVED_INPUT_OBJECTS;
+        assert_eq!(transactions.len(), 5000usize.div_ceil(per_transaction));
+
+        // Limited by the number of commands
+        let limits = TransactionLimits {
+            max_commands: RESERVED_COMMANDS + 10,
+            ..Default::default()
+        };
+        let transactions = transfer_batch(25).with_limits(limits).split().unwrap();
+        let sizes = transactions
+            .into_transactions()
+            .iter()
+            .map(|tx| tx.commands.len())
+            .collect::<Vec<_>>();
+        assert_eq!(sizes, [10, 10, 5]);
+
+        // A single operation which doesn't fit
+        let limits = TransactionLimits {
+            max_commands: RESERVED_COMMANDS,
+            ..Default::default()
+        };
+        assert!(matches!(
+            transfer_batch(1).with_limits(limits).split(),
+            Err(Error::LimitExceeded {
+                limit: Limit::Commands,
+                ..
+            })
+        ));
+
+        assert!(TransactionBatch::new().split().unwrap().is_empty());
+    }
+
+    #[test]
+    fn split_restores_reused_objects() {
+        let counter = Address::from_static("0xc0");
+        let increment = move |tx: &mut TransactionBuilder, mutable: bool, count: usize| {
+            let object = tx.object(ObjectInput::shared(counter, 1, mutable));
+            for _ in 0..count {
+                tx.move_call(
+                    crate::Function::new(
+                        Address::TWO,
+                        Identifier::from_static("counter"),
+                        Identifier::from_static("increment"),
+                    ),
+                    vec![object],
+                );
+            }
+        };
+
+        // The second operation uses the counter mutably but doesn't fit, so the counter must
+        // remain immutable in the first transaction
+        let mut batch = TransactionBatch::new();
+        batch.add(move |tx| increment(tx, false, 1));
+        batch.add(move |tx| increment(tx, true, 2));
+        let limits = TransactionLimits {
+            max_commands: RESERVED_COMMANDS + 2,
+            ..Default::default()
+        };
+
+        let mutability = batch
+            .with_limits(limits)
+            .split()
+            .unwrap()
+            .into_transactions()
+            .iter()
+            .map(|tx| {
+                let (_, InputArg::Object(object)) = &tx.inputs[&InputArgKind::ObjectInput(counter)]
+                else {
+                    panic!("expected an object input");
+                };
+                object.mutable
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(mutability, [Some(false), Some(true)]);
+    }
+}
@@ -1,13 +1,16 @@
 use crate::error::Error;
+use crate::limits::TransactionLimits;
 use std::collections::BTreeMap;
 use std::collections::BTreeSet;
 use std::collections::HashMap;
 use myso_sdk_types::Address;
 use myso_sdk_types::Digest;
+use myso_sdk_types::EpochId;
 use myso_sdk_types::Identifier;
 use myso_sdk_types::Transaction;
 use myso_sdk_types::TransactionExpiration;
 use myso_sdk_types::TypeTag;
+use myso_sdk_types::WithdrawFrom;
 
 /// A builder for creating transactions. Use `resolve` to finalize the transaction data.
 #[derive(Default)]
@@ -17,24 +20,35 @@ pub struct TransactionBuilder {
     /// method to set the gas objects.
     pub(crate) gas: Vec<ObjectInput>,
     /// The gas budget for the transaction.
-    gas_budget: Option<u64>,
+    pub(crate) gas_budget: Option<u64>,
     /// The gas price for the transaction.
-    gas_price: Option<u64>,
+    pub(crate) gas_price: Option<u64>,
     /// The sender of the transaction.
-    sender: Option<Address>,
+    pub(crate) sender: Option<Address>,
     /// The sponsor of the transaction. If None, the sender is also the sponsor.
-    sponsor: Option<Address>,
+    pub(crate) sponsor: Option<Address>,
     /// The expiration of the transaction. The default value of this type is no expiration.
-    expiration: Option<TransactionExpiration>,
+    pub(crate) expiration: Option<TransactionExpiration>,
+    /// Whether Move calls are checked against the signatures of the functions they call when
+    /// building.
+    #[cfg(feature = "intents")]
+    pub(crate) validate_move_calls: bool,
+    /// The protocol limits the transaction is checked against when building.
+    pub(crate) limits: TransactionLimits,
 
     // Resolvers
     #[cfg(feature = "intents")]
     pub(crate) resolvers: BTreeMap<std::any::TypeId, Box<dyn crate::intent::IntentResolver>>,
 
     pub(crate) arguments: BTreeMap<usize, ResolvedArgument>,
-    inputs: HashMap<InputArgKind, (usize, InputArg)>,
+    pub(crate) inputs: HashMap<InputArgKind, (usize, InputArg)>,
     pub(crate) commands: BTreeMap<usize, Command>,
     pub(crate) intents: BTreeMap<usize, Box<dyn std::any::Any + Send + Sync>>,
+
+    // Handles to the inputs and command results of a transaction loaded via `from_kind`, indexed
+    // by their position in the original transaction.
+    pub(crate) loaded_inputs: Vec<Argument>,
+    pub(crate) loaded_results: Vec<Argument>,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -45,18 +59,28 @@ pub(crate) enum ResolvedArgument {
     Resolved(myso_sdk_types::Argument),
 }
 
+/// The state of a builder which it can be returned to with [`TransactionBuilder::rollback`].
+pub(crate) struct Checkpoint {
+    /// The number of arguments in the builder.
+    len: usize,
+    /// The object inputs, which can be updated in place when an object is used again.
+    objects: Vec<ObjectInput>,
+}
+
 #[derive(Debug, PartialEq, Eq, Hash)]
 pub(crate) enum InputArgKind {
     Gas,
     ObjectInput(Address),
     PureInput(Vec<u8>),
     UniquePureInput(usize),
+    FundsWithdrawal(usize),
 }
 
 pub(crate) enum InputArg {
     Gas,
     Pure(Vec<u8>),
     Object(ObjectInput),
+    FundsWithdrawal(myso_sdk_types::FundsWithdrawal),
 }
 
 impl TransactionBuilder {
@@ -65,6 +89,169 @@ impl TransactionBuilder {
         Self::default()
     }
 
+    /// Create a builder from an existing transaction so that it can be modified and rebuilt.
+    ///
+    /// In addition to the inputs and commands loaded by [`TransactionBuilder::from_kind`], the
+    /// sender, gas payment and expiration of the transaction are loaded. If the gas owner differs
+    /// from the sender it is set as the sponsor.
+    pub fn from_transaction(transaction: &Transaction) -> Result<Self, Error> {
+        let mut builder = Self::from_kind(&transaction.kind)?;
+
+        builder.set_sender(transaction.sender);
+        if transaction.gas_payment.owner != transaction.sender {
+            builder.set_sponsor(transaction.gas_payment.owner);
+        }
+        builder.add_gas_objects(transaction.gas_payment.objects.iter().map(|object| {
+            ObjectInput::owned(*object.object_id(), object.version(), *object.digest())
+        }));
+        builder.set_gas_budget(transaction.gas_payment.budget);
+        builder.set_gas_price(transaction.gas_payment.price);
+        builder.set_expiration(transaction.expiration);
+
+        Ok(builder)
+    }
+
+    /// Create a builder from the inputs and commands of an existing programmable transaction so
+    /// that it can be modified and rebuilt.
+    ///
+    /// Handles to the loaded inputs and command results can be obtained with
+    /// [`TransactionBuilder::loaded_input`] and [`TransactionBuilder::loaded_result`] in order to
+    /// use them as arguments to newly added commands. Rebuilding without making any changes
+    /// produces the original inputs and commands.
+    pub fn from_kind(kind: &myso_sdk_types::TransactionKind) -> Result<Self, Error> {
+        let myso_sdk_types::TransactionKind::ProgrammableTransaction(ptb) = kind else {
+            return Err(Error::Input(
+                "only programmable transactions can be loaded".to_owned(),
+            ));
+        };
+
+        let mut builder = Self::new();
+
+        for input in &ptb.inputs {
+            let argument = match input {
+                myso_sdk_types::Input::Pure(value) => builder.pure_bytes_unique(value.clone()),
+                myso_sdk_types::Input::ImmutableOrOwned(object) => builder.object(
+                    ObjectInput::owned(*object.object_id(), object.version(), *object.digest()),
+                ),
+                myso_sdk_types::Input::Shared(shared) => {
+                    let mutable = match shared.mutability() {
+                        myso_sdk_types::Mutability::Immutable => false,
+                        myso_sdk_types::Mutability::Mutable => true,
+                        myso_sdk_types::Mutability::NonExclusiveWrite => {
+                            return Err(Error::SharedObjectMutability(shared.object_id()));
+                        }
+                    };
+                    builder.object(ObjectInput::shared(
+                        shared.object_id(),
+                        shared.version(),
+                        mutable,
+                    ))
+                }
+                myso_sdk_types::Input::Receiving(object) => builder.object(ObjectInput::receiving(
+                    *object.object_id(),
+                    object.version(),
+                    *object.digest(),
+                )),
+                myso_sdk_types::Input::FundsWithdrawal(withdrawal) => {
+                    builder.funds_withdrawal_input(withdrawal.clone())
+                }
+                _ => return Err(Error::Input("unsupported input kind".to_owned())),
+            };
+            builder.loaded_inputs.push(argument);
+        }
+
+        for command in &ptb.commands {
+            let kind = builder.load_command(command)?;
+            let result = builder.command(kind.into());
+            builder.loaded_results.push(result);
+        }
+
+        Ok(builder)
+    }
+
+    /// The handle to the input at `index` of the transaction this builder was loaded from.
+    pub fn loaded_input(&self, index: u16) -> Option<Argument> {
+        self.loaded_inputs.get(index as usize).copied()
+    }
+
+    /// The handle to the result of the command at `index` of the transaction this builder was
+    /// loaded from.
+    pub fn loaded_result(&self, index: u16) -> Option<Argument> {
+        self.loaded_results.get(index as usize).copied()
+    }
+
+    fn load_argument(&mut self, argument: &myso_sdk_types::Argument) -> Result<Argument, Error> {
+        let missing = || Error::Input(format!("invalid argument {argument:?}"));
+
+        match *argument {
+            myso_sdk_types::Argument::Gas => Ok(self.gas()),
+            myso_sdk_types::Argument::Input(index) => self.loaded_input(index).ok_or_else(missing),
+            myso_sdk_types::Argument::Result(index) => {
+                self.loaded_result(index).ok_or_else(missing)
+            }
+            myso_sdk_types::Argument::NestedResult(index, sub_index) => self
+                .loaded_result(index)
+                .map(|result| Argument {
+                    sub_index: Some(sub_index as usize),
+                    ..result
+                })
+                .ok_or_else(missing),
+        }
+    }
+
+    fn load_arguments(
+        &mut self,
+        arguments: &[myso_sdk_types::Argument],
+    ) -> Result<Vec<Argument>, Error> {
+        arguments
+            .iter()
+            .map(|argument| self.load_argument(argument))
+            .collect()
+    }
+
+    fn load_command(&mut self, command: &myso_sdk_types::Command) -> Result<CommandKind, Error> {
+        use myso_sdk_types::Command as C;
+
+        let kind = match command {
+            C::MoveCall(call) => CommandKind::MoveCall(MoveCall {
+                package: call.package,
+                module: call.module.clone(),
+                function: call.function.clone(),
+                type_arguments: call.type_arguments.clone(),
+                arguments: self.load_arguments(&call.arguments)?,
+            }),
+            C::TransferObjects(transfer) => CommandKind::TransferObjects(TransferObjects {
+                objects: self.load_arguments(&transfer.objects)?,
+                address: self.load_argument(&transfer.address)?,
+            }),
+            C::SplitCoins(split) => CommandKind::SplitCoins(SplitCoins {
+                coin: self.load_argument(&split.coin)?,
+                amounts: self.load_arguments(&split.amounts)?,
+            }),
+            C::MergeCoins(merge) => CommandKind::MergeCoins(MergeCoins {
+                coin: self.load_argument(&merge.coin)?,
+                coins_to_merge: self.load_arguments(&merge.coins_to_merge)?,
+            }),
+            C::Publish(publish) => CommandKind::Publish(Publish {
+                modules: publish.modules.clone(),
+                dependencies: publish.dependencies.clone(),
+            }),
+            C::MakeMoveVector(make_move_vector) => CommandKind::MakeMoveVector(MakeMoveVector {
+                type_: make_move_vector.type_.clone(),
+                elements: self.load_arguments(&make_move_vector.elements)?,
+            }),
+            C::Upgrade(upgrade) => CommandKind::Upgrade(Upgrade {
+                modules: upgrade.modules.clone(),
+                dependencies: upgrade.dependencies.clone(),
+                package: upgrade.package,
+                ticket: self.load_argument(&upgrade.ticket)?,
+            }),
+            _ => return Err(Error::Input("unsupported command kind".to_owned())),
+        };
+
+        Ok(kind)
+    }
+
     // Transaction Inputs
 
     pub fn gas(&mut self) -> Argument {
@@ -159,6 +346,33 @@ impl TransactionBuilder {
         }
     }
 
+    /// Withdraw `amount` of `coin_type` from the address balance of either the sender or the
+    /// sponsor of the transaction.
+    ///
+    /// The returned argument is a `0x2::funds_accumulator::Withdrawal<Balance<coin_type>>` which
+    /// can be redeemed for a `Coin` or `Balance` by a subsequent move call. Each call adds a new
+    /// input, even when withdrawing the same amount of the same type more than once.
+    pub fn funds_withdrawal(
+        &mut self,
+        amount: u64,
+        coin_type: TypeTag,
+        source: WithdrawFrom,
+    ) -> Argument {
+        self.funds_withdrawal_input(myso_sdk_types::FundsWithdrawal::new(
+            amount, coin_type, source,
+        ))
+    }
+
+    fn funds_withdrawal_input(&mut self, withdrawal: myso_sdk_types::FundsWithdrawal) -> Argument {
+        let id = self.arguments.len();
+        self.arguments.insert(id, ResolvedArgument::Unresolved);
+        self.inputs.insert(
+            InputArgKind::FundsWithdrawal(id),
+            (id, InputArg::FundsWithdrawal(withdrawal)),
+        );
+        Argument::new(id)
+    }
+
     // Metadata
 
     /// Add one or more gas objects to use to pay for the transaction.
@@ -195,6 +409,91 @@ impl TransactionBuilder {
         self.expiration = Some(expiration);
     }
 
+    /// Set the expiration of the transaction to a range of epochs it is valid during.
+    ///
+    /// Unlike [`TransactionExpiration::Epoch`], the transaction is bound to the chain it is valid
+    /// on and a `nonce` distinguishes it from otherwise identical transactions. This provides replay
+    /// protection for transactions which pay for gas from an address balance rather than with gas
+    /// coins.
+    ///
+    /// The network only accepts transactions whose `max_epoch` is at most `min_epoch + 1`.
+    pub fn set_valid_during(
+        &mut self,
+        min_epoch: EpochId,
+        max_epoch: EpochId,
+        chain: Digest,
+        nonce: u32,
+    ) {
+        self.expiration = Some(TransactionExpiration::ValidDuring {
+            min_epoch: Some(min_epoch),
+            max_epoch: Some(max_epoch),
+            min_timestamp: None,
+            max_timestamp: None,
+            chain,
+            nonce,
+        });
+    }
+
+    /// Expire the transaction once `epochs` epochs have passed after the current epoch.
+    ///
+    /// An `epochs` of `0` expires the transaction at the end of the current epoch.
+    #[cfg(feature = "intents")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "intents")))]
+    pub async fn expire_after_epochs(
+        &mut self,
+        client: &mut myso_rpc::Client,
+        epochs: u64,
+    ) -> Result<(), Error> {
+        let (epoch, _) = current_epoch_and_chain(client).await?;
+        self.set_expiration(TransactionExpiration::Epoch(epoch.saturating_add(epochs)));
+        Ok(())
+    }
+
+    /// Make the transaction valid from the current epoch until `epochs` epochs have passed, on
+    /// the chain `client` is connected to. See [`TransactionBuilder::set_valid_during`].
+    ///
+    /// `epochs` can be at most `1`, since the network rejects wider windows. If no `nonce` is
+    /// provided a random one is used.
+    #[cfg(feature = "intents")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "intents")))]
+    pub async fn valid_during(
+        &mut self,
+        client: &mut myso_rpc::Client,
+        epochs: u64,
+        nonce: Option<u32>,
+    ) -> Result<(), Error> {
+        if epochs > 1 {
+            return Err(Error::Input(format!(
+                "a transaction can be valid until at most 1 epoch after the current epoch, not \
+                 {epochs}"
+            )));
+        }
+
+        let (epoch, chain) = current_epoch_and_chain(client).await?;
+        let nonce = nonce.unwrap_or_else(rand::random);
+        self.set_valid_during(epoch, epoch.saturating_add(epochs), chain, nonce);
+        Ok(())
+    }
+
+    /// Set the limits the transaction is checked against when building. Defaults to
+    /// [`TransactionLimits::default`].
+    pub fn set_limits(&mut self, limits: TransactionLimits) {
+        self.limits = limits;
+    }
+
+    /// Enable checking every Move call against the on-chain signature of the function it calls
+    /// when building with [`TransactionBuilder::build`] or [`TransactionBuilder::build_kind`].
+    ///
+    /// This catches mismatched argument or type argument counts, type arguments missing required
+    /// abilities and pure values which aren't valid BCS for their parameter's type before the
+    /// transaction is simulated. Shared object inputs with unknown mutability are marked as
+    /// immutable if they are only ever passed by immutable reference.
+    #[cfg(feature = "intents")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "intents")))]
+    pub fn set_validate_move_calls(&mut self, validate: bool) {
+        self.validate_move_calls = validate;
+    }
+
     // Commands
 
     fn command(&mut self, command: Command) -> Argument {
@@ -283,7 +582,8 @@ impl TransactionBuilder {
     ///  - `ticket`: is the upgrade ticket
     ///
     ///  To get the ticket, you have to call the `0x2::package::authorize_upgrade` function,
-    ///  and pass the package ID, the upgrade policy, and package digest.
+    ///  and pass the package ID, the upgrade policy, and package digest. See
+    ///  [`PackageUpgrade`](crate::PackageUpgrade) for producing the full sequence of commands.
     pub fn upgrade(
         &mut self,
         modules: Vec<Vec<u8>>,
@@ -341,6 +641,32 @@ impl TransactionBuilder {
             budget,
         };
 
+        let ptb = self.try_build_programmable()?;
+
+        let transaction = Transaction {
+            kind: myso_sdk_types::TransactionKind::ProgrammableTransaction(ptb),
+            sender,
+            gas_payment,
+            expiration: self.expiration.unwrap_or(TransactionExpiration::None),
+        };
+        self.limits.check(&transaction)?;
+        Ok(transaction)
+    }
+
+    /// Assuming everything is resolved, convert this transaction into only its
+    /// [`TransactionKind`](myso_sdk_types::TransactionKind), without any sender, gas or expiration.
+    ///
+    /// This is useful for sponsored transactions where the gas payment is provided by a sponsor
+    /// after the transaction has been built, see [`SponsoredTransaction`](crate::SponsoredTransaction).
+    pub fn try_build_kind(mut self) -> Result<myso_sdk_types::TransactionKind, Error> {
+        let kind = myso_sdk_types::TransactionKind::ProgrammableTransaction(
+            self.try_build_programmable()?,
+        );
+        self.limits.check_kind(&kind)?;
+        Ok(kind)
+    }
+
+    fn try_build_programmable(&mut self) -> Result<myso_sdk_types::ProgrammableTransaction, Error> {
         // Error out if there are any unresolved intents
         if !self.intents.is_empty() {
             return Err(Error::Input("unable to resolve intents offline".to_owned()));
@@ -350,7 +676,9 @@ impl TransactionBuilder {
         // Inputs
         //
 
-        let mut unresolved_inputs = self.inputs.into_values().collect::<Vec<_>>();
+        let mut unresolved_inputs = std::mem::take(&mut self.inputs)
+            .into_values()
+            .collect::<Vec<_>>();
         unresolved_inputs.sort_by_key(|(id, _input)| *id);
 
         let mut resolved_inputs = Vec::new();
@@ -365,39 +693,183 @@ impl TransactionBuilder {
                     resolved_inputs.push(object_input.try_into_input()?);
                     myso_sdk_types::Argument::Input(resolved_inputs.len() as u16 - 1)
                 }
+                InputArg::FundsWithdrawal(withdrawal) => {
+                    resolved_inputs.push(myso_sdk_types::Input::FundsWithdrawal(withdrawal));
+                    myso_sdk_types::Argument::Input(resolved_inputs.len() as u16 - 1)
+                }
             };
 
             *self.arguments.get_mut(&id).unwrap() = ResolvedArgument::Resolved(arg);
         }
 
-        //
-        // Commands
-        //
+        Ok(myso_sdk_types::ProgrammableTransaction {
+            inputs: resolved_inputs,
+            commands: self.resolve_commands()?,
+        })
+    }
 
+    /// Resolve all commands, ordering them such that commands are always placed after any
+    /// commands they depend on.
+    fn resolve_commands(&mut self) -> Result<Vec<myso_sdk_types::Command>, Error> {
         let mut resolved_commands = Vec::new();
 
-        for (id, command) in self.commands {
-            resolved_commands.push(
-                command
-                    .try_resolve(&self.arguments)
-                    .map_err(|e| e.unwrap_err())?,
-            );
-            let arg = myso_sdk_types::Argument::Result(resolved_commands.len() as u16 - 1);
+        let mut stack = Vec::new();
+        let mut to_resolve = self.commands.pop_first();
+        while let Some((id, command)) = to_resolve.take() {
+            let resolved = match command.try_resolve(&self.arguments) {
+                Ok(resolved) => resolved,
+                Err(Ok(next)) => {
+                    // Push the current command on the stack
+                    stack.push((id, command));
+                    // set the next one to be processed
+                    to_resolve = Some(
+                        self.commands
+                            .remove_entry(&next)
+                            .expect("command must be there if it wasn't resolved yet"),
+                    );
+                    continue;
+                }
+                Err(Err(e)) => return Err(e),
+            };
 
+            resolved_commands.push(resolved);
+            let arg = myso_sdk_types::Argument::Result(resolved_commands.len() as u16 - 1);
             *self.arguments.get_mut(&id).unwrap() = ResolvedArgument::Resolved(arg);
+
+            // Pick the next command to resolve, either walked back down the stack or getting the
+            // next in order
+            if let Some(from_stack) = stack.pop() {
+                to_resolve = Some(from_stack);
+            } else {
+                to_resolve = self.commands.pop_first();
+            }
         }
 
-        Ok(Transaction {
-            kind: myso_sdk_types::TransactionKind::ProgrammableTransaction(
-                myso_sdk_types::ProgrammableTransaction {
-                    inputs: resolved_inputs,
-                    commands: resolved_commands,
-                },
-            ),
-            sender,
-            gas_payment,
-            expiration: self.expiration.unwrap_or(TransactionExpiration::None),
-        })
+        Ok(resolved_commands)
+    }
+
+    /// Resolve all intents and any incomplete object inputs, converting this transaction into only
+    /// its [`TransactionKind`](myso_sdk_types::TransactionKind).
+    ///
+    /// Unlike [`TransactionBuilder::build`] this does not require the sender to own any gas and
+    /// does not perform gas selection, making it suitable for building transactions that will be
+    /// sponsored. A sender is still required if any intents need to select the sender's coins.
+    ///
+    /// Shared object inputs whose mutability hasn't been set are marked as mutable, unless
+    /// [`TransactionBuilder::set_validate_move_calls`] is enabled and they are only ever passed
+    /// to Move calls by immutable reference.
+    #[cfg(feature = "intents")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "intents")))]
+    pub async fn build_kind(
+        mut self,
+        client: &mut myso_rpc::Client,
+    ) -> Result<myso_sdk_types::TransactionKind, Error> {
+        self.resolve_intents(client).await?;
+        if self.validate_move_calls {
+            crate::validation::validate_move_calls(&mut self, client).await?;
+        }
+        self.default_shared_mutability();
+        self.resolve_objects(client).await?;
+        self.try_build_kind()
+    }
+
+    /// Mark shared object inputs whose mutability is still unknown as mutable, which is valid for
+    /// every command they can be used in.
+    #[cfg(feature = "intents")]
+    fn default_shared_mutability(&mut self) {
+        for (_, input) in self.inputs.values_mut() {
+            if let InputArg::Object(object) = input
+                && matches!(object.kind, Some(ObjectKind::Shared))
+                && object.mutable.is_none()
+            {
+                object.mutable = Some(true);
+            }
+        }
+    }
+
+    #[cfg(feature = "intents")]
+    async fn resolve_intents(&mut self, client: &mut myso_rpc::Client) -> Result<(), Error> {
+        // For now we'll be dumb and just run through the registered resolvers one by one and if we
+        // still have intents left we'll bail
+
+        let resolvers = std::mem::take(&mut self.resolvers);
+        for resolver in resolvers.values() {
+            resolver
+                .resolve(self, client)
+                .await
+                .map_err(|e| Error::Input(e.to_string()))?;
+        }
+        // Error out if there are any remaining unresolved intents
+        if !self.intents.is_empty() {
+            return Err(Error::Input("unable to resolve all intents".to_owned()));
+        }
+
+        Ok(())
+    }
+
+    /// Fill in the kind, version and digest of any object inputs which are missing them by
+    /// fetching the objects.
+    #[cfg(feature = "intents")]
+    async fn resolve_objects(&mut self, client: &mut myso_rpc::Client) -> Result<(), Error> {
+        use myso_rpc::field::FieldMask;
+        use myso_rpc::field::FieldMaskUtil;
+        use myso_rpc::proto::myso::rpc::v2::BatchGetObjectsRequest;
+        use myso_rpc::proto::myso::rpc::v2::GetObjectRequest;
+        use myso_rpc::proto::myso::rpc::v2::get_object_result::Result as GetObjectResult;
+
+        let incomplete = self
+            .inputs
+            .values()
+            .filter_map(|(_, input)| match input {
+                InputArg::Object(object) if object.try_into_input().is_err() => {
+                    Some(object.object_id)
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        if incomplete.is_empty() {
+            return Ok(());
+        }
+
+        let response = client
+            .ledger_client()
+            .batch_get_objects(
+                BatchGetObjectsRequest::default()
+                    .with_requests(incomplete.iter().map(GetObjectRequest::new).collect())
+                    .with_read_mask(FieldMask::from_paths([
+                        "object_id",
+                        "version",
+                        "digest",
+                        "owner",
+                    ])),
+            )
+            .await
+            .map_err(|e| Error::Input(format!("error fetching objects: {e}")))?
+            .into_inner();
+
+        for (object_id, result) in incomplete.into_iter().zip(response.objects) {
+            let fetched = match result.result {
+                Some(GetObjectResult::Object(object)) => {
+                    ObjectInput::try_from_object_proto(&object)?
+                }
+                Some(GetObjectResult::Error(status)) => {
+                    return Err(Error::Input(format!(
+                        "error fetching object {object_id}: {}",
+                        status.message
+                    )));
+                }
+                _ => return Err(Error::Input(format!("unable to fetch object {object_id}"))),
+            };
+
+            if let Some((_, InputArg::Object(object))) =
+                self.inputs.get_mut(&InputArgKind::ObjectInput(object_id))
+            {
+                object.fill_from(fetched);
+            }
+        }
+
+        Ok(())
     }
 
     #[cfg(feature = "intents")]
@@ -428,30 +900,18 @@ impl TransactionBuilder {
             .with_do_gas_selection(true);
         request.transaction_mut().set_sender(sender);
 
-        //
-        // Intents
-        //
-
-        // For now we'll be dumb and just run through the registered resolvers one by one and if we
-        // still have intents left we'll bail
-
-        let resolvers = std::mem::take(&mut self.resolvers);
-        for resolver in resolvers.values() {
-            resolver
-                .resolve(&mut self, client)
-                .await
-                .map_err(|e| Error::Input(e.to_string()))?;
-        }
-        // Error out if there are any remaining unresolved intents
-        if !self.intents.is_empty() {
-            return Err(Error::Input("unable to resolve all intents".to_owned()));
+        self.resolve_intents(client).await?;
+        if self.validate_move_calls {
+            crate::validation::validate_move_calls(&mut self, client).await?;
         }
 
         //
         // Inputs
         //
 
-        let mut unresolved_inputs = self.inputs.into_values().collect::<Vec<_>>();
+        let mut unresolved_inputs = std::mem::take(&mut self.inputs)
+            .into_values()
+            .collect::<Vec<_>>();
         unresolved_inputs.sort_by_key(|(id, _input)| *id);
 
         let mut resolved_inputs = Vec::new();
@@ -467,6 +927,10 @@ impl TransactionBuilder {
                     resolved_inputs.push(object_input.to_input_proto());
                     myso_sdk_types::Argument::Input(resolved_inputs.len() as u16 - 1)
                 }
+                InputArg::FundsWithdrawal(withdrawal) => {
+                    resolved_inputs.push(myso_sdk_types::Input::FundsWithdrawal(withdrawal).into());
+                    myso_sdk_types::Argument::Input(resolved_inputs.len() as u16 - 1)
+                }
             };
 
             *self.arguments.get_mut(&id).unwrap() = ResolvedArgument::Resolved(arg);
@@ -476,39 +940,7 @@ impl TransactionBuilder {
         // Commands
         //
 
-        let mut resolved_commands = Vec::new();
-
-        let mut stack = Vec::new();
-        let mut to_resolve = self.commands.pop_first();
-        while let Some((id, command)) = to_resolve.take() {
-            let resolved = match command.try_resolve(&self.arguments) {
-                Ok(resolved) => resolved,
-                Err(Ok(next)) => {
-                    // Push the current command on the stack
-                    stack.push((id, command));
-                    // set the next one to be processed
-                    to_resolve = Some(
-                        self.commands
-                            .remove_entry(&next)
-                            .expect("command must be there if it wasn't resolved yet"),
-                    );
-                    continue;
-                }
-                Err(Err(e)) => return Err(e),
-            };
-
-            resolved_commands.push(resolved);
-            let arg = myso_sdk_types::Argument::Result(resolved_commands.len() as u16 - 1);
-            *self.arguments.get_mut(&id).unwrap() = ResolvedArgument::Resolved(arg);
-
-            // Pick the next command to resolve, either walked back down the stack or getting the
-            // next in order
-            if let Some(from_stack) = stack.pop() {
-                to_resolve = Some(from_stack);
-            } else {
-                to_resolve = self.commands.pop_first();
-            }
-        }
+        let resolved_commands = self.resolve_commands()?;
 
         let t = request.transaction_mut();
         t.kind_mut()
@@ -543,32 +975,78 @@ impl TransactionBuilder {
             .await
             .map_err(|e| Error::Input(format!("error simulating transaction: {e}")))?;
 
-        if !response
-            .get_ref()
-            .transaction()
-            .effects()
-            .status()
-            .success()
-        {
-            return Err(Error::Input(format!(
-                "txn failed to execute: {}",
-                response
-                    .get_ref()
-                    .transaction()
-                    .effects()
-                    .status()
-                    .error()
-                    .description()
-            )));
+        let status = response.get_ref().transaction().effects().status();
+        if !status.success() {
+            let error = status.error();
+            let clever_error = match error.abort_opt() {
+                Some(abort) => match abort.clever_error_opt() {
+                    Some(clever_error) => Some(myso_rpc::client::CleverError::from(clever_error)),
+                    None => match myso_sdk_types::MoveLocation::try_from(abort.location()) {
+                        Ok(location) => client
+                            .decode_clever_error(&location, abort.abort_code())
+                            .await
+                            .ok()
+                            .flatten(),
+                        Err(_) => None,
+                    },
+                },
+                None => None,
+            };
+            return Err(Error::Input(match clever_error {
+                Some(clever_error) => format!(
+                    "txn failed to execute: {} ({clever_error})",
+                    error.description()
+                ),
+                None => format!("txn failed to execute: {}", error.description()),
+            }));
         }
 
-        response
+        let transaction: Transaction = response
             .get_ref()
             .transaction()
             .transaction()
             .bcs()
             .deserialize()
-            .map_err(|e| Error::Input(e.to_string()))
+            .map_err(|e| Error::Input(e.to_string()))?;
+        self.limits.check(&transaction)?;
+        Ok(transaction)
+    }
+
+    /// Record the current state of the builder so that later additions can be undone with
+    /// [`TransactionBuilder::rollback`].
+    pub(crate) fn checkpoint(&self) -> Checkpoint {
+        let objects = self
+            .inputs
+            .values()
+            .filter_map(|(_, input)| match input {
+                InputArg::Object(object) => Some(object.clone()),
+                _ => None,
+            })
+            .collect();
+
+        Checkpoint {
+            len: self.arguments.len(),
+            objects,
+        }
+    }
+
+    /// Remove every argument, and the inputs, commands and intents which produce them, created
+    /// since the checkpoint, and restore object inputs which were updated by reusing them.
+    pub(crate) fn rollback(&mut self, checkpoint: Checkpoint) {
+        let len = checkpoint.len;
+        self.arguments.split_off(&len);
+        self.commands.split_off(&len);
+        self.intents.split_off(&len);
+        self.inputs.retain(|_, (id, _)| *id < len);
+
+        for object in checkpoint.objects {
+            if let Some((_, InputArg::Object(input))) = self
+                .inputs
+                .get_mut(&InputArgKind::ObjectInput(object.object_id))
+            {
+                *input = object;
+            }
+        }
     }
 
     #[cfg(feature = "intents")]
@@ -591,10 +1069,34 @@ impl TransactionBuilder {
     }
 }
 
+/// Fetch the current epoch and the ID of the chain a client is connected to from the metadata
+/// of a response.
+#[cfg(feature = "intents")]
+async fn current_epoch_and_chain(
+    client: &mut myso_rpc::Client,
+) -> Result<(EpochId, Digest), Error> {
+    use myso_rpc::client::ResponseExt;
+    use myso_rpc::proto::myso::rpc::v2::GetServiceInfoRequest;
+
+    let response = client
+        .ledger_client()
+        .get_service_info(GetServiceInfoRequest::default())
+        .await
+        .map_err(|e| Error::Input(format!("error fetching service info: {e}")))?;
+
+    let epoch = response
+        .epoch()
+        .ok_or_else(|| Error::Input("response is missing the current epoch".to_owned()))?;
+    let chain = response
+        .chain_id()
+        .ok_or_else(|| Error::Input("response is missing the chain id".to_owned()))?;
+    Ok((epoch, chain))
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct Argument {
-    id: usize,
-    sub_index: Option<usize>,
+    pub(crate) id: usize,
+    pub(crate) sub_index: Option<usize>,
 }
 
 impl Argument {
@@ -665,7 +1167,7 @@ impl Argument {
 }
 
 pub(crate) struct Command {
-    kind: CommandKind,
+    pub(crate) kind: CommandKind,
     // A way to encode dependencies between commands when there aren't dependencies via explicit
     // input/outputs
     pub(crate) dependencies: Vec<Argument>,
@@ -872,16 +1374,17 @@ pub(crate) struct MoveCall {
     // Return value count??
 }
 
+#[derive(Clone)]
 pub struct ObjectInput {
-    object_id: Address,
-    kind: Option<ObjectKind>,
-    version: Option<u64>,
-    digest: Option<Digest>,
-    mutable: Option<bool>,
+    pub(crate) object_id: Address,
+    pub(crate) kind: Option<ObjectKind>,
+    pub(crate) version: Option<u64>,
+    pub(crate) digest: Option<Digest>,
+    pub(crate) mutable: Option<bool>,
 }
 
 #[derive(Clone, Copy)]
-enum ObjectKind {
+pub(crate) enum ObjectKind {
     Shared,
     Receiving,
     ImmutableOrOwned,
@@ -1029,6 +1532,16 @@ impl From<&myso_sdk_types::Object> for ObjectInput {
 
 // private conversions
 impl ObjectInput {
+    /// Fill in any missing information about this object from `other`, keeping everything that
+    /// has already been explicitly set.
+    #[cfg(feature = "intents")]
+    fn fill_from(&mut self, other: ObjectInput) {
+        self.kind = self.kind.or(other.kind);
+        self.version = self.version.or(other.version);
+        self.digest = self.digest.or(other.digest);
+        self.mutable = self.mutable.or(other.mutable);
+    }
+
     fn try_into_object_reference(&self) -> Result<myso_sdk_types::ObjectReference, Error> {
         if matches!(self.kind, Some(ObjectKind::ImmutableOrOwned) | None)
             && let Some(version) = self.version
@@ -1139,8 +1652,8 @@ impl ObjectInput {
             return Err(Error::WrongGasObject);
         }
 
-        let mut input =
-            myso_rpc::proto::myso::rpc::v2::ObjectReference::default().with_object_id(self.object_id);
+        let mut input = myso_rpc::proto::myso::rpc::v2::ObjectReference::default()
+            .with_object_id(self.object_id);
         if let Some(version) = self.version {
             input.set_version(version);
         }
@@ -1258,6 +1771,47 @@ mod tests {
         assert!(tx.try_build().is_ok());
     }
 
+    #[cfg(feature = "intents")]
+    #[test]
+    fn default_shared_mutability() {
+        use myso_sdk_types::Input;
+        use myso_sdk_types::TransactionKind;
+
+        let counter = Address::from_static("0xc0ffee");
+        let clock = Address::from_static("0x6");
+
+        let transaction = || {
+            let mut tx = TransactionBuilder::new();
+            let counter_arg = tx.object(ObjectInput::new(counter).as_shared().with_version(3));
+            let clock_arg = tx.object(ObjectInput::shared(clock, 1, false));
+            tx.move_call(
+                Function::new(
+                    Address::from_static("0x123"),
+                    Identifier::from_static("counter"),
+                    Identifier::from_static("increment"),
+                ),
+                vec![counter_arg, clock_arg],
+            );
+            tx
+        };
+
+        // The kind can't be built while the mutability of the counter is unknown
+        transaction().try_build_kind().unwrap_err();
+
+        let mut tx = transaction();
+        tx.default_shared_mutability();
+        let TransactionKind::ProgrammableTransaction(ptb) = tx.try_build_kind().unwrap() else {
+            panic!("expected a programmable transaction");
+        };
+        assert_eq!(
+            ptb.inputs,
+            vec![
+                Input::Shared(myso_sdk_types::SharedInput::new(counter, 3, true)),
+                Input::Shared(myso_sdk_types::SharedInput::new(clock, 1, false)),
+            ]
+        );
+    }
+
     #[test]
     fn test_split_transfer() {
         let mut tx = TransactionBuilder::new();
@@ -1285,6 +1839,139 @@ mod tests {
         assert!(tx.try_build().is_ok());
     }
 
+    #[test]
+    fn test_funds_withdrawal() {
+        let mut tx = TransactionBuilder::new();
+
+        let coin_type: TypeTag = "0x2::myso::MYSO".parse().unwrap();
+        let first = tx.funds_withdrawal(100, coin_type.clone(), WithdrawFrom::Sender);
+        let second = tx.funds_withdrawal(100, coin_type.clone(), WithdrawFrom::Sender);
+        let coins = [first, second].map(|withdrawal| {
+            tx.move_call(
+                Function::new(
+                    Address::TWO,
+                    Identifier::from_static("coin"),
+                    Identifier::from_static("redeem_funds"),
+                )
+                .with_type_args(vec![coin_type.clone()]),
+                vec![withdrawal],
+            )
+        });
+        let recipient = tx.pure(&Address::from_static("0xabc"));
+        tx.transfer_objects(coins.to_vec(), recipient);
+
+        tx.set_gas_budget(500000000);
+        tx.set_gas_price(1000);
+        tx.add_gas_objects([ObjectInput::owned(
+            Address::from_static(
+                "0xd8792bce2743e002673752902c0e7348dfffd78638cb5367b0b85857bceb9821",
+            ),
+            2,
+            Digest::from_static("2ZigdvsZn5BMeszscPQZq9z8ebnS2FpmAuRbAi9ednCk"),
+        )]);
+        tx.set_sender(Address::from_static(
+            "0xc574ea804d9c1a27c886312e96c0e2c9cfd71923ebaeb3000d04b5e65fca2793",
+        ));
+
+        let tx = tx.try_build().unwrap();
+        let myso_sdk_types::TransactionKind::ProgrammableTransaction(ptb) = tx.kind else {
+            panic!("expected a programmable transaction");
+        };
+
+        // Identical withdrawals are not deduplicated
+        assert_eq!(
+            ptb.inputs[..2],
+            [
+                myso_sdk_types::Input::FundsWithdrawal(myso_sdk_types::FundsWithdrawal::new(
+                    100,
+                    coin_type.clone(),
+                    WithdrawFrom::Sender
+                )),
+                myso_sdk_types::Input::FundsWithdrawal(myso_sdk_types::FundsWithdrawal::new(
+                    100,
+                    coin_type,
+                    WithdrawFrom::Sender
+                )),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_from_transaction() {
+        let mut tx = TransactionBuilder::new();
+        let coin = tx.object(ObjectInput::owned(
+            Address::from_static(
+                "0x19406ea4d9609cd9422b85e6bf2486908f790b778c757aff805241f3f609f9b4",
+            ),
+            2,
+            Digest::from_static("7opR9rFUYivSTqoJHvFb9p6p54THyHTatMG6id4JKZR9"),
+        ));
+        let amount = tx.pure(&1_000u64);
+        let gas = tx.gas();
+        let split = tx.split_coins(gas, vec![amount, amount]);
+        tx.merge_coins(coin, vec![split[1]]);
+        let recipient = tx.pure(&Address::from_static("0xabc"));
+        tx.transfer_objects(vec![coin, split[0]], recipient);
+        tx.set_gas_budget(500000000);
+        tx.set_gas_price(1000);
+        tx.add_gas_objects([ObjectInput::owned(
+            Address::from_static(
+                "0xd8792bce2743e002673752902c0e7348dfffd78638cb5367b0b85857bceb9821",
+            ),
+            2,
+            Digest::from_static("2ZigdvsZn5BMeszscPQZq9z8ebnS2FpmAuRbAi9ednCk"),
+        )]);
+        tx.set_sender(Address::from_static(
+            "0xc574ea804d9c1a27c886312e96c0e2c9cfd71923ebaeb3000d04b5e65fca2793",
+        ));
+        tx.set_sponsor(Address::from_static("0x5"));
+        let transaction = tx.try_build().unwrap();
+
+        // Loading and rebuilding without changes is lossless
+        let rebuilt = TransactionBuilder::from_transaction(&transaction)
+            .unwrap()
+            .try_build()
+            .unwrap();
+        assert_eq!(rebuilt, transaction);
+
+        // Modify the gas budget and append a command using a loaded input
+        let mut tx = TransactionBuilder::from_transaction(&transaction).unwrap();
+        tx.set_gas_budget(1000);
+        let amount = tx.loaded_input(1).unwrap();
+        let gas = tx.gas();
+        let coins = tx.split_coins(gas, vec![amount]);
+        let recipient = tx.loaded_input(2).unwrap();
+        tx.transfer_objects(coins, recipient);
+        let modified = tx.try_build().unwrap();
+
+        assert_eq!(modified.gas_payment.budget, 1000);
+        let (
+            myso_sdk_types::TransactionKind::ProgrammableTransaction(original),
+            myso_sdk_types::TransactionKind::ProgrammableTransaction(modified),
+        ) = (transaction.kind, modified.kind)
+        else {
+            panic!("expected programmable transactions");
+        };
+        assert_eq!(modified.inputs, original.inputs);
+        assert_eq!(
+            modified.commands[..original.commands.len()],
+            original.commands
+        );
+        assert_eq!(
+            modified.commands[original.commands.len()..],
+            [
+                myso_sdk_types::Command::SplitCoins(myso_sdk_types::SplitCoins {
+                    coin: myso_sdk_types::Argument::Gas,
+                    amounts: vec![myso_sdk_types::Argument::Input(1)],
+                }),
+                myso_sdk_types::Command::TransferObjects(myso_sdk_types::TransferObjects {
+                    objects: vec![myso_sdk_types::Argument::NestedResult(3, 0)],
+                    address: myso_sdk_types::Argument::Input(2),
+                }),
+            ]
+        );
+    }
+
     #[test]
     fn test_deterministic_building() {
         let build_tx = || {
@@ -1346,4 +2033,59 @@ mod tests {
                 .all(|d| d == digest)
         )
     }
+
+    #[test]
+    fn test_valid_during() {
+        let chain = Digest::from_static("4btiuiMPvEENsttpZC7CZ53DruC3MAgfznDbASZ7DR6S");
+        let mut tx = TransactionBuilder::new();
+        let coin = tx.gas();
+        let recipient = tx.pure(&Address::from_static("0xabc"));
+        tx.transfer_objects(vec![coin], recipient);
+        tx.set_gas_budget(500000000);
+        tx.set_gas_price(1000);
+        tx.add_gas_objects([ObjectInput::owned(
+            Address::from_static("0xd879"),
+            2,
+            Digest::from_static("2ZigdvsZn5BMeszscPQZq9z8ebnS2FpmAuRbAi9ednCk"),
+        )]);
+        tx.set_sender(Address::from_static("0xc574"));
+        tx.set_valid_during(10, 11, chain, 7);
+
+        let transaction = tx.try_build().unwrap();
+        assert_eq!(
+            transaction.expiration,
+            TransactionExpiration::ValidDuring {
+                min_epoch: Some(10),
+                max_epoch: Some(11),
+                min_timestamp: None,
+                max_timestamp: None,
+                chain,
+                nonce: 7,
+            }
+        );
+
+        let rebuilt = TransactionBuilder::from_transaction(&transaction)
+            .unwrap()
+            .try_build()
+            .unwrap();
+        assert_eq!(rebuilt.digest(), transaction.digest());
+    }
+
+    #[test]
+    fn test_pure_u256() {
+        use myso_sdk_types::Input;
+        use myso_sdk_types::TransactionKind;
+        use myso_sdk_types::U256;
+
+        let mut tx = TransactionBuilder::new();
+        let value = U256::from(u128::MAX);
+        tx.pure(&value);
+
+        let TransactionKind::ProgrammableTransaction(ptb) = tx.try_build_kind().unwrap() else {
+            panic!("expected a programmable transaction");
+        };
+        let mut bytes = [0; 32];
+        bytes[..16].fill(0xff);
+        assert_eq!(ptb.inputs, [Input::Pure(bytes.to_vec())]);
+    }
 }
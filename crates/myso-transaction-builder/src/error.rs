@@ -2,6 +2,7 @@
 // Copyright (c) The Social Proof Foundation, LLC.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::limits::Limit;
 use myso_sdk_types::Address;
 
 #[derive(thiserror::Error, Debug, Clone)]
@@ -29,4 +30,12 @@ pub enum Error {
     MissingObjectKind(Address),
     #[error("Unknown shared object mutability for object {0}")]
     SharedObjectMutability(Address),
+    #[error("Invalid call to {function}: {reason}")]
+    InvalidMoveCall { function: String, reason: String },
+    #[error("Transaction exceeds the {limit} limit: {actual} > {max}")]
+    LimitExceeded {
+        limit: Limit,
+        actual: usize,
+        max: usize,
+    },
 }
@@ -6,25 +6,30 @@ use crate::builder::ResolvedArgument;
 use crate::intent::BoxError;
 use crate::intent::Intent;
 use crate::intent::IntentResolver;
-use crate::intent::MAX_ARGUMENTS;
-use crate::intent::MAX_GAS_OBJECTS;
 use std::collections::BTreeMap;
 use myso_sdk_types::Address;
 use myso_sdk_types::Identifier;
 use myso_sdk_types::StructTag;
+use myso_sdk_types::WithdrawFrom;
 
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
 pub struct CoinWithBalance {
     coin_type: StructTag,
     balance: u64,
     use_gas_coin: bool,
+    use_address_balance: bool,
 }
 
 impl CoinWithBalance {
+    /// The name this intent is registered under when serializing a `TransactionBuilder`.
+    pub(crate) const NAME: &str = "CoinWithBalance";
+
     pub fn new(coin_type: StructTag, balance: u64) -> Self {
         Self {
             coin_type,
             balance,
             use_gas_coin: true,
+            use_address_balance: false,
         }
     }
 
@@ -33,6 +38,7 @@ impl CoinWithBalance {
             coin_type: StructTag::myso(),
             balance,
             use_gas_coin: true,
+            use_address_balance: false,
         }
     }
 
@@ -44,6 +50,15 @@ impl CoinWithBalance {
             ..self
         }
     }
+
+    // Used to opt in to drawing from the sender's address balance when the sender's coin objects
+    // are insufficient to cover the requested balance.
+    pub fn with_use_address_balance(self, use_address_balance: bool) -> Self {
+        Self {
+            use_address_balance,
+            ..self
+        }
+    }
 }
 
 impl Intent for CoinWithBalance {
@@ -54,9 +69,9 @@ impl Intent for CoinWithBalance {
 }
 
 #[derive(Debug)]
-struct CoinWithBalanceResolver;
+pub(crate) struct CoinWithBalanceResolver;
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 enum CoinType {
     Gas,
     Coin(StructTag),
@@ -71,6 +86,8 @@ impl IntentResolver for CoinWithBalanceResolver {
     ) -> Result<(), BoxError> {
         // Collect all the requests
         let mut requests: BTreeMap<CoinType, Vec<(usize, u64)>> = BTreeMap::new();
+        // Address balances are only used for a coin type if every request for it allows it
+        let mut use_address_balance: BTreeMap<CoinType, bool> = BTreeMap::new();
         let mut zero_values = Vec::new();
 
         for (id, intent) in builder.intents.extract_if(.., |_id, intent| {
@@ -86,6 +103,8 @@ impl IntentResolver for CoinWithBalanceResolver {
                 } else {
                     CoinType::Coin(request.coin_type.clone())
                 };
+                *use_address_balance.entry(coin_type.clone()).or_insert(true) &=
+                    request.use_address_balance;
                 requests
                     .entry(coin_type)
                     .or_default()
@@ -98,13 +117,24 @@ impl IntentResolver for CoinWithBalanceResolver {
         }
 
         for (coin_type, requests) in requests {
+            let use_address_balance = use_address_balance[&coin_type];
             match coin_type {
                 CoinType::Gas => {
-                    CoinWithBalanceResolver::resolve_gas_coin(builder, client, &requests).await?;
+                    CoinWithBalanceResolver::resolve_gas_coin(
+                        builder,
+                        client,
+                        &requests,
+                        use_address_balance,
+                    )
+                    .await?;
                 }
                 CoinType::Coin(coin_type) => {
                     CoinWithBalanceResolver::resolve_coin_type(
-                        builder, client, &coin_type, &requests,
+                        builder,
+                        client,
+                        &coin_type,
+                        &requests,
+                        use_address_balance,
                     )
                     .await?;
                 }
@@ -134,11 +164,73 @@ impl CoinWithBalanceResolver {
         *builder.arguments.get_mut(&request_id).unwrap() = ResolvedArgument::ReplaceWith(coin);
     }
 
+    /// Determine how much of `amount` should be taken from `sender`'s coin objects and how much
+    /// from their address balance, preferring coin objects.
+    async fn split_funding(
+        client: &mut myso_rpc::Client,
+        sender: &Address,
+        coin_type: &StructTag,
+        amount: u64,
+        use_address_balance: bool,
+    ) -> Result<(u64, u64), BoxError> {
+        use myso_rpc::proto::myso::rpc::v2::GetBalanceRequest;
+
+        if !use_address_balance {
+            return Ok((amount, 0));
+        }
+
+        let response = client
+            .state_client()
+            .get_balance(
+                GetBalanceRequest::default()
+                    .with_owner(sender.to_string())
+                    .with_coin_type(coin_type.to_string()),
+            )
+            .await?
+            .into_inner();
+        let balance = response.balance();
+
+        let coin_balance = balance.coin_balance();
+        if coin_balance >= amount {
+            return Ok((amount, 0));
+        }
+
+        let shortfall = amount - coin_balance;
+        if balance.address_balance() < shortfall {
+            return Err(format!(
+                "unable to find sufficient coins or address balance of type {coin_type}"
+            )
+            .into());
+        }
+
+        Ok((coin_balance, shortfall))
+    }
+
+    /// Withdraw `amount` from the sender's address balance and redeem it for a coin.
+    fn redeem_address_balance(
+        builder: &mut TransactionBuilder,
+        coin_type: &StructTag,
+        amount: u64,
+    ) -> Argument {
+        let withdrawal =
+            builder.funds_withdrawal(amount, coin_type.clone().into(), WithdrawFrom::Sender);
+        builder.move_call(
+            Function::new(
+                Address::TWO,
+                Identifier::from_static("coin"),
+                Identifier::from_static("redeem_funds"),
+            )
+            .with_type_args(vec![coin_type.clone().into()]),
+            vec![withdrawal],
+        )
+    }
+
     async fn resolve_coin_type(
         builder: &mut TransactionBuilder,
         client: &mut myso_rpc::Client,
         coin_type: &StructTag,
         requests: &[(usize, u64)],
+        use_address_balance: bool,
     ) -> Result<(), BoxError> {
         let sender = builder
             .sender()
@@ -150,20 +242,50 @@ impl CoinWithBalanceResolver {
 
         let sum = requests.iter().map(|(_, balance)| *balance).sum();
 
-        let coins = client
-            //TODO populate excludes
-            .select_coins(&sender, &(coin_type.clone().into()), sum, &[])
-            .await?;
+        let (from_coins, from_address_balance) =
+            Self::split_funding(client, &sender, coin_type, sum, use_address_balance).await?;
+
+        let coins = if from_coins > 0 {
+            client
+                //TODO populate excludes
+                .select_coins(&sender, &(coin_type.clone().into()), from_coins, &[])
+                .await?
+                .into_iter()
+                .map(|coin| ObjectInput::try_from_object_proto(&coin))
+                .collect::<Result<Vec<_>, _>>()?
+        } else {
+            Vec::new()
+        };
 
-        // For MYSO need to handle working with gas coin
-        let split_coin_args = if let [first, rest @ ..] = coins
+        Self::split_coin_type(builder, coin_type, requests, coins, from_address_balance)
+    }
+
+    /// Merge `coins` and `from_address_balance` redeemed from the sender's address balance into
+    /// a single coin and split the requested balances from it.
+    fn split_coin_type(
+        builder: &mut TransactionBuilder,
+        coin_type: &StructTag,
+        requests: &[(usize, u64)],
+        coins: Vec<ObjectInput>,
+        from_address_balance: u64,
+    ) -> Result<(), BoxError> {
+        let mut coins = coins
             .into_iter()
-            .map(|coin| ObjectInput::try_from_object_proto(&coin).map(|coin| builder.object(coin)))
-            .collect::<Result<Vec<_>, _>>()?
-            .as_slice()
-        {
+            .map(|coin| builder.object(coin))
+            .collect::<Vec<_>>();
+
+        if from_address_balance > 0 {
+            coins.push(Self::redeem_address_balance(
+                builder,
+                coin_type,
+                from_address_balance,
+            ));
+        }
+
+        // For MYSO need to handle working with gas coin
+        let split_coin_args = if let [first, rest @ ..] = coins.as_slice() {
             let mut deps = Vec::new();
-            for chunk in rest.chunks(MAX_ARGUMENTS) {
+            for chunk in rest.chunks(max_coins_to_merge(builder)) {
                 builder.merge_coins(*first, chunk.to_vec());
                 deps.push(Argument::new(*builder.commands.last_key_value().unwrap().0));
             }
@@ -202,6 +324,7 @@ impl CoinWithBalanceResolver {
         builder: &mut TransactionBuilder,
         client: &mut myso_rpc::Client,
         requests: &[(usize, u64)],
+        use_address_balance: bool,
     ) -> Result<(), BoxError> {
         let sender = builder
             .sender()
@@ -213,26 +336,66 @@ impl CoinWithBalanceResolver {
 
         let sum = requests.iter().map(|(_, balance)| *balance).sum();
 
-        let mut coins = client
-            //TODO populate excludes
-            .select_coins(&sender, &(StructTag::myso().into()), sum, &[])
-            .await?
-            .into_iter()
-            .map(|coin| ObjectInput::try_from_object_proto(&coin))
-            .collect::<Result<Vec<_>, _>>()?
-            .into_iter();
+        let (from_coins, from_address_balance) = Self::split_funding(
+            client,
+            &sender,
+            &StructTag::myso(),
+            sum,
+            use_address_balance,
+        )
+        .await?;
+
+        let coins = if from_coins > 0 {
+            client
+                //TODO populate excludes
+                .select_coins(&sender, &(StructTag::myso().into()), from_coins, &[])
+                .await?
+                .into_iter()
+                .map(|coin| ObjectInput::try_from_object_proto(&coin))
+                .collect::<Result<Vec<_>, _>>()?
+        } else {
+            Vec::new()
+        };
 
+        Self::split_gas_coin(builder, requests, coins, from_address_balance);
+        Ok(())
+    }
+
+    /// Add `coins` to the gas coin, along with `from_address_balance` redeemed from the sender's
+    /// address balance, and split the requested balances from it.
+    fn split_gas_coin(
+        builder: &mut TransactionBuilder,
+        requests: &[(usize, u64)],
+        coins: Vec<ObjectInput>,
+        from_address_balance: u64,
+    ) {
+        let mut coins = coins.into_iter();
         let gas = builder.gas();
         let mut deps = Vec::new();
 
-        // Append to gas coin up to 250 coins
-        builder
-            .add_gas_objects((&mut coins).take(MAX_GAS_OBJECTS.saturating_sub(builder.gas.len())));
+        // Use as many coins as possible as gas objects
+        builder.add_gas_objects(
+            (&mut coins).take(
+                builder
+                    .limits
+                    .max_gas_objects
+                    .saturating_sub(builder.gas.len()),
+            ),
+        );
 
         // Any remaining do a merge coins
-        let remaining = coins.map(|coin| builder.object(coin)).collect::<Vec<_>>();
+        let mut remaining = coins.map(|coin| builder.object(coin)).collect::<Vec<_>>();
+
+        // Top up the gas coin from the address balance to cover any shortfall
+        if from_address_balance > 0 {
+            remaining.push(Self::redeem_address_balance(
+                builder,
+                &StructTag::myso(),
+                from_address_balance,
+            ));
+        }
 
-        for chunk in remaining.chunks(MAX_ARGUMENTS) {
+        for chunk in remaining.chunks(max_coins_to_merge(builder)) {
             builder.merge_coins(gas, chunk.to_vec());
             deps.push(Argument::new(*builder.commands.last_key_value().unwrap().0));
         }
@@ -259,7 +422,183 @@ impl CoinWithBalanceResolver {
             *builder.arguments.get_mut(&request_index).unwrap() =
                 ResolvedArgument::ReplaceWith(coin);
         }
+    }
+}
 
-        Ok(())
+/// The number of coins which can be merged by a single `MergeCoins` command, which also takes the
+/// coin being merged into as an argument.
+fn max_coins_to_merge(builder: &TransactionBuilder) -> usize {
+    builder.limits.max_arguments.saturating_sub(1).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use myso_sdk_types::Argument as Arg;
+    use myso_sdk_types::Command;
+    use myso_sdk_types::Digest;
+    use myso_sdk_types::FundsWithdrawal;
+    use myso_sdk_types::Input;
+    use myso_sdk_types::MergeCoins;
+    use myso_sdk_types::MoveCall;
+    use myso_sdk_types::ProgrammableTransaction;
+    use myso_sdk_types::SplitCoins;
+    use myso_sdk_types::TransactionKind;
+
+    fn coin(id: &'static str) -> ObjectInput {
+        ObjectInput::owned(
+            Address::from_static(id),
+            1,
+            Digest::from_static("7opR9rFUYivSTqoJHvFb9p6p54THyHTatMG6id4JKZR9"),
+        )
+    }
+
+    /// Register a request for each balance and return them in the form the resolver sees them.
+    fn register(
+        tx: &mut TransactionBuilder,
+        coin_type: &StructTag,
+        balances: &[u64],
+    ) -> Vec<(usize, u64)> {
+        let requests = balances
+            .iter()
+            .map(|balance| {
+                let coin = tx.intent(CoinWithBalance::new(coin_type.clone(), *balance));
+                (coin.id, *balance)
+            })
+            .collect::<Vec<_>>();
+
+        let coins = requests.iter().map(|(id, _)| Argument::new(*id)).collect();
+        let recipient = tx.pure(&Address::from_static("0xabc"));
+        tx.transfer_objects(coins, recipient);
+        tx.intents.clear();
+        requests
+    }
+
+    fn build(tx: TransactionBuilder) -> ProgrammableTransaction {
+        let TransactionKind::ProgrammableTransaction(ptb) = tx.try_build_kind().unwrap() else {
+            panic!("expected a programmable transaction");
+        };
+        ptb
+    }
+
+    fn redeem_funds(coin_type: &StructTag, withdrawal: u16) -> Command {
+        Command::MoveCall(MoveCall {
+            package: Address::TWO,
+            module: Identifier::from_static("coin"),
+            function: Identifier::from_static("redeem_funds"),
+            type_arguments: vec![coin_type.clone().into()],
+            arguments: vec![Arg::Input(withdrawal)],
+        })
+    }
+
+    #[test]
+    fn split_coin_type() {
+        let coin_type: StructTag = "0xabc::token::TOKEN".parse().unwrap();
+        let mut tx = TransactionBuilder::new();
+        let requests = register(&mut tx, &coin_type, &[10, 20]);
+        CoinWithBalanceResolver::split_coin_type(
+            &mut tx,
+            &coin_type,
+            &requests,
+            vec![coin("0x1001"), coin("0x1002")],
+            5,
+        )
+        .unwrap();
+        let ptb = build(tx);
+
+        // The recipient is followed by the coins, the withdrawal and the amounts
+        assert_eq!(
+            ptb.inputs[3],
+            Input::FundsWithdrawal(FundsWithdrawal::new(
+                5,
+                coin_type.clone().into(),
+                WithdrawFrom::Sender
+            ))
+        );
+        assert_eq!(
+            ptb.commands,
+            [
+                redeem_funds(&coin_type, 3),
+                Command::MergeCoins(MergeCoins {
+                    coin: Arg::Input(1),
+                    coins_to_merge: vec![Arg::Input(2), Arg::Result(0)],
+                }),
+                Command::SplitCoins(SplitCoins {
+                    coin: Arg::Input(1),
+                    amounts: vec![Arg::Input(4), Arg::Input(5)],
+                }),
+                Command::TransferObjects(myso_sdk_types::TransferObjects {
+                    objects: vec![Arg::NestedResult(2, 0), Arg::NestedResult(2, 1)],
+                    address: Arg::Input(0),
+                }),
+            ]
+        );
+
+        // Funded entirely from the address balance the redeemed coin is split directly
+        let mut tx = TransactionBuilder::new();
+        let requests = register(&mut tx, &coin_type, &[10]);
+        CoinWithBalanceResolver::split_coin_type(&mut tx, &coin_type, &requests, vec![], 10)
+            .unwrap();
+        let ptb = build(tx);
+        assert_eq!(
+            ptb.commands[..2],
+            [
+                redeem_funds(&coin_type, 1),
+                Command::SplitCoins(SplitCoins {
+                    coin: Arg::Result(0),
+                    amounts: vec![Arg::Input(2)],
+                }),
+            ]
+        );
+
+        let mut tx = TransactionBuilder::new();
+        let requests = register(&mut tx, &coin_type, &[10]);
+        CoinWithBalanceResolver::split_coin_type(&mut tx, &coin_type, &requests, vec![], 0)
+            .unwrap_err();
+    }
+
+    #[test]
+    fn split_gas_coin() {
+        let mut tx = TransactionBuilder::new();
+        tx.add_gas_objects([coin("0x1000")]);
+        tx.set_limits(crate::limits::TransactionLimits {
+            max_gas_objects: 2,
+            ..Default::default()
+        });
+        let requests = register(&mut tx, &StructTag::myso(), &[10, 20]);
+        CoinWithBalanceResolver::split_gas_coin(
+            &mut tx,
+            &requests,
+            vec![coin("0x1001"), coin("0x1002")],
+            5,
+        );
+
+        // Coins are used as gas objects up to the limit and the rest are merged into the gas coin
+        assert_eq!(tx.gas.len(), 2);
+        assert_eq!(tx.gas[1].object_id, Address::from_static("0x1001"));
+        let ptb = build(tx);
+
+        assert_eq!(
+            ptb.inputs[2],
+            Input::FundsWithdrawal(FundsWithdrawal::new(
+                5,
+                StructTag::myso().into(),
+                WithdrawFrom::Sender
+            ))
+        );
+        assert_eq!(
+            ptb.commands[..3],
+            [
+                redeem_funds(&StructTag::myso(), 2),
+                Command::MergeCoins(MergeCoins {
+                    coin: Arg::Gas,
+                    coins_to_merge: vec![Arg::Input(1), Arg::Result(0)],
+                }),
+                Command::SplitCoins(SplitCoins {
+                    coin: Arg::Gas,
+                    amounts: vec![Arg::Input(3), Arg::Input(4)],
+                }),
+            ]
+        );
     }
 }
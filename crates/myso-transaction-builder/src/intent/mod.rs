@@ -4,15 +4,40 @@ use crate::TransactionBuilder;
 mod coin_with_balance;
 pub use coin_with_balance::CoinWithBalance;
 
-const MAX_GAS_OBJECTS: usize = 250; // 256
-#[allow(unused)]
-const MAX_COMMANDS: usize = 1000; // 1024
-#[allow(unused)]
-const MAX_INPUT_OBJECTS: usize = 2000; // 2048
-const MAX_ARGUMENTS: usize = 500; // 512
-
 pub(crate) type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
 
+/// Serialize a registered intent into its type name and a JSON representation of its data.
+///
+/// Returns `None` if the intent is of an unknown type.
+pub(crate) fn serialize_intent(
+    intent: &(dyn std::any::Any + Send + Sync),
+) -> Option<(&'static str, serde_json::Value)> {
+    if let Some(intent) = intent.downcast_ref::<CoinWithBalance>() {
+        return serde_json::to_value(intent)
+            .ok()
+            .map(|data| (CoinWithBalance::NAME, data));
+    }
+
+    None
+}
+
+/// Deserialize an intent previously serialized with `serialize_intent`, registering its resolver
+/// with the builder.
+pub(crate) fn deserialize_intent(
+    builder: &mut TransactionBuilder,
+    name: &str,
+    data: serde_json::Value,
+) -> Result<Box<dyn std::any::Any + Send + Sync>, BoxError> {
+    match name {
+        CoinWithBalance::NAME => {
+            let intent: CoinWithBalance = serde_json::from_value(data)?;
+            builder.register_resolver(coin_with_balance::CoinWithBalanceResolver);
+            Ok(Box::new(intent))
+        }
+        _ => Err(format!("unknown intent {name}").into()),
+    }
+}
+
 pub(crate) trait Intent: std::any::Any + Send + Sync {
     fn register(self, builder: &mut TransactionBuilder) -> Argument;
 }
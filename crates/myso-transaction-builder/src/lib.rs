@@ -4,14 +4,29 @@
 
 #![cfg_attr(doc_cfg, feature(doc_cfg))]
 
+mod batch;
 mod builder;
 mod error;
 #[cfg(feature = "intents")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "intents")))]
 pub mod intent;
+mod limits;
+mod serialized;
+mod sponsor;
+mod upgrade;
+#[cfg(feature = "intents")]
+mod validation;
 
+pub use batch::BatchedTransactions;
+pub use batch::TransactionBatch;
 pub use builder::Argument;
 pub use builder::Function;
 pub use builder::ObjectInput;
 pub use builder::TransactionBuilder;
 pub use error::Error;
+pub use limits::Limit;
+pub use limits::TransactionLimits;
+pub use sponsor::SponsoredTransaction;
+pub use upgrade::PackageUpgrade;
+pub use upgrade::UpgradePolicy;
+pub use upgrade::package_digest;
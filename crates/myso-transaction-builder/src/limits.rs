@@ -0,0 +1,355 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::Error;
+use myso_sdk_types::Command;
+use myso_sdk_types::Input;
+use myso_sdk_types::Transaction;
+use myso_sdk_types::TransactionKind;
+use myso_sdk_types::TypeTag;
+
+/// A limit imposed by the protocol on the size or shape of a transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Limit {
+    /// The serialized size of the transaction.
+    TransactionSize,
+    /// The number of object inputs.
+    InputObjects,
+    /// The number of commands.
+    Commands,
+    /// The size of a single pure input.
+    PureArgumentSize,
+    /// The number of arguments to a single command.
+    Arguments,
+    /// The number of type arguments to a single Move call.
+    TypeArguments,
+    /// The depth of a single type argument.
+    TypeArgumentDepth,
+    /// The number of gas payment objects.
+    GasObjects,
+}
+
+impl Limit {
+    /// The name of the `ProtocolConfig` attribute defining this limit.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Limit::TransactionSize => "max_tx_size_bytes",
+            Limit::InputObjects => "max_input_objects",
+            Limit::Commands => "max_programmable_tx_commands",
+            Limit::PureArgumentSize => "max_pure_argument_size",
+            Limit::Arguments => "max_arguments",
+            Limit::TypeArguments => "max_type_arguments",
+            Limit::TypeArgumentDepth => "max_type_argument_depth",
+            Limit::GasObjects => "max_gas_payment_objects",
+        }
+    }
+}
+
+impl std::fmt::Display for Limit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The limits a transaction is checked against when it is built.
+///
+/// The default limits match those of the current protocol version. Limits for the protocol
+/// version a network is actually running can be fetched with [`TransactionLimits::fetch`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TransactionLimits {
+    /// The maximum serialized size of a transaction, in bytes.
+    ///
+    /// Only the transaction data itself is checked, the signatures submitted along with it count
+    /// towards the protocol's limit as well.
+    pub max_tx_size_bytes: usize,
+    /// The maximum number of object inputs to a transaction.
+    pub max_input_objects: usize,
+    /// The maximum number of commands in a programmable transaction.
+    pub max_commands: usize,
+    /// The maximum size of a single pure input, in bytes.
+    pub max_pure_argument_size: usize,
+    /// The maximum number of arguments to a single command.
+    pub max_arguments: usize,
+    /// The maximum number of type arguments to a single Move call.
+    pub max_type_arguments: usize,
+    /// The maximum depth of a single type argument.
+    pub max_type_argument_depth: usize,
+    /// The maximum number of gas payment objects.
+    pub max_gas_objects: usize,
+}
+
+impl Default for TransactionLimits {
+    fn default() -> Self {
+        Self {
+            max_tx_size_bytes: 128 * 1024,
+            max_input_objects: 2048,
+            max_commands: 1024,
+            max_pure_argument_size: 16 * 1024,
+            max_arguments: 512,
+            max_type_arguments: 16,
+            max_type_argument_depth: 16,
+            max_gas_objects: 256,
+        }
+    }
+}
+
+impl TransactionLimits {
+    /// Read the limits from a `ProtocolConfig`, using the default for any limit which isn't
+    /// present in its attributes.
+    #[cfg(feature = "intents")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "intents")))]
+    pub fn from_protocol_config(config: &myso_rpc::proto::myso::rpc::v2::ProtocolConfig) -> Self {
+        let mut limits = Self::default();
+        for (limit, value) in [
+            (Limit::TransactionSize, &mut limits.max_tx_size_bytes),
+            (Limit::InputObjects, &mut limits.max_input_objects),
+            (Limit::Commands, &mut limits.max_commands),
+            (Limit::PureArgumentSize, &mut limits.max_pure_argument_size),
+            (Limit::Arguments, &mut limits.max_arguments),
+            (Limit::TypeArguments, &mut limits.max_type_arguments),
+            (
+                Limit::TypeArgumentDepth,
+                &mut limits.max_type_argument_depth,
+            ),
+            (Limit::GasObjects, &mut limits.max_gas_objects),
+        ] {
+            if let Some(configured) = config
+                .attributes
+                .get(limit.as_str())
+                .and_then(|attribute| attribute.parse().ok())
+            {
+                *value = configured;
+            }
+        }
+        limits
+    }
+
+    /// Fetch the limits of the protocol version of the current epoch.
+    #[cfg(feature = "intents")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "intents")))]
+    pub async fn fetch(client: &mut myso_rpc::Client) -> Result<Self, Error> {
+        use myso_rpc::field::FieldMask;
+        use myso_rpc::field::FieldMaskUtil;
+        use myso_rpc::proto::myso::rpc::v2::GetEpochRequest;
+
+        let response = client
+            .ledger_client()
+            .get_epoch(
+                GetEpochRequest::latest()
+                    .with_read_mask(FieldMask::from_paths(["protocol_config"])),
+            )
+            .await
+            .map_err(|e| Error::Input(format!("error fetching protocol config: {e}")))?
+            .into_inner();
+
+        Ok(Self::from_protocol_config(
+            response.epoch().protocol_config(),
+        ))
+    }
+
+    /// Check that a transaction is within these limits.
+    pub fn check(&self, transaction: &Transaction) -> Result<(), Error> {
+        check(
+            Limit::GasObjects,
+            transaction.gas_payment.objects.len(),
+            self.max_gas_objects,
+        )?;
+        self.check_kind(&transaction.kind)?;
+
+        let size = bcs::to_bytes(transaction)
+            .map_err(|e| Error::Input(e.to_string()))?
+            .len();
+        check(Limit::TransactionSize, size, self.max_tx_size_bytes)
+    }
+
+    /// Check that a transaction kind is within these limits.
+    ///
+    /// Only programmable transactions are checked.
+    pub fn check_kind(&self, kind: &TransactionKind) -> Result<(), Error> {
+        let TransactionKind::ProgrammableTransaction(ptb) = kind else {
+            return Ok(());
+        };
+
+        let mut input_objects = 0;
+        for input in &ptb.inputs {
+            match input {
+                Input::Pure(bytes) => {
+                    check(
+                        Limit::PureArgumentSize,
+                        bytes.len(),
+                        self.max_pure_argument_size,
+                    )?;
+                }
+                Input::ImmutableOrOwned(_) | Input::Shared(_) | Input::Receiving(_) => {
+                    input_objects += 1;
+                }
+                _ => {}
+            }
+        }
+        check(Limit::InputObjects, input_objects, self.max_input_objects)?;
+        check(Limit::Commands, ptb.commands.len(), self.max_commands)?;
+
+        for command in &ptb.commands {
+            let (arguments, type_arguments) = match command {
+                Command::MoveCall(call) => (call.arguments.len(), call.type_arguments.as_slice()),
+                Command::TransferObjects(transfer) => (transfer.objects.len() + 1, &[][..]),
+                Command::SplitCoins(split) => (split.amounts.len() + 1, &[][..]),
+                Command::MergeCoins(merge) => (merge.coins_to_merge.len() + 1, &[][..]),
+                Command::MakeMoveVector(vector) => (vector.elements.len(), vector.type_.as_slice()),
+                _ => (0, &[][..]),
+            };
+
+            check(Limit::Arguments, arguments, self.max_arguments)?;
+            check(
+                Limit::TypeArguments,
+                type_arguments.len(),
+                self.max_type_arguments,
+            )?;
+            for type_argument in type_arguments {
+                check(
+                    Limit::TypeArgumentDepth,
+                    type_depth(type_argument),
+                    self.max_type_argument_depth,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn check(limit: Limit, actual: usize, max: usize) -> Result<(), Error> {
+    if actual > max {
+        Err(Error::LimitExceeded { limit, actual, max })
+    } else {
+        Ok(())
+    }
+}
+
+fn type_depth(type_: &TypeTag) -> usize {
+    match type_ {
+        TypeTag::Vector(inner) => 1 + type_depth(inner),
+        TypeTag::Struct(tag) => 1 + tag.type_params().iter().map(type_depth).max().unwrap_or(0),
+        _ => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ObjectInput;
+    use crate::TransactionBuilder;
+    use myso_sdk_types::Address;
+    use myso_sdk_types::Digest;
+
+    fn builder() -> TransactionBuilder {
+        let mut tx = TransactionBuilder::new();
+        tx.add_gas_objects(vec![ObjectInput::owned(
+            Address::from_static("0xd879"),
+            2,
+            Digest::from_static("2ZigdvsZn5BMeszscPQZq9z8ebnS2FpmAuRbAi9ednCk"),
+        )]);
+        tx.set_gas_budget(500000000);
+        tx.set_gas_price(1000);
+        tx.set_sender(Address::from_static("0xc574"));
+        tx
+    }
+
+    #[test]
+    fn within_limits() {
+        let mut tx = builder();
+        let coin = tx.gas();
+        let amount = tx.pure(&1u64);
+        let coins = tx.split_coins(coin, vec![amount]);
+        let recipient = tx.pure(&Address::TWO);
+        tx.transfer_objects(coins, recipient);
+        tx.try_build().unwrap();
+    }
+
+    #[test]
+    fn limits_exceeded() {
+        let limits = TransactionLimits {
+            max_commands: 2,
+            max_pure_argument_size: 8,
+            max_type_argument_depth: 2,
+            ..Default::default()
+        };
+
+        let assert_exceeds = |tx: TransactionBuilder, expected: Limit| {
+            let mut tx = tx;
+            tx.set_limits(limits.clone());
+            match tx.try_build() {
+                Err(Error::LimitExceeded { limit, .. }) => assert_eq!(limit, expected),
+                _ => panic!("expected {expected} to be exceeded"),
+            }
+        };
+
+        let mut tx = builder();
+        let coin = tx.gas();
+        for _ in 0..3 {
+            let amount = tx.pure(&1u64);
+            tx.split_coins(coin, vec![amount]);
+        }
+        assert_exceeds(tx, Limit::Commands);
+
+        let mut tx = builder();
+        let recipient = tx.pure(&"a string longer than eight bytes");
+        let coin = tx.gas();
+        tx.transfer_objects(vec![coin], recipient);
+        assert_exceeds(tx, Limit::PureArgumentSize);
+
+        let mut tx = builder();
+        tx.make_move_vec(Some("vector<vector<u8>>".parse().unwrap()), vec![]);
+        assert_exceeds(tx, Limit::TypeArgumentDepth);
+
+        let mut tx = builder();
+        let limits = TransactionLimits {
+            max_tx_size_bytes: 100,
+            ..Default::default()
+        };
+        tx.set_limits(limits);
+        let coin = tx.gas();
+        let recipient = tx.pure(&Address::TWO);
+        tx.transfer_objects(vec![coin], recipient);
+        assert!(matches!(
+            tx.try_build(),
+            Err(Error::LimitExceeded {
+                limit: Limit::TransactionSize,
+                max: 100,
+                ..
+            })
+        ));
+    }
+
+    #[cfg(feature = "intents")]
+    #[test]
+    fn protocol_config() {
+        let mut config = myso_rpc::proto::myso::rpc::v2::ProtocolConfig::default();
+        config
+            .attributes
+            .insert("max_programmable_tx_commands".to_owned(), "10".to_owned());
+        config
+            .attributes
+            .insert("max_arguments".to_owned(), "not a number".to_owned());
+
+        let limits = TransactionLimits::from_protocol_config(&config);
+        assert_eq!(limits.max_commands, 10);
+        assert_eq!(
+            limits.max_arguments,
+            TransactionLimits::default().max_arguments
+        );
+    }
+
+    #[test]
+    fn type_argument_depth() {
+        assert_eq!(type_depth(&TypeTag::U8), 1);
+        assert_eq!(type_depth(&"vector<u8>".parse().unwrap()), 2);
+        assert_eq!(
+            type_depth(&"0x2::coin::Coin<vector<0x2::myso::MYSO>>".parse().unwrap()),
+            3
+        );
+    }
+}
@@ -0,0 +1,924 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A stable, versioned JSON or BCS representation of an unresolved [`TransactionBuilder`].
+//!
+//! Every input, command and intent registered with a builder is identified by the position at
+//! which it was added, which is what [`Argument`] handles refer to. The serialized form records
+//! each of these in order so that a builder, and any handles into it, can be faithfully restored.
+//!
+//! Both encodings share the same structure and version. Binary data is Base64 encoded in JSON and
+//! stored as raw bytes in BCS, while intent data, which is arbitrary JSON, is stored as a JSON
+//! string in BCS.
+
+use crate::Argument;
+use crate::ObjectInput;
+use crate::TransactionBuilder;
+use crate::builder::Command;
+use crate::builder::CommandKind;
+use crate::builder::InputArg;
+use crate::builder::InputArgKind;
+use crate::builder::MakeMoveVector;
+use crate::builder::MergeCoins;
+use crate::builder::MoveCall;
+use crate::builder::ObjectKind;
+use crate::builder::Publish;
+use crate::builder::ResolvedArgument;
+use crate::builder::SplitCoins;
+use crate::builder::TransferObjects;
+use crate::builder::Upgrade;
+use crate::error::Error;
+use crate::limits::TransactionLimits;
+use myso_sdk_types::Address;
+use myso_sdk_types::Digest;
+use myso_sdk_types::FundsWithdrawal;
+use myso_sdk_types::Identifier;
+use myso_sdk_types::TransactionExpiration;
+use myso_sdk_types::TypeTag;
+use myso_sdk_types::WithdrawFrom;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// The current version of the serialized format.
+const VERSION: u8 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct SerializedTransactionBuilder {
+    version: u8,
+    sender: Option<Address>,
+    sponsor: Option<Address>,
+    expiration: Option<TransactionExpiration>,
+    gas_budget: Option<u64>,
+    gas_price: Option<u64>,
+    gas_objects: Vec<SerializedObjectInput>,
+    /// Missing from builders serialized before limits could be configured.
+    #[serde(default)]
+    limits: SerializedTransactionLimits,
+    /// Whether Move calls are validated when building, see
+    /// [`TransactionBuilder::set_validate_move_calls`].
+    #[serde(default)]
+    validate_move_calls: bool,
+    /// Every input, command and intent, ordered by the id of the argument it produces.
+    entries: Vec<Entry>,
+    loaded_inputs: Vec<SerializedArgument>,
+    loaded_results: Vec<SerializedArgument>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Entry {
+    Gas,
+    Pure {
+        #[serde(with = "base64")]
+        bytes: Vec<u8>,
+        /// Whether this input is excluded from deduplication with identical pure inputs.
+        unique: bool,
+    },
+    Object(SerializedObjectInput),
+    FundsWithdrawal {
+        amount: u64,
+        coin_type: TypeTag,
+        source: SerializedWithdrawFrom,
+    },
+    Command {
+        command: SerializedCommand,
+        dependencies: Vec<SerializedArgument>,
+    },
+    Intent {
+        name: String,
+        #[serde(with = "json")]
+        data: serde_json::Value,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedObjectInput {
+    object_id: Address,
+    kind: Option<SerializedObjectKind>,
+    version: Option<u64>,
+    digest: Option<Digest>,
+    mutable: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum SerializedObjectKind {
+    Shared,
+    Receiving,
+    ImmutableOrOwned,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedTransactionLimits {
+    max_tx_size_bytes: usize,
+    max_input_objects: usize,
+    max_commands: usize,
+    max_pure_argument_size: usize,
+    max_arguments: usize,
+    max_type_arguments: usize,
+    max_type_argument_depth: usize,
+    max_gas_objects: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum SerializedWithdrawFrom {
+    Sender,
+    Sponsor,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+struct SerializedArgument {
+    id: usize,
+    #[serde(default)]
+    sub_index: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum SerializedCommand {
+    MoveCall {
+        package: Address,
+        module: Identifier,
+        function: Identifier,
+        type_arguments: Vec<TypeTag>,
+        arguments: Vec<SerializedArgument>,
+    },
+    TransferObjects {
+        objects: Vec<SerializedArgument>,
+        address: SerializedArgument,
+    },
+    SplitCoins {
+        coin: SerializedArgument,
+        amounts: Vec<SerializedArgument>,
+    },
+    MergeCoins {
+        coin: SerializedArgument,
+        coins_to_merge: Vec<SerializedArgument>,
+    },
+    Publish {
+        #[serde(with = "base64_vec")]
+        modules: Vec<Vec<u8>>,
+        dependencies: Vec<Address>,
+    },
+    MakeMoveVector {
+        type_: Option<TypeTag>,
+        elements: Vec<SerializedArgument>,
+    },
+    Upgrade {
+        #[serde(with = "base64_vec")]
+        modules: Vec<Vec<u8>>,
+        dependencies: Vec<Address>,
+        package: Address,
+        ticket: SerializedArgument,
+    },
+}
+
+impl TransactionBuilder {
+    /// Serialize this builder, including any unresolved object inputs and intents, to JSON so
+    /// that it can be stored or handed off to another process and later restored with
+    /// [`TransactionBuilder::from_json`].
+    pub fn to_json(&self) -> Result<String, Error> {
+        let serialized = SerializedTransactionBuilder::from_builder(self)?;
+        serde_json::to_string(&serialized).map_err(|e| Error::Input(e.to_string()))
+    }
+
+    /// Restore a builder previously serialized with [`TransactionBuilder::to_json`].
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        let serialized: SerializedTransactionBuilder =
+            serde_json::from_str(json).map_err(|e| Error::Input(e.to_string()))?;
+        serialized.into_builder()
+    }
+
+    /// Serialize this builder to BCS. This is the same representation as
+    /// [`TransactionBuilder::to_json`] in a more compact binary encoding, and can be restored
+    /// with [`TransactionBuilder::from_bcs`].
+    pub fn to_bcs(&self) -> Result<Vec<u8>, Error> {
+        let serialized = SerializedTransactionBuilder::from_builder(self)?;
+        bcs::to_bytes(&serialized).map_err(|e| Error::Input(e.to_string()))
+    }
+
+    /// Restore a builder previously serialized with [`TransactionBuilder::to_bcs`].
+    pub fn from_bcs(bytes: &[u8]) -> Result<Self, Error> {
+        let serialized: SerializedTransactionBuilder =
+            bcs::from_bytes(bytes).map_err(|e| Error::Input(e.to_string()))?;
+        serialized.into_builder()
+    }
+}
+
+impl SerializedTransactionBuilder {
+    fn from_builder(builder: &TransactionBuilder) -> Result<Self, Error> {
+        let mut entries = (0..builder.arguments.len())
+            .map(|_| None)
+            .collect::<Vec<Option<Entry>>>();
+
+        if builder
+            .arguments
+            .values()
+            .any(|argument| !matches!(argument, ResolvedArgument::Unresolved))
+        {
+            return Err(Error::Input(
+                "unable to serialize a partially resolved transaction".to_owned(),
+            ));
+        }
+
+        for (kind, (id, input)) in &builder.inputs {
+            let entry = match input {
+                InputArg::Gas => Entry::Gas,
+                InputArg::Pure(bytes) => Entry::Pure {
+                    bytes: bytes.clone(),
+                    unique: matches!(kind, InputArgKind::UniquePureInput(_)),
+                },
+                InputArg::Object(object) => Entry::Object(object.into()),
+                InputArg::FundsWithdrawal(withdrawal) => Entry::FundsWithdrawal {
+                    amount: withdrawal.amount().ok_or_else(|| {
+                        Error::Input("unsupported funds withdrawal reservation".to_owned())
+                    })?,
+                    coin_type: withdrawal.coin_type().clone(),
+                    source: match withdrawal.source() {
+                        WithdrawFrom::Sender => SerializedWithdrawFrom::Sender,
+                        WithdrawFrom::Sponsor => SerializedWithdrawFrom::Sponsor,
+                        _ => {
+                            return Err(Error::Input(
+                                "unsupported funds withdrawal source".to_owned(),
+                            ));
+                        }
+                    },
+                },
+            };
+            entries[*id] = Some(entry);
+        }
+
+        for (id, command) in &builder.commands {
+            entries[*id] = Some(Entry::Command {
+                command: (&command.kind).into(),
+                dependencies: command.dependencies.iter().map(Into::into).collect(),
+            });
+        }
+
+        for (id, intent) in &builder.intents {
+            entries[*id] = Some(intent_entry(intent.as_ref())?);
+        }
+
+        Ok(Self {
+            version: VERSION,
+            sender: builder.sender,
+            sponsor: builder.sponsor,
+            expiration: builder.expiration,
+            gas_budget: builder.gas_budget,
+            gas_price: builder.gas_price,
+            gas_objects: builder.gas.iter().map(Into::into).collect(),
+            limits: (&builder.limits).into(),
+            validate_move_calls: validate_move_calls(builder),
+            entries: entries
+                .into_iter()
+                .collect::<Option<_>>()
+                .ok_or_else(|| Error::Input("BUG: argument without a source".to_owned()))?,
+            loaded_inputs: builder.loaded_inputs.iter().map(Into::into).collect(),
+            loaded_results: builder.loaded_results.iter().map(Into::into).collect(),
+        })
+    }
+
+    fn into_builder(self) -> Result<TransactionBuilder, Error> {
+        if self.version != VERSION {
+            return Err(Error::Input(format!(
+                "unsupported serialized transaction version {}",
+                self.version
+            )));
+        }
+
+        // Entries can only refer to arguments produced by earlier entries, which rules out cycles
+        let argument = |argument: SerializedArgument, bound: usize| -> Result<Argument, Error> {
+            if argument.id >= bound {
+                return Err(Error::Input(format!(
+                    "argument {} does not exist before entry {bound}",
+                    argument.id
+                )));
+            }
+            Ok(Argument {
+                id: argument.id,
+                sub_index: argument.sub_index,
+            })
+        };
+        let arguments =
+            |arguments: Vec<SerializedArgument>, bound: usize| -> Result<Vec<Argument>, Error> {
+                arguments
+                    .into_iter()
+                    .map(|arg| argument(arg, bound))
+                    .collect()
+            };
+        let count = self.entries.len();
+
+        let mut builder = TransactionBuilder::new();
+        builder.sender = self.sender;
+        builder.sponsor = self.sponsor;
+        builder.expiration = self.expiration;
+        builder.gas_budget = self.gas_budget;
+        builder.gas_price = self.gas_price;
+        builder.gas = self.gas_objects.into_iter().map(Into::into).collect();
+        builder.limits = self.limits.into();
+        set_validate_move_calls(&mut builder, self.validate_move_calls)?;
+
+        for (id, entry) in self.entries.into_iter().enumerate() {
+            builder.arguments.insert(id, ResolvedArgument::Unresolved);
+
+            let (kind, input) = match entry {
+                Entry::Gas => (InputArgKind::Gas, InputArg::Gas),
+                Entry::Pure {
+                    bytes,
+                    unique: true,
+                } => (InputArgKind::UniquePureInput(id), InputArg::Pure(bytes)),
+                Entry::Pure {
+                    bytes,
+                    unique: false,
+                } => (
+                    InputArgKind::PureInput(bytes.clone()),
+                    InputArg::Pure(bytes),
+                ),
+                Entry::Object(object) => (
+                    InputArgKind::ObjectInput(object.object_id),
+                    InputArg::Object(object.into()),
+                ),
+                Entry::FundsWithdrawal {
+                    amount,
+                    coin_type,
+                    source,
+                } => {
+                    let source = match source {
+                        SerializedWithdrawFrom::Sender => WithdrawFrom::Sender,
+                        SerializedWithdrawFrom::Sponsor => WithdrawFrom::Sponsor,
+                    };
+                    (
+                        InputArgKind::FundsWithdrawal(id),
+                        InputArg::FundsWithdrawal(FundsWithdrawal::new(amount, coin_type, source)),
+                    )
+                }
+                Entry::Command {
+                    command,
+                    dependencies,
+                } => {
+                    let kind = command
+                        .into_command_kind(&|args| arguments(args, id), &|arg| argument(arg, id))?;
+                    builder.commands.insert(
+                        id,
+                        Command {
+                            kind,
+                            dependencies: arguments(dependencies, id)?,
+                        },
+                    );
+                    continue;
+                }
+                Entry::Intent { name, data } => {
+                    let intent = load_intent(&mut builder, &name, data)?;
+                    builder.intents.insert(id, intent);
+                    continue;
+                }
+            };
+
+            if builder.inputs.insert(kind, (id, input)).is_some() {
+                return Err(Error::Input(format!("duplicate input {id}")));
+            }
+        }
+
+        builder.loaded_inputs = arguments(self.loaded_inputs, count)?;
+        builder.loaded_results = arguments(self.loaded_results, count)?;
+
+        Ok(builder)
+    }
+}
+
+#[cfg(feature = "intents")]
+fn validate_move_calls(builder: &TransactionBuilder) -> bool {
+    builder.validate_move_calls
+}
+
+#[cfg(not(feature = "intents"))]
+fn validate_move_calls(_builder: &TransactionBuilder) -> bool {
+    false
+}
+
+#[cfg(feature = "intents")]
+fn set_validate_move_calls(builder: &mut TransactionBuilder, validate: bool) -> Result<(), Error> {
+    builder.validate_move_calls = validate;
+    Ok(())
+}
+
+#[cfg(not(feature = "intents"))]
+fn set_validate_move_calls(_builder: &mut TransactionBuilder, validate: bool) -> Result<(), Error> {
+    if validate {
+        return Err(Error::Input(
+            "unable to validate move calls without the `intents` feature".to_owned(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(feature = "intents")]
+fn intent_entry(intent: &(dyn std::any::Any + Send + Sync)) -> Result<Entry, Error> {
+    let (name, data) = crate::intent::serialize_intent(intent)
+        .ok_or_else(|| Error::Input("unable to serialize intent".to_owned()))?;
+    Ok(Entry::Intent {
+        name: name.to_owned(),
+        data,
+    })
+}
+
+#[cfg(not(feature = "intents"))]
+fn intent_entry(_intent: &(dyn std::any::Any + Send + Sync)) -> Result<Entry, Error> {
+    Err(Error::Input("unable to serialize intent".to_owned()))
+}
+
+#[cfg(feature = "intents")]
+fn load_intent(
+    builder: &mut TransactionBuilder,
+    name: &str,
+    data: serde_json::Value,
+) -> Result<Box<dyn std::any::Any + Send + Sync>, Error> {
+    crate::intent::deserialize_intent(builder, name, data).map_err(|e| Error::Input(e.to_string()))
+}
+
+#[cfg(not(feature = "intents"))]
+fn load_intent(
+    _builder: &mut TransactionBuilder,
+    name: &str,
+    _data: serde_json::Value,
+) -> Result<Box<dyn std::any::Any + Send + Sync>, Error> {
+    Err(Error::Input(format!(
+        "unable to load intent {name} without the `intents` feature"
+    )))
+}
+
+impl From<&Argument> for SerializedArgument {
+    fn from(argument: &Argument) -> Self {
+        Self {
+            id: argument.id,
+            sub_index: argument.sub_index,
+        }
+    }
+}
+
+impl Default for SerializedTransactionLimits {
+    fn default() -> Self {
+        (&TransactionLimits::default()).into()
+    }
+}
+
+impl From<&TransactionLimits> for SerializedTransactionLimits {
+    fn from(limits: &TransactionLimits) -> Self {
+        Self {
+            max_tx_size_bytes: limits.max_tx_size_bytes,
+            max_input_objects: limits.max_input_objects,
+            max_commands: limits.max_commands,
+            max_pure_argument_size: limits.max_pure_argument_size,
+            max_arguments: limits.max_arguments,
+            max_type_arguments: limits.max_type_arguments,
+            max_type_argument_depth: limits.max_type_argument_depth,
+            max_gas_objects: limits.max_gas_objects,
+        }
+    }
+}
+
+impl From<SerializedTransactionLimits> for TransactionLimits {
+    fn from(limits: SerializedTransactionLimits) -> Self {
+        Self {
+            max_tx_size_bytes: limits.max_tx_size_bytes,
+            max_input_objects: limits.max_input_objects,
+            max_commands: limits.max_commands,
+            max_pure_argument_size: limits.max_pure_argument_size,
+            max_arguments: limits.max_arguments,
+            max_type_arguments: limits.max_type_arguments,
+            max_type_argument_depth: limits.max_type_argument_depth,
+            max_gas_objects: limits.max_gas_objects,
+        }
+    }
+}
+
+impl From<&ObjectInput> for SerializedObjectInput {
+    fn from(object: &ObjectInput) -> Self {
+        Self {
+            object_id: object.object_id,
+            kind: object.kind.map(|kind| match kind {
+                ObjectKind::Shared => SerializedObjectKind::Shared,
+                ObjectKind::Receiving => SerializedObjectKind::Receiving,
+                ObjectKind::ImmutableOrOwned => SerializedObjectKind::ImmutableOrOwned,
+            }),
+            version: object.version,
+            digest: object.digest,
+            mutable: object.mutable,
+        }
+    }
+}
+
+impl From<SerializedObjectInput> for ObjectInput {
+    fn from(object: SerializedObjectInput) -> Self {
+        Self {
+            object_id: object.object_id,
+            kind: object.kind.map(|kind| match kind {
+                SerializedObjectKind::Shared => ObjectKind::Shared,
+                SerializedObjectKind::Receiving => ObjectKind::Receiving,
+                SerializedObjectKind::ImmutableOrOwned => ObjectKind::ImmutableOrOwned,
+            }),
+            version: object.version,
+            digest: object.digest,
+            mutable: object.mutable,
+        }
+    }
+}
+
+impl From<&CommandKind> for SerializedCommand {
+    fn from(command: &CommandKind) -> Self {
+        let many = |arguments: &[Argument]| arguments.iter().map(Into::into).collect();
+
+        match command {
+            CommandKind::MoveCall(MoveCall {
+                package,
+                module,
+                function,
+                type_arguments,
+                arguments,
+            }) => Self::MoveCall {
+                package: *package,
+                module: module.clone(),
+                function: function.clone(),
+                type_arguments: type_arguments.clone(),
+                arguments: many(arguments),
+            },
+            CommandKind::TransferObjects(TransferObjects { objects, address }) => {
+                Self::TransferObjects {
+                    objects: many(objects),
+                    address: address.into(),
+                }
+            }
+            CommandKind::SplitCoins(SplitCoins { coin, amounts }) => Self::SplitCoins {
+                coin: coin.into(),
+                amounts: many(amounts),
+            },
+            CommandKind::MergeCoins(MergeCoins {
+                coin,
+                coins_to_merge,
+            }) => Self::MergeCoins {
+                coin: coin.into(),
+                coins_to_merge: many(coins_to_merge),
+            },
+            CommandKind::Publish(Publish {
+                modules,
+                dependencies,
+            }) => Self::Publish {
+                modules: modules.clone(),
+                dependencies: dependencies.clone(),
+            },
+            CommandKind::MakeMoveVector(MakeMoveVector { type_, elements }) => {
+                Self::MakeMoveVector {
+                    type_: type_.clone(),
+                    elements: many(elements),
+                }
+            }
+            CommandKind::Upgrade(Upgrade {
+                modules,
+                dependencies,
+                package,
+                ticket,
+            }) => Self::Upgrade {
+                modules: modules.clone(),
+                dependencies: dependencies.clone(),
+                package: *package,
+                ticket: ticket.into(),
+            },
+        }
+    }
+}
+
+impl SerializedCommand {
+    fn into_command_kind(
+        self,
+        many: &dyn Fn(Vec<SerializedArgument>) -> Result<Vec<Argument>, Error>,
+        one: &dyn Fn(SerializedArgument) -> Result<Argument, Error>,
+    ) -> Result<CommandKind, Error> {
+        let kind = match self {
+            Self::MoveCall {
+                package,
+                module,
+                function,
+                type_arguments,
+                arguments,
+            } => CommandKind::MoveCall(MoveCall {
+                package,
+                module,
+                function,
+                type_arguments,
+                arguments: many(arguments)?,
+            }),
+            Self::TransferObjects { objects, address } => {
+                CommandKind::TransferObjects(TransferObjects {
+                    objects: many(objects)?,
+                    address: one(address)?,
+                })
+            }
+            Self::SplitCoins { coin, amounts } => CommandKind::SplitCoins(SplitCoins {
+                coin: one(coin)?,
+                amounts: many(amounts)?,
+            }),
+            Self::MergeCoins {
+                coin,
+                coins_to_merge,
+            } => CommandKind::MergeCoins(MergeCoins {
+                coin: one(coin)?,
+                coins_to_merge: many(coins_to_merge)?,
+            }),
+            Self::Publish {
+                modules,
+                dependencies,
+            } => CommandKind::Publish(Publish {
+                modules,
+                dependencies,
+            }),
+            Self::MakeMoveVector { type_, elements } => {
+                CommandKind::MakeMoveVector(MakeMoveVector {
+                    type_,
+                    elements: many(elements)?,
+                })
+            }
+            Self::Upgrade {
+                modules,
+                dependencies,
+                package,
+                ticket,
+            } => CommandKind::Upgrade(Upgrade {
+                modules,
+                dependencies,
+                package,
+                ticket: one(ticket)?,
+            }),
+        };
+
+        Ok(kind)
+    }
+}
+
+mod base64 {
+    use base64ct::Base64;
+    use base64ct::Encoding;
+    use serde::Deserialize;
+    use serde::Deserializer;
+    use serde::Serializer;
+
+    pub(super) fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&Base64::encode_string(bytes))
+        } else {
+            serializer.serialize_bytes(bytes)
+        }
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<u8>, D::Error> {
+        if deserializer.is_human_readable() {
+            let s = String::deserialize(deserializer)?;
+            Base64::decode_vec(&s).map_err(serde::de::Error::custom)
+        } else {
+            Vec::deserialize(deserializer)
+        }
+    }
+}
+
+mod base64_vec {
+    use base64ct::Base64;
+    use base64ct::Encoding;
+    use serde::Deserialize;
+    use serde::Deserializer;
+    use serde::Serialize;
+    use serde::Serializer;
+
+    pub(super) fn serialize<S: Serializer>(
+        values: &[Vec<u8>],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_seq(values.iter().map(|bytes| Base64::encode_string(bytes)))
+        } else {
+            values.serialize(serializer)
+        }
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<Vec<u8>>, D::Error> {
+        if deserializer.is_human_readable() {
+            Vec::<String>::deserialize(deserializer)?
+                .iter()
+                .map(|s| Base64::decode_vec(s).map_err(serde::de::Error::custom))
+                .collect()
+        } else {
+            Vec::deserialize(deserializer)
+        }
+    }
+}
+
+/// Binary formats such as BCS aren't self-describing and can't represent an arbitrary JSON value,
+/// so it is embedded as a JSON string instead.
+mod json {
+    use serde::Deserialize;
+    use serde::Deserializer;
+    use serde::Serialize;
+    use serde::Serializer;
+
+    pub(super) fn serialize<S: Serializer>(
+        value: &serde_json::Value,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            value.serialize(serializer)
+        } else {
+            serializer.serialize_str(&value.to_string())
+        }
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<serde_json::Value, D::Error> {
+        if deserializer.is_human_readable() {
+            serde_json::Value::deserialize(deserializer)
+        } else {
+            let s = String::deserialize(deserializer)?;
+            serde_json::from_str(&s).map_err(serde::de::Error::custom)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Function;
+
+    fn builder() -> TransactionBuilder {
+        let mut tx = TransactionBuilder::new();
+        let coin = tx.object(ObjectInput::new(Address::from_static("0x12345")));
+        let shared = tx.object(ObjectInput::shared(Address::from_static("0x6"), 1, false));
+        let amount = tx.pure(&1_000u64);
+        let _unique = tx.pure_unique(&1_000u64);
+        let withdrawal = tx.funds_withdrawal(5, TypeTag::U64, WithdrawFrom::Sponsor);
+        let gas = tx.gas();
+        let split = tx.split_coins(gas, vec![amount]);
+        tx.merge_coins(coin, split);
+        tx.move_call(
+            Function::new(
+                Address::from_static("0xabc"),
+                Identifier::from_static("module"),
+                Identifier::from_static("function"),
+            )
+            .with_type_args(vec![TypeTag::U64]),
+            vec![shared, withdrawal],
+        );
+        tx.publish(vec![vec![1, 2, 3]], vec![Address::TWO]);
+        let recipient = tx.pure(&Address::from_static("0xdef"));
+        tx.transfer_objects(vec![coin], recipient);
+        tx.set_sender(Address::from_static("0x1"));
+        tx.set_sponsor(Address::from_static("0x2"));
+        tx.set_gas_budget(500000000);
+        tx.set_gas_price(1000);
+        tx.add_gas_objects([ObjectInput::owned(
+            Address::from_static("0xd879"),
+            2,
+            Digest::from_static("2ZigdvsZn5BMeszscPQZq9z8ebnS2FpmAuRbAi9ednCk"),
+        )]);
+        tx
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let tx = builder();
+        let json = tx.to_json().unwrap();
+
+        let restored = TransactionBuilder::from_json(&json).unwrap();
+        assert_eq!(restored.to_json().unwrap(), json);
+
+        // Once the incomplete object is filled in both build to the same transaction
+        let complete = |mut tx: TransactionBuilder| {
+            tx.object(
+                ObjectInput::owned(
+                    Address::from_static("0x12345"),
+                    2,
+                    Digest::from_static("7opR9rFUYivSTqoJHvFb9p6p54THyHTatMG6id4JKZR9"),
+                )
+                .with_mutable(true),
+            );
+            tx.try_build().unwrap()
+        };
+        assert_eq!(complete(restored), complete(builder()));
+    }
+
+    #[test]
+    fn bcs_round_trip() {
+        let tx = builder();
+        let bytes = tx.to_bcs().unwrap();
+
+        // Both encodings restore the same builder
+        let restored = TransactionBuilder::from_bcs(&bytes).unwrap();
+        assert_eq!(restored.to_bcs().unwrap(), bytes);
+        assert_eq!(restored.to_json().unwrap(), tx.to_json().unwrap());
+
+        let mut bytes = bytes;
+        bytes[0] = 2;
+        assert!(TransactionBuilder::from_bcs(&bytes).is_err());
+    }
+
+    #[test]
+    fn limits_round_trip() {
+        let limits = TransactionLimits {
+            max_arguments: 10,
+            max_gas_objects: 3,
+            ..Default::default()
+        };
+
+        let mut tx = builder();
+        tx.set_limits(limits.clone());
+        let json = tx.to_json().unwrap();
+        assert_eq!(TransactionBuilder::from_json(&json).unwrap().limits, limits);
+
+        // Builders serialized before limits were recorded use the default limits
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        value.as_object_mut().unwrap().remove("limits");
+        assert_eq!(
+            TransactionBuilder::from_json(&value.to_string())
+                .unwrap()
+                .limits,
+            TransactionLimits::default()
+        );
+    }
+
+    #[test]
+    fn rejects_invalid() {
+        let json = builder().to_json().unwrap();
+
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        value["version"] = 2.into();
+        assert!(TransactionBuilder::from_json(&value.to_string()).is_err());
+
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        value["loaded_inputs"] = serde_json::json!([{ "id": 100 }]);
+        assert!(TransactionBuilder::from_json(&value.to_string()).is_err());
+
+        // Commands can only depend on the results of earlier entries
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let (id, _) = value["entries"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .enumerate()
+            .find(|(_, entry)| entry.get("command").is_some())
+            .unwrap();
+        for dependency in [id, id + 1] {
+            let mut value = value.clone();
+            value["entries"][id]["command"]["dependencies"] =
+                serde_json::json!([{ "id": dependency }]);
+            assert!(TransactionBuilder::from_json(&value.to_string()).is_err());
+        }
+    }
+
+    #[cfg(feature = "intents")]
+    #[test]
+    fn intents_round_trip() {
+        use crate::intent::CoinWithBalance;
+
+        let mut tx = builder();
+        let coin = tx.intent(CoinWithBalance::myso(10).with_use_address_balance(false));
+        let recipient = tx.pure(&Address::from_static("0xdef"));
+        tx.transfer_objects(vec![coin], recipient);
+
+        let json = tx.to_json().unwrap();
+        let restored = TransactionBuilder::from_json(&json).unwrap();
+        assert_eq!(restored.intents.len(), 1);
+        assert_eq!(restored.resolvers.len(), 1);
+        assert_eq!(restored.to_json().unwrap(), json);
+
+        let bytes = tx.to_bcs().unwrap();
+        let restored = TransactionBuilder::from_bcs(&bytes).unwrap();
+        assert_eq!(restored.intents.len(), 1);
+        assert_eq!(restored.to_json().unwrap(), json);
+
+        // intents can't be resolved offline
+        restored.try_build().unwrap_err();
+    }
+
+    #[cfg(feature = "intents")]
+    #[test]
+    fn validate_move_calls_round_trip() {
+        let mut tx = builder();
+        tx.set_validate_move_calls(true);
+        let json = tx.to_json().unwrap();
+        assert!(
+            TransactionBuilder::from_json(&json)
+                .unwrap()
+                .validate_move_calls
+        );
+
+        let json = builder().to_json().unwrap();
+        assert!(
+            !TransactionBuilder::from_json(&json)
+                .unwrap()
+                .validate_move_calls
+        );
+    }
+}
@@ -0,0 +1,196 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::Error;
+use myso_sdk_types::Address;
+use myso_sdk_types::GasPayment;
+use myso_sdk_types::SignedTransaction;
+use myso_sdk_types::Transaction;
+use myso_sdk_types::TransactionExpiration;
+use myso_sdk_types::TransactionKind;
+use myso_sdk_types::UserSignature;
+
+/// A transaction whose gas is paid for by a sponsor, along with the signatures collected so far.
+///
+/// A sponsored transaction is typically produced by building only the
+/// [`TransactionKind`] of a transaction (see [`TransactionBuilder::try_build_kind`]), which is
+/// then combined with a gas payment owned by the sponsor. The resulting transaction must be
+/// signed by both the sender and the sponsor before it can be executed.
+///
+/// Signatures are not verified when added, only matched to either the sender or the sponsor based
+/// on the address they were produced by.
+///
+/// [`TransactionBuilder::try_build_kind`]: crate::TransactionBuilder::try_build_kind
+#[derive(Clone, Debug)]
+pub struct SponsoredTransaction {
+    transaction: Transaction,
+    sender_signature: Option<UserSignature>,
+    sponsor_signature: Option<UserSignature>,
+}
+
+impl SponsoredTransaction {
+    /// Combine a transaction kind with the sponsor's gas payment. The owner of the gas payment is
+    /// the sponsor of the transaction.
+    pub fn new(kind: TransactionKind, sender: Address, gas_payment: GasPayment) -> Self {
+        Self {
+            transaction: Transaction {
+                kind,
+                sender,
+                gas_payment,
+                expiration: TransactionExpiration::None,
+            },
+            sender_signature: None,
+            sponsor_signature: None,
+        }
+    }
+
+    /// Set the expiration of the transaction.
+    ///
+    /// Any signatures which have already been collected are discarded as they would no longer be
+    /// valid for the modified transaction.
+    pub fn with_expiration(mut self, expiration: TransactionExpiration) -> Self {
+        self.transaction.expiration = expiration;
+        self.sender_signature = None;
+        self.sponsor_signature = None;
+        self
+    }
+
+    /// The transaction to be signed by both the sender and the sponsor.
+    pub fn transaction(&self) -> &Transaction {
+        &self.transaction
+    }
+
+    pub fn sender(&self) -> Address {
+        self.transaction.sender
+    }
+
+    pub fn sponsor(&self) -> Address {
+        self.transaction.gas_payment.owner
+    }
+
+    pub fn sender_signature(&self) -> Option<&UserSignature> {
+        self.sender_signature.as_ref()
+    }
+
+    pub fn sponsor_signature(&self) -> Option<&UserSignature> {
+        self.sponsor_signature.as_ref()
+    }
+
+    /// Add a signature from either the sender or the sponsor, replacing any previous signature
+    /// from the same party.
+    ///
+    /// Returns an error if the signature was produced by neither the sender nor the sponsor. If
+    /// the sender is also the sponsor the signature is used for both.
+    pub fn add_signature(&mut self, signature: UserSignature) -> Result<(), Error> {
+        let signed_by = |address: Address| signature.derive_addresses().any(|a| a == address);
+        let is_sender = signed_by(self.sender());
+        let is_sponsor = signed_by(self.sponsor());
+
+        if !is_sender && !is_sponsor {
+            return Err(Error::Input(
+                "signature is from neither the sender nor the sponsor".to_owned(),
+            ));
+        }
+
+        if is_sender {
+            self.sender_signature = Some(signature.clone());
+        }
+        if is_sponsor {
+            self.sponsor_signature = Some(signature);
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if signatures from both the sender and the sponsor have been collected.
+    pub fn is_complete(&self) -> bool {
+        self.sender_signature.is_some() && self.sponsor_signature.is_some()
+    }
+
+    /// Finish collecting signatures, producing a transaction ready to be executed.
+    pub fn finish(self) -> Result<SignedTransaction, Error> {
+        let sender_signature = self
+            .sender_signature
+            .ok_or_else(|| Error::Input("missing signature from the sender".to_owned()))?;
+        let sponsor_signature = self
+            .sponsor_signature
+            .ok_or_else(|| Error::Input("missing signature from the sponsor".to_owned()))?;
+
+        let signatures = if self.transaction.sender == self.transaction.gas_payment.owner {
+            vec![sender_signature]
+        } else {
+            vec![sender_signature, sponsor_signature]
+        };
+
+        Ok(SignedTransaction {
+            transaction: self.transaction,
+            signatures,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ObjectInput;
+    use crate::TransactionBuilder;
+    use myso_sdk_types::Digest;
+    use myso_sdk_types::Ed25519PublicKey;
+    use myso_sdk_types::Ed25519Signature;
+    use myso_sdk_types::ObjectReference;
+    use myso_sdk_types::SimpleSignature;
+
+    fn signature(public_key: [u8; 32]) -> UserSignature {
+        UserSignature::Simple(SimpleSignature::Ed25519 {
+            signature: Ed25519Signature::new([0; 64]),
+            public_key: Ed25519PublicKey::new(public_key),
+        })
+    }
+
+    #[test]
+    fn collect_signatures() {
+        let sender_signature = signature([1; 32]);
+        let sponsor_signature = signature([2; 32]);
+        let sender = sender_signature.derive_address();
+        let sponsor = sponsor_signature.derive_address();
+
+        let mut tx = TransactionBuilder::new();
+        let coin = tx.object(ObjectInput::owned(
+            Address::from_static("0x12345"),
+            2,
+            Digest::from_static("7opR9rFUYivSTqoJHvFb9p6p54THyHTatMG6id4JKZR9"),
+        ));
+        let recipient = tx.pure(&Address::from_static("0xabc"));
+        tx.transfer_objects(vec![coin], recipient);
+        let kind = tx.try_build_kind().unwrap();
+
+        let mut sponsored = SponsoredTransaction::new(
+            kind,
+            sender,
+            GasPayment {
+                objects: vec![ObjectReference::new(
+                    Address::from_static("0xd879"),
+                    2,
+                    Digest::from_static("2ZigdvsZn5BMeszscPQZq9z8ebnS2FpmAuRbAi9ednCk"),
+                )],
+                owner: sponsor,
+                price: 1000,
+                budget: 500000000,
+            },
+        );
+
+        sponsored.add_signature(signature([3; 32])).unwrap_err();
+        sponsored.add_signature(sponsor_signature.clone()).unwrap();
+        assert!(!sponsored.is_complete());
+        sponsored.clone().finish().unwrap_err();
+
+        sponsored.add_signature(sender_signature.clone()).unwrap();
+        assert!(sponsored.is_complete());
+
+        let signed = sponsored.finish().unwrap();
+        assert_eq!(signed.signatures, [sender_signature, sponsor_signature]);
+        assert_eq!(signed.transaction.sender, sender);
+        assert_eq!(signed.transaction.gas_payment.owner, sponsor);
+    }
+}
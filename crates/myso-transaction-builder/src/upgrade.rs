@@ -0,0 +1,349 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::Argument;
+use crate::Function;
+use crate::ObjectInput;
+use crate::TransactionBuilder;
+use myso_sdk_types::Address;
+use myso_sdk_types::Digest;
+use myso_sdk_types::Identifier;
+use myso_sdk_types::hash::Hasher;
+
+const PACKAGE_MODULE: Identifier = Identifier::from_static("package");
+
+/// The policy an `UpgradeCap` restricts upgrades of its package to.
+///
+/// Policies are ordered from least to most restrictive and a cap's policy can only ever be made
+/// more restrictive.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum UpgradePolicy {
+    /// Any upgrade which is compatible with the previous version of the package.
+    #[default]
+    Compatible,
+    /// Upgrades which only add new functions and types to the package.
+    Additive,
+    /// Upgrades which only change the package's dependencies.
+    DepOnly,
+}
+
+impl UpgradePolicy {
+    /// The value of the policy in `0x2::package`.
+    pub fn as_u8(self) -> u8 {
+        match self {
+            UpgradePolicy::Compatible => 0,
+            UpgradePolicy::Additive => 128,
+            UpgradePolicy::DepOnly => 192,
+        }
+    }
+
+    pub fn from_u8(policy: u8) -> Option<Self> {
+        match policy {
+            0 => Some(UpgradePolicy::Compatible),
+            128 => Some(UpgradePolicy::Additive),
+            192 => Some(UpgradePolicy::DepOnly),
+            _ => None,
+        }
+    }
+}
+
+/// Compute the digest of a package which is authorized by `0x2::package::authorize_upgrade`.
+///
+/// The digest commits to the bytecode of every module and the IDs of every transitive
+/// dependency of the package, regardless of the order they are provided in.
+pub fn package_digest<M>(modules: &[M], dependencies: &[Address]) -> Digest
+where
+    M: AsRef<[u8]>,
+{
+    let module_digests = modules
+        .iter()
+        .map(|module| Hasher::digest(module).into_inner())
+        .collect::<Vec<_>>();
+
+    let mut components = module_digests
+        .iter()
+        .map(|digest| digest.as_slice())
+        .chain(dependencies.iter().map(Address::as_bytes))
+        .collect::<Vec<_>>();
+    components.sort();
+
+    let mut hasher = Hasher::new();
+    for component in components {
+        hasher.update(component);
+    }
+    hasher.finalize()
+}
+
+/// An upgrade of a Move package, authorized by the package's `UpgradeCap`.
+///
+/// Adding the upgrade to a transaction produces the full sequence of commands needed to upgrade a
+/// package: authorizing the upgrade with `0x2::package::authorize_upgrade`, the upgrade itself
+/// and committing it with `0x2::package::commit_upgrade`.
+///
+/// ```
+/// use myso_sdk_types::Address;
+/// use myso_transaction_builder::ObjectInput;
+/// use myso_transaction_builder::PackageUpgrade;
+/// use myso_transaction_builder::TransactionBuilder;
+/// use myso_transaction_builder::UpgradePolicy;
+///
+/// # let modules = vec![vec![0xa1, 0x1c, 0xeb, 0x0b]];
+/// let mut tx = TransactionBuilder::new();
+/// let upgrade_cap = PackageUpgrade::new(
+///     ObjectInput::new(Address::from_static("0xca9")),
+///     Address::from_static("0x1234"),
+///     modules,
+///     vec![Address::from_static("0x1"), Address::from_static("0x2")],
+/// )
+/// .add_to(&mut tx);
+///
+/// // Only allow additive upgrades from now on
+/// tx.restrict_upgrade_policy(upgrade_cap, UpgradePolicy::Additive);
+/// ```
+pub struct PackageUpgrade {
+    upgrade_cap: ObjectInput,
+    package: Address,
+    modules: Vec<Vec<u8>>,
+    dependencies: Vec<Address>,
+    policy: UpgradePolicy,
+}
+
+impl PackageUpgrade {
+    /// Upgrade `package` to the provided modules and transitive dependencies.
+    ///
+    /// The upgrade is authorized with the [`UpgradePolicy::Compatible`] policy unless another
+    /// policy is provided with [`PackageUpgrade::with_policy`].
+    pub fn new(
+        upgrade_cap: ObjectInput,
+        package: Address,
+        modules: Vec<Vec<u8>>,
+        dependencies: Vec<Address>,
+    ) -> Self {
+        Self {
+            upgrade_cap,
+            package,
+            modules,
+            dependencies,
+            policy: UpgradePolicy::Compatible,
+        }
+    }
+
+    /// Fetch the `UpgradeCap` to determine the package it upgrades and its current policy, which
+    /// the upgrade is authorized with.
+    #[cfg(feature = "intents")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "intents")))]
+    pub async fn fetch(
+        client: &mut myso_rpc::Client,
+        upgrade_cap: Address,
+        modules: Vec<Vec<u8>>,
+        dependencies: Vec<Address>,
+    ) -> Result<Self, crate::Error> {
+        use crate::Error;
+        use myso_rpc::field::FieldMask;
+        use myso_rpc::field::FieldMaskUtil;
+        use myso_rpc::proto::myso::rpc::v2::GetObjectRequest;
+
+        let read_mask =
+            FieldMask::from_paths(["object_id", "version", "digest", "owner", "contents"]);
+        let response = client
+            .ledger_client()
+            .get_object(GetObjectRequest::new(&upgrade_cap).with_read_mask(read_mask))
+            .await
+            .map_err(|e| Error::Input(format!("error fetching object {upgrade_cap}: {e}")))?
+            .into_inner();
+        let object = response.object();
+
+        // The fields of `0x2::package::UpgradeCap`: `id`, `package`, `version` and `policy`
+        let (_, package, _, policy): (Address, Address, u64, u8) = object
+            .contents()
+            .deserialize()
+            .map_err(|e| Error::Input(format!("{upgrade_cap} is not an UpgradeCap: {e}")))?;
+        let policy = UpgradePolicy::from_u8(policy)
+            .ok_or_else(|| Error::Input(format!("unknown upgrade policy {policy}")))?;
+
+        Ok(Self::new(
+            ObjectInput::try_from_object_proto(object)?,
+            package,
+            modules,
+            dependencies,
+        )
+        .with_policy(policy))
+    }
+
+    /// Set the policy the upgrade is authorized with.
+    ///
+    /// The policy must be at least as restrictive as the `UpgradeCap`'s current policy.
+    pub fn with_policy(self, policy: UpgradePolicy) -> Self {
+        Self { policy, ..self }
+    }
+
+    /// The digest of the upgraded package, see [`package_digest`].
+    pub fn digest(&self) -> Digest {
+        package_digest(&self.modules, &self.dependencies)
+    }
+
+    /// Add the commands authorizing, performing and committing the upgrade to a transaction.
+    ///
+    /// Returns the `UpgradeCap` argument so that its policy can be restricted further, for
+    /// example with [`TransactionBuilder::restrict_upgrade_policy`].
+    pub fn add_to(self, builder: &mut TransactionBuilder) -> Argument {
+        let digest = self.digest();
+        let upgrade_cap = builder.object(self.upgrade_cap.with_mutable(true));
+        let policy = builder.pure(&self.policy.as_u8());
+        let digest = builder.pure(&digest.as_bytes());
+
+        let ticket = builder.move_call(
+            package_function("authorize_upgrade"),
+            vec![upgrade_cap, policy, digest],
+        );
+        let receipt = builder.upgrade(self.modules, self.dependencies, self.package, ticket);
+        builder.move_call(
+            package_function("commit_upgrade"),
+            vec![upgrade_cap, receipt],
+        );
+
+        upgrade_cap
+    }
+}
+
+impl TransactionBuilder {
+    /// Restrict the upgrades an `UpgradeCap` authorizes to the provided policy.
+    ///
+    /// Restricting to [`UpgradePolicy::Compatible`] adds no commands as it is the least
+    /// restrictive policy.
+    pub fn restrict_upgrade_policy(&mut self, upgrade_cap: Argument, policy: UpgradePolicy) {
+        let function = match policy {
+            UpgradePolicy::Compatible => return,
+            UpgradePolicy::Additive => "only_additive_upgrades",
+            UpgradePolicy::DepOnly => "only_dep_upgrades",
+        };
+        self.move_call(package_function(function), vec![upgrade_cap]);
+    }
+
+    /// Make a package immutable by destroying its `UpgradeCap`, preventing any further upgrades.
+    pub fn make_package_immutable(&mut self, upgrade_cap: Argument) {
+        self.move_call(package_function("make_immutable"), vec![upgrade_cap]);
+    }
+}
+
+fn package_function(function: &'static str) -> Function {
+    Function::new(
+        Address::TWO,
+        PACKAGE_MODULE,
+        Identifier::from_static(function),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use myso_sdk_types::Command;
+    use myso_sdk_types::Input;
+    use myso_sdk_types::TransactionKind;
+
+    #[test]
+    fn digest() {
+        let modules = [vec![1u8, 2, 3], vec![4, 5]];
+        let dependencies = [Address::from_static("0x1"), Address::TWO];
+        let digest = package_digest(&modules, &dependencies);
+
+        let mut components = modules
+            .iter()
+            .map(|module| Hasher::digest(module).into_inner())
+            .chain(
+                dependencies
+                    .iter()
+                    .map(|dependency| dependency.into_inner()),
+            )
+            .collect::<Vec<_>>();
+        components.sort();
+        assert_eq!(digest, Hasher::digest(components.concat()));
+
+        // The order of modules and dependencies doesn't matter
+        assert_eq!(
+            digest,
+            package_digest(
+                &[vec![4u8, 5], vec![1, 2, 3]],
+                &[Address::TWO, Address::from_static("0x1")]
+            )
+        );
+        assert_ne!(digest, package_digest(&modules[..1], &dependencies));
+    }
+
+    #[test]
+    fn upgrade_commands() {
+        let modules = vec![vec![1u8, 2, 3]];
+        let dependencies = vec![Address::from_static("0x1"), Address::TWO];
+        let upgrade = PackageUpgrade::new(
+            ObjectInput::owned(
+                Address::from_static("0xcafe"),
+                3,
+                Digest::from_static("7opR9rFUYivSTqoJHvFb9p6p54THyHTatMG6id4JKZR9"),
+            ),
+            Address::from_static("0x1234"),
+            modules.clone(),
+            dependencies.clone(),
+        )
+        .with_policy(UpgradePolicy::Additive);
+        let digest = upgrade.digest();
+
+        let mut tx = TransactionBuilder::new();
+        let upgrade_cap = upgrade.add_to(&mut tx);
+        tx.restrict_upgrade_policy(upgrade_cap, UpgradePolicy::Compatible);
+        tx.restrict_upgrade_policy(upgrade_cap, UpgradePolicy::DepOnly);
+        tx.make_package_immutable(upgrade_cap);
+
+        let TransactionKind::ProgrammableTransaction(ptb) = tx.try_build_kind().unwrap() else {
+            panic!("expected a programmable transaction");
+        };
+
+        assert_eq!(ptb.inputs.len(), 3);
+        assert_eq!(ptb.inputs[1], Input::Pure(vec![128]));
+        assert_eq!(
+            ptb.inputs[2],
+            Input::Pure(bcs::to_bytes(digest.as_bytes()).unwrap())
+        );
+
+        let functions = ptb
+            .commands
+            .iter()
+            .map(|command| match command {
+                Command::MoveCall(call) => {
+                    assert_eq!(call.package, Address::TWO);
+                    call.function.as_str()
+                }
+                Command::Upgrade(upgrade) => {
+                    assert_eq!(upgrade.modules, modules);
+                    assert_eq!(upgrade.dependencies, dependencies);
+                    assert_eq!(upgrade.package, Address::from_static("0x1234"));
+                    "upgrade"
+                }
+                _ => panic!("unexpected command"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            functions,
+            [
+                "authorize_upgrade",
+                "upgrade",
+                "commit_upgrade",
+                "only_dep_upgrades",
+                "make_immutable"
+            ]
+        );
+    }
+
+    #[test]
+    fn policy() {
+        for policy in [
+            UpgradePolicy::Compatible,
+            UpgradePolicy::Additive,
+            UpgradePolicy::DepOnly,
+        ] {
+            assert_eq!(UpgradePolicy::from_u8(policy.as_u8()), Some(policy));
+        }
+        assert_eq!(UpgradePolicy::from_u8(1), None);
+        assert!(UpgradePolicy::Compatible < UpgradePolicy::DepOnly);
+    }
+}
@@ -0,0 +1,705 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Opt-in validation of Move calls against the on-chain signatures of the functions they call.
+//!
+//! See [`TransactionBuilder::set_validate_move_calls`].
+
+use crate::TransactionBuilder;
+use crate::builder::CommandKind;
+use crate::builder::InputArg;
+use crate::builder::ObjectKind;
+use crate::error::Error;
+use myso_rpc::proto::myso::rpc::v2::Ability;
+use myso_rpc::proto::myso::rpc::v2::DatatypeDescriptor;
+use myso_rpc::proto::myso::rpc::v2::FunctionDescriptor;
+use myso_rpc::proto::myso::rpc::v2::GetDatatypeRequest;
+use myso_rpc::proto::myso::rpc::v2::GetFunctionRequest;
+use myso_rpc::proto::myso::rpc::v2::OpenSignatureBody;
+use myso_rpc::proto::myso::rpc::v2::open_signature::Reference;
+use myso_rpc::proto::myso::rpc::v2::open_signature_body::Type;
+use myso_sdk_types::Address;
+use myso_sdk_types::Identifier;
+use myso_sdk_types::StructTag;
+use myso_sdk_types::TypeTag;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::hash_map::Entry;
+
+type ItemKey = (Address, Identifier, Identifier);
+
+const STD: Address = Address::from_static("0x1");
+
+/// Check every Move call in `builder` against the signature of the function it calls, and infer
+/// the mutability of shared object inputs whose mutability hasn't been explicitly set.
+pub(crate) async fn validate_move_calls(
+    builder: &mut TransactionBuilder,
+    client: &mut myso_rpc::Client,
+) -> Result<(), Error> {
+    let mut functions: HashMap<ItemKey, FunctionDescriptor> = HashMap::new();
+    let mut datatypes: HashMap<ItemKey, DatatypeDescriptor> = HashMap::new();
+
+    // Fetch the signatures of every called function along with the declarations of every
+    // datatype used as a type argument, which are needed to determine their abilities.
+    for command in builder.commands.values() {
+        let CommandKind::MoveCall(call) = &command.kind else {
+            continue;
+        };
+
+        let key = (call.package, call.module.clone(), call.function.clone());
+        if let Entry::Vacant(entry) = functions.entry(key) {
+            let function = client
+                .package_client()
+                .get_function(
+                    GetFunctionRequest::default()
+                        .with_package_id(call.package.to_string())
+                        .with_module_name(call.module.as_str())
+                        .with_name(call.function.as_str()),
+                )
+                .await
+                .map_err(|e| {
+                    move_call_error(
+                        entry.key(),
+                        format!("unable to fetch the function's signature: {e}"),
+                    )
+                })?
+                .into_inner()
+                .function
+                .unwrap_or_default();
+            entry.insert(function);
+        }
+
+        let mut structs = Vec::new();
+        for type_argument in &call.type_arguments {
+            collect_structs(type_argument, &mut structs);
+        }
+        for tag in structs {
+            let key = (*tag.address(), tag.module().clone(), tag.name().clone());
+            if datatypes.contains_key(&key) {
+                continue;
+            }
+            let datatype = client
+                .package_client()
+                .get_datatype(
+                    GetDatatypeRequest::default()
+                        .with_package_id(key.0.to_string())
+                        .with_module_name(key.1.as_str())
+                        .with_name(key.2.as_str()),
+                )
+                .await
+                .map_err(|e| Error::Input(format!("unable to fetch datatype {tag}: {e}")))?
+                .into_inner()
+                .datatype
+                .unwrap_or_default();
+            datatypes.insert(key, datatype);
+        }
+    }
+
+    let inputs = builder
+        .inputs
+        .values()
+        .map(|(id, input)| (*id, input))
+        .collect::<HashMap<_, _>>();
+
+    // Whether each object input is required to be mutable by the commands it is used in
+    let mut object_mutability: HashMap<usize, bool> = HashMap::new();
+    // Object inputs used by commands other than Move calls, which always require mutable access
+    let mut used_outside_move_calls = HashSet::new();
+
+    for command in builder.commands.values() {
+        let call = match &command.kind {
+            CommandKind::MoveCall(call) => call,
+            other => {
+                used_outside_move_calls
+                    .extend(command_arguments(other).map(|argument| argument.id));
+                continue;
+            }
+        };
+
+        let key = (call.package, call.module.clone(), call.function.clone());
+        let function = &functions[&key];
+
+        if function.type_parameters.len() != call.type_arguments.len() {
+            return Err(move_call_error(
+                &key,
+                format!(
+                    "expected {} type arguments but {} were provided",
+                    function.type_parameters.len(),
+                    call.type_arguments.len()
+                ),
+            ));
+        }
+
+        for (index, (parameter, type_argument)) in function
+            .type_parameters
+            .iter()
+            .zip(&call.type_arguments)
+            .enumerate()
+        {
+            let abilities = abilities(type_argument, &datatypes)
+                .map_err(|reason| move_call_error(&key, reason))?;
+            if let Some(missing) = parameter
+                .constraints()
+                .find(|constraint| !abilities.has(*constraint))
+            {
+                return Err(move_call_error(
+                    &key,
+                    format!(
+                        "type argument {index} ({type_argument}) is missing the {} ability",
+                        missing.as_str_name().to_lowercase()
+                    ),
+                ));
+            }
+        }
+
+        // A trailing `&mut TxContext` or `&TxContext` parameter is supplied by the runtime
+        let mut parameters = function.parameters.as_slice();
+        if let Some((last, rest)) = parameters.split_last()
+            && is_tx_context(last.body())
+        {
+            parameters = rest;
+        }
+
+        if parameters.len() != call.arguments.len() {
+            return Err(move_call_error(
+                &key,
+                format!(
+                    "expected {} arguments but {} were provided",
+                    parameters.len(),
+                    call.arguments.len()
+                ),
+            ));
+        }
+
+        for (index, (parameter, argument)) in parameters.iter().zip(&call.arguments).enumerate() {
+            // Only inputs can be checked, the types of command results are unknown
+            let Some(input) = inputs
+                .get(&argument.id)
+                .filter(|_| argument.sub_index.is_none())
+            else {
+                continue;
+            };
+
+            let type_ = signature_type(parameter.body(), &call.type_arguments)
+                .map_err(|reason| move_call_error(&key, reason))?;
+            let by_value = !matches!(
+                parameter.reference(),
+                Reference::Immutable | Reference::Mutable
+            );
+
+            match input {
+                InputArg::Pure(bytes) => {
+                    check_pure(bytes, &type_).map_err(|reason| {
+                        move_call_error(&key, format!("argument {index}: {reason}"))
+                    })?;
+                }
+                InputArg::Gas if by_value => {
+                    return Err(move_call_error(
+                        &key,
+                        format!("argument {index}: the gas coin cannot be passed by value"),
+                    ));
+                }
+                InputArg::Object(object) => {
+                    if is_pure_type(&type_) {
+                        return Err(move_call_error(
+                            &key,
+                            format!(
+                                "argument {index}: object {} passed for a value of type {type_}",
+                                object.object_id
+                            ),
+                        ));
+                    }
+                    if matches!(object.kind, Some(ObjectKind::Receiving)) && !is_receiving(&type_) {
+                        return Err(move_call_error(
+                            &key,
+                            format!(
+                                "argument {index}: receiving object {} passed for a value of type {type_}",
+                                object.object_id
+                            ),
+                        ));
+                    }
+
+                    let mutable = parameter.reference() != Reference::Immutable;
+                    *object_mutability.entry(argument.id).or_default() |= mutable;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    for (id, input) in builder.inputs.values_mut() {
+        let InputArg::Object(object) = input else {
+            continue;
+        };
+        let Some(mutable) = object_mutability.get(id) else {
+            continue;
+        };
+        let mutable = *mutable || used_outside_move_calls.contains(id);
+
+        let maybe_shared = match object.kind {
+            Some(ObjectKind::Shared) => true,
+            None => object.digest.is_none(),
+            _ => false,
+        };
+        if !maybe_shared {
+            continue;
+        }
+
+        match object.mutable {
+            None => object.mutable = Some(mutable),
+            Some(false) if mutable => {
+                return Err(Error::Input(format!(
+                    "shared object {} is used mutably but was provided as immutable",
+                    object.object_id
+                )));
+            }
+            Some(_) => {}
+        }
+    }
+
+    Ok(())
+}
+
+fn move_call_error(key: &ItemKey, reason: String) -> Error {
+    Error::InvalidMoveCall {
+        function: format!("{}::{}::{}", key.0, key.1, key.2),
+        reason,
+    }
+}
+
+fn command_arguments(command: &CommandKind) -> impl Iterator<Item = &crate::Argument> {
+    let arguments: Vec<&crate::Argument> = match command {
+        CommandKind::MoveCall(call) => call.arguments.iter().collect(),
+        CommandKind::TransferObjects(transfer) => transfer
+            .objects
+            .iter()
+            .chain(std::iter::once(&transfer.address))
+            .collect(),
+        CommandKind::SplitCoins(split) => {
+            std::iter::once(&split.coin).chain(&split.amounts).collect()
+        }
+        CommandKind::MergeCoins(merge) => std::iter::once(&merge.coin)
+            .chain(&merge.coins_to_merge)
+            .collect(),
+        CommandKind::MakeMoveVector(vector) => vector.elements.iter().collect(),
+        CommandKind::Upgrade(upgrade) => vec![&upgrade.ticket],
+        CommandKind::Publish(_) => Vec::new(),
+    };
+    arguments.into_iter()
+}
+
+fn collect_structs<'a>(type_: &'a TypeTag, structs: &mut Vec<&'a StructTag>) {
+    match type_ {
+        TypeTag::Vector(inner) => collect_structs(inner, structs),
+        TypeTag::Struct(tag) => {
+            structs.push(tag);
+            for param in tag.type_params() {
+                collect_structs(param, structs);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn is_tx_context(body: &OpenSignatureBody) -> bool {
+    body.r#type() == Type::Datatype
+        && body.type_name().parse::<StructTag>().is_ok_and(|tag| {
+            tag.address() == &Address::TWO
+                && tag.module().as_str() == "tx_context"
+                && tag.name().as_str() == "TxContext"
+        })
+}
+
+fn is_receiving(type_: &TypeTag) -> bool {
+    matches!(
+        type_,
+        TypeTag::Struct(tag) if tag.address() == &Address::TWO
+            && tag.module().as_str() == "transfer"
+            && tag.name().as_str() == "Receiving"
+    )
+}
+
+/// Instantiate the type of a parameter with the type arguments of a call.
+fn signature_type(body: &OpenSignatureBody, type_arguments: &[TypeTag]) -> Result<TypeTag, String> {
+    let type_ = match body.r#type() {
+        Type::Address => TypeTag::Address,
+        Type::Bool => TypeTag::Bool,
+        Type::U8 => TypeTag::U8,
+        Type::U16 => TypeTag::U16,
+        Type::U32 => TypeTag::U32,
+        Type::U64 => TypeTag::U64,
+        Type::U128 => TypeTag::U128,
+        Type::U256 => TypeTag::U256,
+        Type::Vector => {
+            let element = body
+                .type_parameter_instantiation
+                .first()
+                .ok_or_else(|| "vector type without an element type".to_owned())?;
+            TypeTag::Vector(Box::new(signature_type(element, type_arguments)?))
+        }
+        Type::Datatype => {
+            let tag = body
+                .type_name()
+                .parse::<StructTag>()
+                .map_err(|e| format!("invalid datatype {}: {e}", body.type_name()))?;
+            let type_params = body
+                .type_parameter_instantiation
+                .iter()
+                .map(|param| signature_type(param, type_arguments))
+                .collect::<Result<_, _>>()?;
+            TypeTag::Struct(Box::new(StructTag::new(
+                *tag.address(),
+                tag.module().clone(),
+                tag.name().clone(),
+                type_params,
+            )))
+        }
+        Type::Parameter => type_arguments
+            .get(body.type_parameter() as usize)
+            .cloned()
+            .ok_or_else(|| format!("unknown type parameter {}", body.type_parameter()))?,
+        _ => return Err("unknown parameter type".to_owned()),
+    };
+    Ok(type_)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct AbilitySet(u8);
+
+impl AbilitySet {
+    const EMPTY: Self = Self(0);
+    const PRIMITIVES: Self =
+        Self(1 << Ability::Copy as u8 | 1 << Ability::Drop as u8 | 1 << Ability::Store as u8);
+
+    fn with(self, ability: Ability) -> Self {
+        Self(self.0 | 1 << ability as u8)
+    }
+
+    fn without(self, ability: Ability) -> Self {
+        Self(self.0 & !(1 << ability as u8))
+    }
+
+    fn has(self, ability: Ability) -> bool {
+        self.0 & (1 << ability as u8) != 0
+    }
+}
+
+/// Compute the abilities of a fully instantiated type.
+fn abilities(
+    type_: &TypeTag,
+    datatypes: &HashMap<ItemKey, DatatypeDescriptor>,
+) -> Result<AbilitySet, String> {
+    let abilities = match type_ {
+        TypeTag::Signer => AbilitySet::EMPTY.with(Ability::Drop),
+        TypeTag::Vector(inner) => {
+            AbilitySet(abilities(inner, datatypes)?.0 & AbilitySet::PRIMITIVES.0)
+        }
+        TypeTag::Struct(tag) => {
+            let datatype = datatypes
+                .get(&(*tag.address(), tag.module().clone(), tag.name().clone()))
+                .ok_or_else(|| format!("unknown datatype {tag}"))?;
+
+            if datatype.type_parameters.len() != tag.type_params().len() {
+                return Err(format!(
+                    "datatype {tag} expects {} type arguments",
+                    datatype.type_parameters.len()
+                ));
+            }
+
+            let mut declared = datatype
+                .abilities()
+                .fold(AbilitySet::EMPTY, AbilitySet::with);
+
+            // A declared ability is only present if every non-phantom type argument has the
+            // ability required by it
+            for (parameter, argument) in datatype.type_parameters.iter().zip(tag.type_params()) {
+                if parameter.is_phantom() {
+                    continue;
+                }
+                let argument = abilities(argument, datatypes)?;
+                for ability in [Ability::Copy, Ability::Drop, Ability::Store] {
+                    if !argument.has(ability) {
+                        declared = declared.without(ability);
+                    }
+                }
+                if !argument.has(Ability::Store) {
+                    declared = declared.without(Ability::Key);
+                }
+            }
+            declared
+        }
+        _ => AbilitySet::PRIMITIVES,
+    };
+    Ok(abilities)
+}
+
+/// Returns `true` if values of this type can be provided as a pure input.
+fn is_pure_type(type_: &TypeTag) -> bool {
+    match type_ {
+        TypeTag::Signer => false,
+        TypeTag::Vector(inner) => is_pure_type(inner),
+        TypeTag::Struct(tag) => match pure_struct(tag) {
+            Some(PureStruct::Option(inner)) => is_pure_type(inner),
+            Some(_) => true,
+            None => false,
+        },
+        _ => true,
+    }
+}
+
+enum PureStruct<'a> {
+    Utf8String,
+    AsciiString,
+    Id,
+    Option(&'a TypeTag),
+}
+
+fn pure_struct(tag: &StructTag) -> Option<PureStruct<'_>> {
+    let pure = match (
+        tag.address(),
+        tag.module().as_str(),
+        tag.name().as_str(),
+        tag.type_params(),
+    ) {
+        (&STD, "string", "String", []) => PureStruct::Utf8String,
+        (&STD, "ascii", "String", []) => PureStruct::AsciiString,
+        (&STD, "option", "Option", [inner]) => PureStruct::Option(inner),
+        (&Address::TWO, "object", "ID", []) => PureStruct::Id,
+        _ => return None,
+    };
+    Some(pure)
+}
+
+/// Check that `bytes` is a valid BCS encoding of a value of type `type_`.
+fn check_pure(bytes: &[u8], type_: &TypeTag) -> Result<(), String> {
+    if !is_pure_type(type_) {
+        return Err(format!("a pure value cannot be passed for type {type_}"));
+    }
+
+    let mut remaining = bytes;
+    check_pure_value(&mut remaining, type_)
+        .ok_or_else(|| format!("pure value is not a valid {type_}"))?;
+    if !remaining.is_empty() {
+        return Err(format!("pure value is not a valid {type_}: trailing bytes"));
+    }
+    Ok(())
+}
+
+fn check_pure_value(bytes: &mut &[u8], type_: &TypeTag) -> Option<()> {
+    match type_ {
+        TypeTag::Bool => match take(bytes, 1)? {
+            [0 | 1] => Some(()),
+            _ => None,
+        },
+        TypeTag::U8 => take(bytes, 1).map(drop),
+        TypeTag::U16 => take(bytes, 2).map(drop),
+        TypeTag::U32 => take(bytes, 4).map(drop),
+        TypeTag::U64 => take(bytes, 8).map(drop),
+        TypeTag::U128 => take(bytes, 16).map(drop),
+        TypeTag::U256 => take(bytes, 32).map(drop),
+        TypeTag::Address => take(bytes, Address::LENGTH).map(drop),
+        TypeTag::Signer => None,
+        TypeTag::Vector(inner) => {
+            let len = read_length(bytes)?;
+            for _ in 0..len {
+                check_pure_value(bytes, inner)?;
+            }
+            Some(())
+        }
+        TypeTag::Struct(tag) => match pure_struct(tag)? {
+            PureStruct::Utf8String => {
+                let len = read_length(bytes)?;
+                std::str::from_utf8(take(bytes, len)?).ok().map(drop)
+            }
+            PureStruct::AsciiString => {
+                let len = read_length(bytes)?;
+                take(bytes, len)?.is_ascii().then_some(())
+            }
+            PureStruct::Id => take(bytes, Address::LENGTH).map(drop),
+            PureStruct::Option(inner) => match read_length(bytes)? {
+                0 => Some(()),
+                1 => check_pure_value(bytes, inner),
+                _ => None,
+            },
+        },
+    }
+}
+
+fn take<'a>(bytes: &mut &'a [u8], len: usize) -> Option<&'a [u8]> {
+    if bytes.len() < len {
+        return None;
+    }
+    let (taken, rest) = bytes.split_at(len);
+    *bytes = rest;
+    Some(taken)
+}
+
+/// Read a ULEB128 encoded length as used by BCS for sequences.
+fn read_length(bytes: &mut &[u8]) -> Option<usize> {
+    let mut value: u64 = 0;
+    for shift in (0..32).step_by(7) {
+        let [byte] = take(bytes, 1)? else {
+            return None;
+        };
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            // BCS requires the canonical, shortest, encoding of a length
+            if shift != 0 && *byte == 0 {
+                return None;
+            }
+            return (value <= u32::MAX as u64 >> 1).then_some(value as usize);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use myso_rpc::proto::myso::rpc::v2::TypeParameter;
+
+    fn type_(s: &str) -> TypeTag {
+        s.parse().unwrap()
+    }
+
+    fn body(type_: Type) -> OpenSignatureBody {
+        OpenSignatureBody::default().with_type(type_)
+    }
+
+    fn datatype(abilities: &[Ability], type_parameters: &[bool]) -> DatatypeDescriptor {
+        let mut datatype = DatatypeDescriptor::default();
+        datatype.abilities = abilities.iter().map(|ability| *ability as i32).collect();
+        datatype.type_parameters = type_parameters
+            .iter()
+            .map(|is_phantom| TypeParameter::default().with_is_phantom(*is_phantom))
+            .collect();
+        datatype
+    }
+
+    #[test]
+    fn pure_values() {
+        check_pure(&bcs::to_bytes(&true).unwrap(), &TypeTag::Bool).unwrap();
+        check_pure(&[2], &TypeTag::Bool).unwrap_err();
+        check_pure(&bcs::to_bytes(&5u64).unwrap(), &TypeTag::U64).unwrap();
+        check_pure(&bcs::to_bytes(&5u32).unwrap(), &TypeTag::U64).unwrap_err();
+        check_pure(&bcs::to_bytes(&5u64).unwrap(), &TypeTag::U32).unwrap_err();
+        check_pure(&bcs::to_bytes(&Address::TWO).unwrap(), &TypeTag::Address).unwrap();
+        check_pure(
+            &bcs::to_bytes(&Address::TWO).unwrap(),
+            &type_("0x2::object::ID"),
+        )
+        .unwrap();
+
+        check_pure(
+            &bcs::to_bytes(&vec![1u16, 2, 3]).unwrap(),
+            &type_("vector<u16>"),
+        )
+        .unwrap();
+        check_pure(
+            &bcs::to_bytes(&vec![vec![1u8], vec![]]).unwrap(),
+            &type_("vector<vector<u8>>"),
+        )
+        .unwrap();
+        check_pure(
+            &bcs::to_bytes(&vec![1u8, 2]).unwrap(),
+            &type_("vector<u16>"),
+        )
+        .unwrap_err();
+
+        check_pure(
+            &bcs::to_bytes("héllo").unwrap(),
+            &type_("0x1::string::String"),
+        )
+        .unwrap();
+        check_pure(
+            &bcs::to_bytes("héllo").unwrap(),
+            &type_("0x1::ascii::String"),
+        )
+        .unwrap_err();
+        check_pure(
+            &bcs::to_bytes(&vec![0xffu8]).unwrap(),
+            &type_("0x1::string::String"),
+        )
+        .unwrap_err();
+
+        check_pure(
+            &bcs::to_bytes(&Some(7u8)).unwrap(),
+            &type_("0x1::option::Option<u8>"),
+        )
+        .unwrap();
+        check_pure(
+            &bcs::to_bytes(&None::<u8>).unwrap(),
+            &type_("0x1::option::Option<u8>"),
+        )
+        .unwrap();
+        check_pure(
+            &bcs::to_bytes(&vec![1u8, 2]).unwrap(),
+            &type_("0x1::option::Option<u8>"),
+        )
+        .unwrap_err();
+
+        // objects can't be passed as pure values
+        check_pure(&[], &type_("0x2::coin::Coin<0x2::myso::MYSO>")).unwrap_err();
+        // non-canonical length
+        check_pure(&[0x80, 0x00], &type_("vector<u8>")).unwrap_err();
+    }
+
+    #[test]
+    fn instantiate_signature() {
+        let coin = body(Type::Datatype)
+            .with_type_name(
+                "0x0000000000000000000000000000000000000000000000000000000000000002::coin::Coin",
+            )
+            .with_type_parameter_instantiation(vec![body(Type::Parameter).with_type_parameter(0)]);
+        let vector = body(Type::Vector).with_type_parameter_instantiation(vec![coin]);
+
+        assert_eq!(
+            signature_type(&vector, &[type_("0x2::myso::MYSO")]).unwrap(),
+            type_("vector<0x2::coin::Coin<0x2::myso::MYSO>>")
+        );
+        signature_type(&vector, &[]).unwrap_err();
+        assert_eq!(
+            signature_type(&body(Type::U128), &[]).unwrap(),
+            TypeTag::U128
+        );
+
+        let tx_context = body(Type::Datatype).with_type_name(
+            "0x0000000000000000000000000000000000000000000000000000000000000002::tx_context::TxContext",
+        );
+        assert!(is_tx_context(&tx_context));
+        assert!(!is_tx_context(&body(Type::Address)));
+    }
+
+    #[test]
+    fn type_abilities() {
+        let key = |s: &str| {
+            let tag = s.parse::<StructTag>().unwrap();
+            (*tag.address(), tag.module().clone(), tag.name().clone())
+        };
+        let mut datatypes = HashMap::new();
+        datatypes.insert(
+            key("0x2::coin::Coin"),
+            datatype(&[Ability::Key, Ability::Store], &[true]),
+        );
+        datatypes.insert(key("0x2::myso::MYSO"), datatype(&[Ability::Drop], &[]));
+        datatypes.insert(
+            key("0x1::option::Option"),
+            datatype(&[Ability::Copy, Ability::Drop, Ability::Store], &[false]),
+        );
+
+        let coin = abilities(&type_("0x2::coin::Coin<0x2::myso::MYSO>"), &datatypes).unwrap();
+        assert!(coin.has(Ability::Key) && coin.has(Ability::Store) && !coin.has(Ability::Drop));
+
+        assert_eq!(
+            abilities(&type_("vector<u8>"), &datatypes).unwrap(),
+            AbilitySet::PRIMITIVES
+        );
+
+        // Option<T> only has the abilities of T
+        let option = abilities(&type_("0x1::option::Option<0x2::myso::MYSO>"), &datatypes).unwrap();
+        assert_eq!(option, AbilitySet::EMPTY.with(Ability::Drop));
+
+        abilities(&type_("0x2::unknown::Type"), &datatypes).unwrap_err();
+    }
+}